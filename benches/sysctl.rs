@@ -0,0 +1,57 @@
+//! Cost of reading a sysctl by name versus through a resolved handle
+//!
+//! `MemoryCollector::sysctl_u64` resolves each name to a MIB once and keeps
+//! the `Ctl`; this compares that against resolving on every read, which is
+//! what the collector did before. Only meaningful on FreeBSD.
+
+#[cfg(target_os = "freebsd")]
+mod freebsd {
+    use criterion::{black_box, criterion_group, Criterion};
+    use sysctl::Sysctl;
+
+    /// Counters read on every memory collection
+    const NAMES: &[&str] = &[
+        "hw.pagesize",
+        "vm.stats.vm.v_page_count",
+        "vm.stats.vm.v_active_count",
+        "vm.stats.vm.v_inactive_count",
+        "vm.stats.vm.v_free_count",
+    ];
+
+    fn lookups(c: &mut Criterion) {
+        let mut group = c.benchmark_group("sysctl");
+
+        group.bench_function("Ctl::new + value", |b| {
+            b.iter(|| {
+                for name in NAMES {
+                    let ctl = sysctl::Ctl::new(black_box(name)).expect("sysctl exists");
+                    black_box(ctl.value().expect("sysctl readable"));
+                }
+            })
+        });
+
+        let ctls: Vec<sysctl::Ctl> = NAMES
+            .iter()
+            .map(|name| sysctl::Ctl::new(name).expect("sysctl exists"))
+            .collect();
+        group.bench_function("cached Ctl value", |b| {
+            b.iter(|| {
+                for ctl in &ctls {
+                    black_box(ctl.value().expect("sysctl readable"));
+                }
+            })
+        });
+
+        group.finish();
+    }
+
+    criterion_group!(benches, lookups);
+}
+
+#[cfg(target_os = "freebsd")]
+criterion::criterion_main!(freebsd::benches);
+
+#[cfg(not(target_os = "freebsd"))]
+fn main() {
+    eprintln!("the sysctl benchmarks only run on FreeBSD");
+}
@@ -0,0 +1,206 @@
+//! Identifier scrubbing for `--anonymize`
+//!
+//! Rewrites the parts of a snapshot that identify a site before it is
+//! written out or shown, so a capture or screenshot can go into a public bug
+//! report: drive serials and multipath labels become stable hashes, pools
+//! become "pool-N", VMs and jails "vm-N" and "jail-N", and host names and
+//! jail addresses become documentation placeholders (example.com,
+//! 192.0.2.0/24 and the other RFC 5737 ranges, 2001:db8::/32). The same identifier always maps to the
+//! same replacement, so which paths belong to which device, which drives
+//! share a pool and the rest of the topology survive intact.
+
+use crate::collectors::{JailInfo, MultipathInfo, VmInfo, ZfsDriveInfo};
+use crate::domain::device::{MultipathDevice, PhysicalDisk};
+use crate::remote::Snapshot;
+use std::collections::HashMap;
+
+/// Host name shown in place of the real one
+pub const HOSTNAME: &str = "host.example.com";
+
+/// Hex digits kept from a serial's hash
+const HASH_DIGITS: usize = 12;
+
+/// RFC 5737 documentation networks, handed out in order
+const IPV4_DOCUMENTATION_NETS: [[u8; 3]; 3] = [[192, 0, 2], [198, 51, 100], [203, 0, 113]];
+
+/// Where IPv4 placeholders go once the documentation networks are used
+/// up: 198.18.0.0/15, reserved for benchmarking (RFC 2544)
+const IPV4_OVERFLOW_NET: u32 = 0xc612_0000;
+const IPV4_OVERFLOW_HOSTS: u32 = (1 << 17) - 2;
+
+/// Replacements handed out so far; keep one per run so every snapshot
+/// uses the same names
+#[derive(Debug, Default)]
+pub struct Anonymizer {
+    pools: HashMap<String, String>,
+    vms: HashMap<String, String>,
+    jails: HashMap<String, String>,
+    addresses: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scrub everything identifying in `snapshot`
+    pub fn snapshot(&mut self, snapshot: &mut Snapshot) {
+        for device in &mut snapshot.multipath_devices {
+            self.multipath_device(device);
+        }
+        for disk in &mut snapshot.standalone_disks {
+            self.disk(disk);
+        }
+        for vm in snapshot.vms.iter_mut().flatten() {
+            self.vm(vm);
+        }
+        for jail in snapshot.jails.iter_mut().flatten() {
+            self.jail(jail);
+        }
+        for usage in snapshot.pool_usage.iter_mut().flatten() {
+            usage.pool = self.pool(&usage.pool);
+        }
+        for dataset in snapshot.datasets.iter_mut().flatten() {
+            dataset.name = self.dataset(&dataset.name);
+        }
+        if snapshot.hostname.is_some() {
+            snapshot.hostname = Some(HOSTNAME.to_string());
+        }
+    }
+
+    pub fn multipath_device(&mut self, device: &mut MultipathDevice) {
+        device.name = device_name(&device.name);
+        device.ident = device.ident.as_deref().map(serial);
+        for partition in &mut device.partitions {
+            partition.name = device_name(&partition.name);
+        }
+        if let Some(ref mut zfs) = device.zfs_info {
+            self.zfs_info(zfs);
+        }
+    }
+
+    pub fn disk(&mut self, disk: &mut PhysicalDisk) {
+        disk.device_name = device_name(&disk.device_name);
+        disk.ident = disk.ident.as_deref().map(serial);
+        disk.multipath_parent = disk.multipath_parent.as_deref().map(device_name);
+        for partition in &mut disk.partitions {
+            partition.name = device_name(&partition.name);
+        }
+        if let Some(ref mut zfs) = disk.zfs_info {
+            self.zfs_info(zfs);
+        }
+    }
+
+    pub fn multipath_info(&mut self, info: &mut MultipathInfo) {
+        info.name = device_name(&info.name);
+        info.serial = serial(&info.serial);
+    }
+
+    /// The pool name; vdev names ("raidz2-0") carry nothing identifying
+    pub fn zfs_info(&mut self, zfs: &mut ZfsDriveInfo) {
+        zfs.pool = self.pool(&zfs.pool);
+    }
+
+    pub fn vm(&mut self, vm: &mut VmInfo) {
+        vm.name = numbered(&mut self.vms, "vm", &vm.name);
+    }
+
+    pub fn jail(&mut self, jail: &mut JailInfo) {
+        let name = numbered(&mut self.jails, "jail", &jail.name);
+        jail.hostname = format!("{}.example.com", name);
+        jail.path = format!("/jails/{}", name);
+        jail.name = name;
+        jail.ip_addresses = jail.ip_addresses.iter().map(|ip| self.address(ip)).collect();
+    }
+
+    pub fn pool(&mut self, pool: &str) -> String {
+        numbered(&mut self.pools, "pool", pool)
+    }
+
+    /// "pool-N/" followed by each child component hashed, so the hierarchy
+    /// stays visible
+    pub fn dataset(&mut self, dataset: &str) -> String {
+        let mut components = dataset.split('/');
+        let mut name = self.pool(components.next().unwrap_or_default());
+        for component in components {
+            name.push('/');
+            name.push_str(&serial(component));
+        }
+        name
+    }
+
+    /// An address from the documentation range of the same family
+    fn address(&mut self, address: &str) -> String {
+        if let Some(replacement) = self.addresses.get(address) {
+            return replacement.clone();
+        }
+        let replacement = if address.contains(':') {
+            format!("2001:db8::{:x}", self.addresses.len() + 1)
+        } else {
+            ipv4_placeholder(self.addresses.values().filter(|r| !r.contains(':')).count())
+        };
+        self.addresses.insert(address.to_string(), replacement.clone());
+        replacement
+    }
+}
+
+/// The `n`th (0-based) IPv4 placeholder: hosts .1 to .254 of each
+/// documentation network in turn, then the benchmarking range
+fn ipv4_placeholder(n: usize) -> String {
+    match IPV4_DOCUMENTATION_NETS.get(n / 254) {
+        Some([a, b, c]) => format!("{}.{}.{}.{}", a, b, c, n % 254 + 1),
+        None => {
+            let host = (n - IPV4_DOCUMENTATION_NETS.len() * 254) as u32 % IPV4_OVERFLOW_HOSTS + 1;
+            std::net::Ipv4Addr::from(IPV4_OVERFLOW_NET + host).to_string()
+        }
+    }
+}
+
+/// `prefix-N`, numbered in order of first appearance
+fn numbered(names: &mut HashMap<String, String>, prefix: &str, name: &str) -> String {
+    let next = names.len();
+    names
+        .entry(name.to_string())
+        .or_insert_with(|| format!("{}-{}", prefix, next))
+        .clone()
+}
+
+/// A device name with its label hashed when the label is chosen by the
+/// operator ("multipath/2MVULJ1A" is usually the drive serial); kernel
+/// names like "da5" are left alone
+pub fn device_name(name: &str) -> String {
+    match name.split_once('/') {
+        Some((class, label)) => format!("{}/{}", class, serial(label)),
+        None => name.to_string(),
+    }
+}
+
+/// Stable stand-in for a serial: the same input gives the same output on
+/// every run and every host (64-bit FNV-1a)
+fn serial(serial: &str) -> String {
+    let hash = serial.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)[..HASH_DIGITS].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn addresses_past_the_first_network_stay_distinct() {
+        let mut anonymizer = Anonymizer::new();
+        let placeholders: Vec<String> = (0..1000)
+            .map(|i| anonymizer.address(&format!("10.0.{}.{}", i / 256, i % 256)))
+            .collect();
+        assert_eq!(placeholders.iter().collect::<HashSet<_>>().len(), 1000);
+        assert_eq!(placeholders[0], "192.0.2.1");
+        assert_eq!(placeholders[253], "192.0.2.254");
+        assert_eq!(placeholders[254], "198.51.100.1");
+        assert_eq!(placeholders[508], "203.0.113.1");
+        assert_eq!(placeholders[762], "198.18.0.1");
+        assert_eq!(anonymizer.address("10.0.0.0"), "192.0.2.1");
+    }
+}
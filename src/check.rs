@@ -0,0 +1,119 @@
+//! Compatibility report for `sanview --check`
+//!
+//! Probes the running system without starting the TUI: kernel release,
+//! layouts of the FreeBSD-version-sensitive structs we read directly, the
+//! external tools we shell out to, and one collection pass per collector.
+
+use crate::collectors::{
+    bhyve, network, BhyveCollector, CpuCollector, GeomCollector, JailCollector, MemoryCollector,
+    MultipathCollector, NetworkCollector, SesCollector, ZfsCollector,
+};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Print the compatibility report to stdout
+///
+/// Returns false if a critical collector (one the main loop cannot run
+/// without) failed its probe.
+pub fn run() -> Result<bool> {
+    println!("sanview {} compatibility check", env!("CARGO_PKG_VERSION"));
+    println!();
+
+    println!("System");
+    row("kern.osrelease", &kernel_release().unwrap_or_else(|e| format!("unknown ({})", e)));
+    row("kinfo_proc size", &struct_size_report(bhyve::kinfo_proc_size()));
+    row("if_data size", &struct_size_report(network::if_data_size()));
+    for tool in ["gmultipath", "zpool", "jls"] {
+        let found = find_in_path(tool)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "not found".to_string());
+        row(tool, &found);
+    }
+    println!();
+
+    println!("Collectors");
+    let mut critical_ok = true;
+
+    // GEOM statistics need two snapshots before any disk is reported
+    let geom = GeomCollector::new().and_then(|mut c| {
+        c.collect()?;
+        std::thread::sleep(Duration::from_millis(250));
+        c.collect()
+    });
+    critical_ok &= probe("geom", true, geom.map(|d| format!("{} disks", d.len())));
+
+    let multipath = MultipathCollector::new().collect();
+    critical_ok &= probe("multipath", true, multipath.map(|m| format!("{} devices", m.len())));
+
+    probe("ses", false, SesCollector::new().collect().map(|s| format!("{} slot mappings", s.len())));
+    probe("zfs", false, ZfsCollector::new().collect().map(|z| format!("{} pool members", z.len())));
+    probe("cpu", false, CpuCollector::new().collect().map(|c| format!("{} cores", c.cores.len())));
+    probe(
+        "memory",
+        false,
+        MemoryCollector::new()
+            .collect()
+            .map(|m| format!("{} MiB total", m.total_bytes / 1024 / 1024)),
+    );
+    probe("network", false, NetworkCollector::new().collect().map(|n| format!("{} interfaces", n.len())));
+    probe("bhyve", false, BhyveCollector::new().collect().map(|v| format!("{} VMs", v.len())));
+    probe("jails", false, JailCollector::new().collect().map(|j| format!("{} jails", j.len())));
+
+    println!();
+    if critical_ok {
+        println!("All critical collectors working");
+    } else {
+        println!("A critical collector failed; sanview will not show storage data");
+    }
+
+    Ok(critical_ok)
+}
+
+fn row(label: &str, value: &str) {
+    println!("  {:<18} {}", label, value);
+}
+
+/// Print one collector result; returns whether it succeeded
+fn probe(name: &str, critical: bool, result: Result<String>) -> bool {
+    let kind = if critical { "critical" } else { "optional" };
+    match result {
+        Ok(detail) => {
+            println!("  [ ok ] {:<10} {:<9} {}", name, kind, detail);
+            true
+        }
+        Err(e) => {
+            println!("  [FAIL] {:<10} {:<9} {:#}", name, kind, e);
+            false
+        }
+    }
+}
+
+fn struct_size_report(sizes: Result<(usize, usize)>) -> String {
+    match sizes {
+        Ok((expected, actual)) if expected == actual => {
+            format!("expected {}, kernel {} (ok)", expected, actual)
+        }
+        Ok((expected, actual)) => {
+            format!("expected {}, kernel {} (MISMATCH - values will be wrong)", expected, actual)
+        }
+        Err(e) => format!("unknown ({:#})", e),
+    }
+}
+
+fn kernel_release() -> Result<String> {
+    use sysctl::Sysctl;
+
+    sysctl::Ctl::new("kern.osrelease")
+        .context("Failed to access sysctl kern.osrelease")?
+        .value_string()
+        .context("Failed to read sysctl kern.osrelease")
+}
+
+fn find_in_path(tool: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .chain(["/sbin", "/usr/sbin"].iter().map(PathBuf::from))
+        .map(|dir| dir.join(tool))
+        .find(|candidate| candidate.is_file())
+}
@@ -1,26 +1,42 @@
+#[cfg(target_os = "freebsd")]
 use anyhow::Result;
+#[cfg(target_os = "freebsd")]
 use libc::{c_int, c_void, size_t};
+#[cfg(any(target_os = "freebsd", test))]
 use log::debug;
+#[cfg(target_os = "freebsd")]
 use nix::unistd::sysconf;
+#[cfg(target_os = "freebsd")]
 use nix::unistd::SysconfVar;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "freebsd")]
 use std::collections::HashMap;
+#[cfg(target_os = "freebsd")]
 use std::mem;
 
 // FreeBSD sysctl MIB values
+#[cfg(target_os = "freebsd")]
 const CTL_KERN: c_int = 1;
+#[cfg(target_os = "freebsd")]
 const KERN_PROC: c_int = 14;
+#[cfg(target_os = "freebsd")]
 const KERN_PROC_ALL: c_int = 0;
+#[cfg(target_os = "freebsd")]
+const KERN_PROC_PID: c_int = 1;
+#[cfg(target_os = "freebsd")]
 const KERN_PROC_ARGS: c_int = 7;
 
 // Fixed-point to float conversion for ki_pctcpu
 // FreeBSD uses FSCALE = 2048 for fixpt_t
+#[cfg(target_os = "freebsd")]
 const FSCALE: f64 = 2048.0;
 
+#[cfg(target_os = "freebsd")]
 fn fixpt_to_pct(fixpt: u32) -> f64 {
     (fixpt as f64 / FSCALE) * 100.0
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct VmInfo {
     pub name: String,
     pub pid: u32,
@@ -30,10 +46,12 @@ pub struct VmInfo {
     pub runtime_secs: f64,      // Total runtime in seconds
 }
 
+#[cfg(target_os = "freebsd")]
 pub struct BhyveCollector {
     page_size: usize,
 }
 
+#[cfg(target_os = "freebsd")]
 impl BhyveCollector {
     pub fn new() -> Self {
         // Use nix for safe sysconf access
@@ -56,8 +74,11 @@ impl BhyveCollector {
         Ok(vms)
     }
 
-    /// Get the process title (argv[0]) for a given PID using KERN_PROC_ARGS
-    fn get_proc_args(&self, pid: i32) -> Option<String> {
+    /// Get the process arguments for a given PID using KERN_PROC_ARGS
+    ///
+    /// A process that called setproctitle() (bhyve does, once its options
+    /// are parsed) has a single argument: the title.
+    fn get_proc_args(&self, pid: i32) -> Option<Vec<String>> {
         let mib: [c_int; 4] = [CTL_KERN, KERN_PROC, KERN_PROC_ARGS, pid];
         let mut size: size_t = 0;
 
@@ -94,9 +115,14 @@ impl BhyveCollector {
             return None;
         }
 
-        // Args are null-separated; get the first one (process title)
-        let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
-        Some(String::from_utf8_lossy(&buffer[..end]).into_owned())
+        // Args are null-separated (with a trailing null)
+        buffer.truncate(size);
+        let args: Vec<String> = buffer
+            .split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+        (!args.is_empty()).then_some(args)
     }
 
     fn get_bhyve_vms(&self) -> Result<Vec<VmInfo>> {
@@ -198,17 +224,11 @@ impl BhyveCollector {
         // Now get VM names for each PID using KERN_PROC_ARGS
         let mut vms = Vec::new();
         for (pid, stats) in vm_stats {
-            // Get process title to extract VM name
-            let name = if let Some(args) = self.get_proc_args(pid) {
-                // Format is "bhyve: <vmname>"
-                args.strip_prefix("bhyve: ")
-                    .or_else(|| args.strip_prefix("bhyve:"))
-                    .unwrap_or(&args)
-                    .trim()
-                    .to_string()
-            } else {
-                format!("pid-{}", pid)
-            };
+            // Get process title or command line to extract VM name
+            let name = self
+                .get_proc_args(pid)
+                .and_then(|args| vm_name(&args))
+                .unwrap_or_else(|| format!("pid-{}", pid));
 
             vms.push(VmInfo {
                 name,
@@ -224,12 +244,130 @@ impl BhyveCollector {
     }
 }
 
+/// bhyve options that take an argument (from its getopt string)
+#[cfg(any(target_os = "freebsd", test))]
+const BHYVE_ARG_OPTIONS: &str = "BcfGkKlmopsUr";
+
+/// VM name from a bhyve process's arguments
+///
+/// bhyve titles itself "bhyve: <name>", but managers vary: vm-bhyve and
+/// cbsd can leave extra words or a parenthetical after the name, and some
+/// wrappers replace the title with the whole command line. The name is the
+/// first word of the title, or else bhyve's own: a `-o name=` setting, the
+/// positional argument after the options, or the `name=` line of a `-k`
+/// configuration file.
+#[cfg(any(target_os = "freebsd", test))]
+fn vm_name(args: &[String]) -> Option<String> {
+    if let [title] = args {
+        if let Some(rest) = title.strip_prefix("bhyve:") {
+            return rest
+                .split_whitespace()
+                .next()
+                .filter(|word| !word.starts_with('('))
+                .map(str::to_string);
+        }
+        // A command line squeezed into the title
+        let words: Vec<String> = title.split_whitespace().map(str::to_string).collect();
+        return if words.len() > 1 { argv_name(&words) } else { None };
+    }
+    argv_name(args)
+}
+
+/// VM name from a full bhyve command line (argv[0] first)
+#[cfg(any(target_os = "freebsd", test))]
+fn argv_name(args: &[String]) -> Option<String> {
+    let mut config = None;
+    let mut options = args.iter().skip(1);
+
+    while let Some(arg) = options.next() {
+        let Some(flags) = arg.strip_prefix('-').filter(|f| !f.is_empty()) else {
+            // getopt stops at the first positional argument: the VM name
+            return Some(arg.clone());
+        };
+        if flags == "-" {
+            return options.next().cloned();
+        }
+
+        // Flags can be grouped ("-AHP"); the first one that takes an
+        // argument consumes the rest of the word or the next one
+        for (i, flag) in flags.char_indices() {
+            if !BHYVE_ARG_OPTIONS.contains(flag) {
+                continue;
+            }
+            let attached = &flags[i + flag.len_utf8()..];
+            let value = if attached.is_empty() {
+                options.next().map(String::as_str)
+            } else {
+                Some(attached)
+            };
+            match (flag, value) {
+                ('o', Some(setting)) => {
+                    if let Some(name) = setting.strip_prefix("name=") {
+                        return Some(name.to_string());
+                    }
+                }
+                ('k', Some(path)) => config = Some(path.to_string()),
+                _ => {}
+            }
+            break;
+        }
+    }
+
+    config.and_then(|path| config_name(&path))
+}
+
+/// `name=` from a bhyve configuration file (`bhyve -k`)
+#[cfg(any(target_os = "freebsd", test))]
+fn config_name(path: &str) -> Option<String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| debug!("Failed to read bhyve config {}: {}", path, e))
+        .ok()?;
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("name="))
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+}
+
+/// Compare our kinfo_proc layout against the running kernel's
+///
+/// Returns (expected, actual) sizes; actual is the kernel-reported
+/// ki_structsize for this process.
+#[cfg(target_os = "freebsd")]
+pub fn kinfo_proc_size() -> Result<(usize, usize)> {
+    let mib: [c_int; 4] = [CTL_KERN, KERN_PROC, KERN_PROC_PID, std::process::id() as c_int];
+    let mut buffer: Vec<u8> = vec![0; mem::size_of::<KinfoProc>().max(4096)];
+    let mut size: size_t = buffer.len();
+
+    // SAFETY: buffer is larger than any kinfo_proc the kernel returns
+    let ret = unsafe {
+        libc::sysctl(
+            mib.as_ptr(),
+            4,
+            buffer.as_mut_ptr() as *mut c_void,
+            &mut size,
+            std::ptr::null(),
+            0,
+        )
+    };
+
+    if ret != 0 || size < mem::size_of::<i32>() {
+        anyhow::bail!("sysctl KERN_PROC_PID failed: {}", std::io::Error::last_os_error());
+    }
+
+    // ki_structsize is the first field regardless of layout
+    let actual = i32::from_ne_bytes([buffer[0], buffer[1], buffer[2], buffer[3]]) as usize;
+    Ok((mem::size_of::<KinfoProc>(), actual))
+}
+
+#[cfg(target_os = "freebsd")]
 impl Default for BhyveCollector {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(target_os = "freebsd")]
 struct VmStats {
     cpu_pct: f64,
     memory_bytes: u64,
@@ -243,6 +381,7 @@ struct VmStats {
 /// WARNING: This struct layout is FreeBSD version-specific.
 /// It was created for FreeBSD 14.x and may need updates for other versions.
 /// See sys/user.h for the authoritative definition.
+#[cfg(target_os = "freebsd")]
 #[repr(C)]
 struct KinfoProc {
     ki_structsize: i32,
@@ -334,3 +473,51 @@ struct KinfoProc {
     ki_sflag: i64,
     ki_tdflags: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn names_from_titles_and_command_lines() {
+        let cases = [
+            // Process titles (a single argument)
+            (vec!["bhyve: vm1".to_string()], Some("vm1")),
+            (vec!["bhyve: vm1 (1234)".to_string()], Some("vm1")),
+            (vec!["bhyve: (1234)".to_string()], None),
+            (vec!["bhyve".to_string()], None),
+            (vec!["bhyve -c 2 -m 4G vm2".to_string()], Some("vm2")),
+            // Full command lines
+            (args("bhyve -c 2 -m 4G -s 0,hostbridge -l com1,stdio vm1"), Some("vm1")),
+            (args("bhyve -AHP -c2 -m 4G -s 4,virtio-blk,/dev/zvol/tank/vm3 vm3"), Some("vm3")),
+            (args("bhyve -AHPw -c 2 vm4"), Some("vm4")),
+            (args("bhyve -Hl bootrom,/usr/local/share/uefi-firmware/BHYVE_UEFI.fd vm5"), Some("vm5")),
+            (args("bhyve -c 2 -o name=vm6 -o memory.size=4G"), Some("vm6")),
+            (args("bhyve -c 2 -oname=vm7"), Some("vm7")),
+            (args("bhyve -o memory.size=4G -- -vm8"), Some("-vm8")),
+            (args("bhyve -c 2 -m 4G"), None),
+        ];
+        for (argv, expected) in cases {
+            assert_eq!(vm_name(&argv).as_deref(), expected, "{:?}", argv);
+        }
+    }
+
+    #[test]
+    fn name_from_config_file() {
+        let path = std::env::temp_dir().join(format!("sanview-bhyve-{}.conf", std::process::id()));
+        std::fs::write(&path, "cpus=2\nmemory.size=4G\n name= vm9 \n").unwrap();
+        let path = path.to_string_lossy().into_owned();
+
+        assert_eq!(argv_name(&args(&format!("bhyve -k {}", path))).as_deref(), Some("vm9"));
+        assert_eq!(argv_name(&args(&format!("bhyve -Hk{}", path))).as_deref(), Some("vm9"));
+        // The command line wins over the file
+        assert_eq!(argv_name(&args(&format!("bhyve -k {} vm10", path))).as_deref(), Some("vm10"));
+        assert_eq!(argv_name(&args("bhyve -k /nonexistent/vm.conf")), None);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,24 +1,34 @@
+use super::interrupts::InterruptStats;
+#[cfg(target_os = "freebsd")]
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "freebsd")]
 use std::ffi::CString;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CpuStats {
     pub cores: Vec<CoreStats>,
+    /// System-wide interrupt rate, from the InterruptCollector
+    #[serde(default)]
+    pub interrupts: Option<InterruptStats>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CoreStats {
     pub core_id: usize,
-    pub user_pct: f64,
-    pub system_pct: f64,
+    pub user_pct: f64,       // user + nice
+    pub system_pct: f64,     // kernel, excluding interrupt handlers
+    pub interrupt_pct: f64,  // interrupt threads (NIC/HBA interrupt load)
     pub idle_pct: f64,
-    pub total_pct: f64,  // user + system
+    pub total_pct: f64,      // user + system + interrupt
 }
 
+#[cfg(target_os = "freebsd")]
 pub struct CpuCollector {
     previous_times: Option<Vec<CpuTime>>,
 }
 
+#[cfg(target_os = "freebsd")]
 #[derive(Clone, Debug)]
 struct CpuTime {
     user: u64,
@@ -28,6 +38,7 @@ struct CpuTime {
     idle: u64,
 }
 
+#[cfg(target_os = "freebsd")]
 impl CpuCollector {
     pub fn new() -> Self {
         Self {
@@ -53,22 +64,24 @@ impl CpuCollector {
 
                     let total = delta_user + delta_nice + delta_system + delta_interrupt + delta_idle;
 
-                    let (user_pct, system_pct, idle_pct) = if total > 0 {
+                    let (user_pct, system_pct, interrupt_pct, idle_pct) = if total > 0 {
                         (
                             ((delta_user + delta_nice) as f64 / total as f64) * 100.0,
-                            ((delta_system + delta_interrupt) as f64 / total as f64) * 100.0,
+                            (delta_system as f64 / total as f64) * 100.0,
+                            (delta_interrupt as f64 / total as f64) * 100.0,
                             (delta_idle as f64 / total as f64) * 100.0,
                         )
                     } else {
-                        (0.0, 0.0, 100.0)
+                        (0.0, 0.0, 0.0, 100.0)
                     };
 
                     CoreStats {
                         core_id,
                         user_pct,
                         system_pct,
+                        interrupt_pct,
                         idle_pct,
-                        total_pct: user_pct + system_pct,
+                        total_pct: user_pct + system_pct + interrupt_pct,
                     }
                 })
                 .collect()
@@ -81,6 +94,7 @@ impl CpuCollector {
                     core_id,
                     user_pct: 0.0,
                     system_pct: 0.0,
+                    interrupt_pct: 0.0,
                     idle_pct: 100.0,
                     total_pct: 0.0,
                 })
@@ -89,7 +103,7 @@ impl CpuCollector {
 
         self.previous_times = Some(current_times);
 
-        Ok(CpuStats { cores })
+        Ok(CpuStats { cores, interrupts: None })
     }
 
     fn read_cp_times(&self) -> Result<Vec<CpuTime>> {
@@ -160,6 +174,7 @@ impl CpuCollector {
     }
 }
 
+#[cfg(target_os = "freebsd")]
 impl Default for CpuCollector {
     fn default() -> Self {
         Self::new()
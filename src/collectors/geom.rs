@@ -1,8 +1,18 @@
-use crate::domain::device::{DiskStatistics, PathState, PhysicalDisk};
+use super::memory::ctl_u64;
+use crate::domain::device::{DiskStatistics, Partition, PathState, PhysicalDisk};
 use anyhow::{Context, Result};
 use freebsd_libgeom::{Snapshot, Statistics, Tree};
 use log::debug;
-use std::time::Instant;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How often to re-read disk identifiers even if the device set is unchanged
+const IDENT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Shortest devstat interval worth computing rates over
+const MIN_ETIME: f64 = 0.01;
+/// Longest plausible devstat interval, raised to twice the wall time between
+/// collections when sampling slowly (`--json --interval 1m`)
+const MAX_ETIME: f64 = 10.0;
 
 fn is_physical_disk(name: &str) -> bool {
     name.starts_with("da") || name.starts_with("nda") || name.starts_with("multipath/")
@@ -10,7 +20,14 @@ fn is_physical_disk(name: &str) -> bool {
 
 pub struct GeomCollector {
     previous_snapshot: Option<Snapshot>,
+    previous_taken: Option<Instant>,       // When previous_snapshot was taken
+    last_good: Vec<PhysicalDisk>,          // Result of the last cycle with a usable interval
     tree: Tree,
+    idents: HashMap<String, String>,       // Provider name -> disk ident (serial)
+    partitions: HashMap<String, Vec<Partition>>, // Disk name -> PART providers on it
+    multipath_parents: HashMap<String, String>,  // Consumer disk -> multipath provider
+    ident_names: Vec<String>,              // Device set the ident map was built for
+    ident_refreshed: Option<Instant>,
 }
 
 impl GeomCollector {
@@ -20,7 +37,14 @@ impl GeomCollector {
 
         Ok(Self {
             previous_snapshot: None,
+            previous_taken: None,
+            last_good: Vec::new(),
             tree,
+            idents: HashMap::new(),
+            partitions: HashMap::new(),
+            multipath_parents: HashMap::new(),
+            ident_names: Vec::new(),
+            ident_refreshed: None,
         })
     }
 
@@ -28,13 +52,78 @@ impl GeomCollector {
         let mut current_snapshot = Snapshot::new()
             .context("Failed to create GEOM snapshot")?;
 
-        let disks = self.compute_statistics(&mut current_snapshot)?;
+        // A snapshot taken in the same tick as the previous one has no interval
+        // to compute rates over: keep the older snapshot as the baseline and
+        // repeat the last result rather than reporting no disks. After a
+        // clock step or suspend the interval is meaningless, so the last
+        // result is repeated too but this snapshot becomes the new baseline.
+        let mut disks = match self.compute_statistics(&mut current_snapshot)? {
+            Interval::Rates(disks) => disks,
+            Interval::Empty => {
+                debug!("Zero-length GEOM interval, reusing previous statistics");
+                return Ok(self.last_good.clone());
+            }
+            Interval::Discontinuity => {
+                self.previous_snapshot = Some(current_snapshot);
+                self.previous_taken = Some(Instant::now());
+                return Ok(self.last_good.clone());
+            }
+        };
+        self.attach_idents(&mut disks);
+        attach_cam_errors(&mut disks);
 
+        self.last_good = disks.clone();
         self.previous_snapshot = Some(current_snapshot);
+        self.previous_taken = Some(Instant::now());
         Ok(disks)
     }
 
-    fn compute_statistics(&mut self, current: &mut Snapshot) -> Result<Vec<PhysicalDisk>> {
+    /// Re-read idents and partitions on the next collect()
+    pub fn force_refresh(&mut self) {
+        self.ident_refreshed = None;
+    }
+
+    /// Fill in each disk's GEOM ident, partition list and multipath parent
+    ///
+    /// The maps are rebuilt whenever the set of devices changes (a disk
+    /// was pulled or inserted) so a replacement disk that reuses a name is
+    /// picked up on the next cycle, and otherwise every 30 seconds.
+    fn attach_idents(&mut self, disks: &mut [PhysicalDisk]) {
+        if disks.is_empty() {
+            return;
+        }
+
+        let mut names: Vec<String> = disks.iter().map(|d| d.device_name.clone()).collect();
+        names.sort();
+
+        let stale = self
+            .ident_refreshed
+            .is_none_or(|t| t.elapsed() >= IDENT_REFRESH_INTERVAL);
+        if stale || names != self.ident_names {
+            match read_disk_idents() {
+                Ok(idents) => self.idents = idents,
+                Err(e) => debug!("Failed to read disk idents: {}", e),
+            }
+            match read_conftxt() {
+                Ok(edges) => {
+                    self.partitions = edges.partitions;
+                    self.multipath_parents = edges.multipath_parents;
+                }
+                Err(e) => debug!("Failed to read partition topology: {}", e),
+            }
+            self.ident_names = names;
+            self.ident_refreshed = Some(Instant::now());
+        }
+
+        for disk in disks.iter_mut() {
+            disk.ident = self.idents.get(&disk.device_name).cloned();
+            disk.partitions = self.partitions.get(&disk.device_name).cloned().unwrap_or_default();
+            disk.multipath_parent = self.multipath_parents.get(&disk.device_name).cloned();
+        }
+    }
+
+    /// Per-disk rates since the previous snapshot
+    fn compute_statistics(&mut self, current: &mut Snapshot) -> Result<Interval> {
         let mut disks = Vec::new();
         let timestamp = Instant::now();
 
@@ -42,11 +131,20 @@ impl GeomCollector {
             f64::from(current.timestamp() - prev.timestamp())
         } else {
             debug!("First snapshot, no statistics available yet");
-            return Ok(vec![]);
+            return Ok(Interval::Rates(vec![]));
         };
 
-        if etime <= 0.0 {
-            return Ok(vec![]);
+        // Rates over a stepped clock would be absurd spikes that flatten the
+        // charts' autoscaling for the rest of the history window
+        let max_etime = self
+            .previous_taken
+            .map_or(MAX_ETIME, |t| MAX_ETIME.max(2.0 * t.elapsed().as_secs_f64()));
+        if etime < 0.0 || etime > max_etime {
+            debug!("Discarding GEOM sample with implausible interval {:.3}s", etime);
+            return Ok(Interval::Discontinuity);
+        }
+        if etime < MIN_ETIME {
+            return Ok(Interval::Empty);
         }
 
         for (curstat, prevstat) in current.iter_pair(self.previous_snapshot.as_mut()) {
@@ -82,6 +180,9 @@ impl GeomCollector {
                         write_latency_ms: stats_computed.ms_per_transaction_write(),
                         queue_depth: stats_computed.queue_length() as f64,
                         busy_pct: stats_computed.busy_pct(),
+                        read_errors: 0,  // Filled in from the CAM sysctls after the pass
+                        write_errors: 0,
+                        other_errors: 0,
                         timestamp: Some(timestamp),
                     };
 
@@ -99,21 +200,187 @@ impl GeomCollector {
                     disks.push(PhysicalDisk {
                         device_name,
                         rank,
-                        ident: None,  // Populated by topology correlator
+                        ident: None,  // Filled in from kern.geom.confxml after the pass
                         multipath_parent: None,
                         slot: None,   // Populated by topology correlator from SES
                         enclosure: None,
                         statistics: stats,
                         path_state: PathState::Unknown,
+                        zfs_info: None, // Populated by topology correlator
+                        partitions: Vec::new(),  // Filled in from kern.geom.conftxt after the pass
                     });
                 }
             }
         }
 
-        Ok(disks)
+        Ok(Interval::Rates(disks))
+    }
+}
+
+/// Fill in CAM error counters for da/nda disks
+///
+/// devstat doesn't count errors, so they come from the CAM peripheral's
+/// sysctls: per-direction `iosched.{read,write,trim}.errs` on kernels with
+/// CAM_IOSCHED_DYNAMIC, else the `stats.errors` and `stats.timeouts` totals
+/// with CAM_IO_STATS. Kernels with neither leave the counts at zero.
+fn attach_cam_errors(disks: &mut [PhysicalDisk]) {
+    use sysctl::Sysctl;
+
+    let read = |name: &str| {
+        sysctl::Ctl::new(name)
+            .ok()
+            .and_then(|ctl| ctl_u64(name, &ctl).ok())
+    };
+
+    for disk in disks.iter_mut() {
+        let Some((driver, unit)) = cam_unit(&disk.device_name) else {
+            continue;
+        };
+        let node = format!("kern.cam.{}.{}", driver, unit);
+        let stats = &mut disk.statistics;
+
+        if let Some(read_errs) = read(&format!("{}.iosched.read.errs", node)) {
+            stats.read_errors = read_errs;
+            stats.write_errors = read(&format!("{}.iosched.write.errs", node)).unwrap_or(0);
+            stats.other_errors = read(&format!("{}.iosched.trim.errs", node)).unwrap_or(0);
+        } else {
+            stats.other_errors = read(&format!("{}.stats.errors", node)).unwrap_or(0)
+                + read(&format!("{}.stats.timeouts", node)).unwrap_or(0);
+        }
     }
 }
 
+/// "da12" -> ("da", "12"), "nda0" -> ("nda", "0"); None for anything else
+fn cam_unit(device_name: &str) -> Option<(&str, &str)> {
+    let split = device_name.find(|c: char| c.is_ascii_digit())?;
+    let (driver, unit) = device_name.split_at(split);
+    let is_unit = unit.bytes().all(|b| b.is_ascii_digit());
+    (matches!(driver, "da" | "nda") && is_unit).then_some((driver, unit))
+}
+
+/// Outcome of comparing a snapshot against the previous one
+enum Interval {
+    Rates(Vec<PhysicalDisk>),
+    /// Too little time has passed; keep the older baseline
+    Empty,
+    /// The interval is negative or implausibly long; rebase on this snapshot
+    Discontinuity,
+}
+
+/// Read provider idents from the GEOM configuration XML
+///
+/// Disk providers carry their serial in `<config><ident>`; providers
+/// without one (partitions, labels) are skipped.
+fn read_disk_idents() -> Result<HashMap<String, String>> {
+    use sysctl::Sysctl;
+
+    let xml = sysctl::Ctl::new("kern.geom.confxml")
+        .context("Failed to access sysctl kern.geom.confxml")?
+        .value_string()
+        .context("Failed to read sysctl kern.geom.confxml")?;
+
+    Ok(parse_provider_idents(&xml))
+}
+
+fn parse_provider_idents(xml: &str) -> HashMap<String, String> {
+    let mut idents = HashMap::new();
+
+    for block in xml.split("<provider id=").skip(1) {
+        let block = block.split("</provider>").next().unwrap_or(block);
+        let name = xml_element(block, "name");
+        let ident = xml_element(block, "ident");
+        if let (Some(name), Some(ident)) = (name, ident) {
+            if is_physical_disk(name) && !ident.is_empty() {
+                idents.insert(name.to_string(), ident.to_string());
+            }
+        }
+    }
+
+    idents
+}
+
+/// Edges of the GEOM graph that start at a physical disk
+#[derive(Debug, Default)]
+struct GeomEdges {
+    partitions: HashMap<String, Vec<Partition>>, // Disk name -> PART providers on it
+    multipath_parents: HashMap<String, String>,  // Consumer disk -> MULTIPATH provider
+}
+
+/// Read the disk -> partition and disk -> multipath edges of the GEOM graph
+///
+/// kern.geom.conftxt lists every geom depth-first, one provider per line:
+/// `<rank> <class> <name> <mediasize> <sectorsize> [<key> <value>]...`.
+/// A provider at rank N sits on the most recent provider at rank N-1; a
+/// MULTIPATH provider is listed once under each of its consumer disks.
+fn read_conftxt() -> Result<GeomEdges> {
+    use sysctl::Sysctl;
+
+    let conftxt = sysctl::Ctl::new("kern.geom.conftxt")
+        .context("Failed to access sysctl kern.geom.conftxt")?
+        .value_string()
+        .context("Failed to read sysctl kern.geom.conftxt")?;
+
+    Ok(parse_conftxt(&conftxt))
+}
+
+fn parse_conftxt(conftxt: &str) -> GeomEdges {
+    let mut edges = GeomEdges::default();
+    let mut by_rank: Vec<String> = Vec::new();
+
+    for line in conftxt.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let Ok(rank) = fields[0].parse::<usize>() else {
+            continue;
+        };
+        let (class, name) = (fields[1], fields[2]);
+
+        by_rank.truncate(rank);
+        let parent = rank.checked_sub(1).and_then(|r| by_rank.get(r)).cloned();
+        by_rank.resize(rank, String::new());
+        by_rank.push(name.to_string());
+
+        let Some(parent) = parent.filter(|p| is_physical_disk(p)) else {
+            continue;
+        };
+        if class == "MULTIPATH" {
+            edges.multipath_parents.insert(parent, name.to_string());
+            continue;
+        }
+        if class != "PART" {
+            continue;
+        }
+
+        // Key/value pairs follow the sector size
+        let part_type = fields
+            .get(5..)
+            .unwrap_or_default()
+            .chunks(2)
+            .find(|kv| kv[0] == "ty")
+            .and_then(|kv| kv.get(1))
+            .map(|ty| ty.to_string());
+
+        edges.partitions.entry(parent).or_default().push(Partition {
+            name: name.to_string(),
+            part_type,
+            size_bytes: fields[3].parse().unwrap_or(0),
+        });
+    }
+
+    edges
+}
+
+/// Text of the first `<tag>...</tag>` element in `s`
+fn xml_element<'a>(s: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = s.find(&open)? + open.len();
+    let len = s[start..].find(&close)?;
+    Some(s[start..start + len].trim())
+}
+
 impl Default for GeomCollector {
     fn default() -> Self {
         Self::new().expect("Failed to create GeomCollector")
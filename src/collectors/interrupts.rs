@@ -0,0 +1,178 @@
+#[cfg(target_os = "freebsd")]
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "freebsd")]
+use std::ffi::CString;
+#[cfg(target_os = "freebsd")]
+use std::time::Instant;
+
+/// Sources listed by name in the CPU panel
+#[cfg(target_os = "freebsd")]
+const TOP_SOURCES: usize = 3;
+
+/// Interrupt activity since the last collection (`vmstat -i` as rates)
+///
+/// Read from `hw.intrcnt` (one counter per interrupt source) and
+/// `hw.intrnames` (the matching names, e.g. "irq264: mpr0").
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct InterruptStats {
+    pub total_per_sec: f64,
+    pub top_sources: Vec<InterruptSource>,  // Busiest first
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InterruptSource {
+    pub name: String,
+    pub per_sec: f64,
+}
+
+/// Cumulative counters for delta-based rates
+#[cfg(target_os = "freebsd")]
+#[derive(Clone, Debug)]
+struct InterruptCounters {
+    counts: Vec<u64>,
+    timestamp: Instant,
+}
+
+#[cfg(target_os = "freebsd")]
+pub struct InterruptCollector {
+    previous: Option<InterruptCounters>,
+}
+
+#[cfg(target_os = "freebsd")]
+impl InterruptCollector {
+    pub fn new() -> Self {
+        Self { previous: None }
+    }
+
+    /// Rates since the previous call; zero on the first call and whenever
+    /// the set of interrupt sources changes (a driver attached or detached)
+    pub fn collect(&mut self) -> Result<InterruptStats> {
+        let counts = read_counts()?;
+        let current = InterruptCounters {
+            counts,
+            timestamp: Instant::now(),
+        };
+
+        let stats = match self.previous {
+            Some(ref prev) if prev.counts.len() == current.counts.len() => {
+                let elapsed = current.timestamp.duration_since(prev.timestamp).as_secs_f64();
+                if elapsed > 0.0 {
+                    rates(&prev.counts, &current.counts, elapsed)?
+                } else {
+                    InterruptStats::default()
+                }
+            }
+            _ => InterruptStats::default(),
+        };
+
+        self.previous = Some(current);
+        Ok(stats)
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+impl Default for InterruptCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+fn rates(prev: &[u64], current: &[u64], elapsed: f64) -> Result<InterruptStats> {
+    let deltas: Vec<f64> = current
+        .iter()
+        .zip(prev)
+        .map(|(cur, prev)| cur.saturating_sub(*prev) as f64 / elapsed)
+        .collect();
+    let total_per_sec = deltas.iter().sum();
+
+    // Names are only needed for the busiest few, so read them lazily
+    let mut busiest: Vec<(usize, f64)> = deltas
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, rate)| *rate > 0.0)
+        .collect();
+    busiest.sort_by(|a, b| b.1.total_cmp(&a.1));
+    busiest.truncate(TOP_SOURCES);
+
+    let top_sources = if busiest.is_empty() {
+        Vec::new()
+    } else {
+        let names = read_names(current.len())?;
+        busiest
+            .into_iter()
+            .map(|(i, per_sec)| InterruptSource {
+                name: names.get(i).cloned().unwrap_or_else(|| format!("intr{}", i)),
+                per_sec,
+            })
+            .collect()
+    };
+
+    Ok(InterruptStats {
+        total_per_sec,
+        top_sources,
+    })
+}
+
+/// hw.intrcnt: an array of u_long, one per source
+#[cfg(target_os = "freebsd")]
+fn read_counts() -> Result<Vec<u64>> {
+    let buffer = sysctl_bytes("hw.intrcnt")?;
+    let long_size = std::mem::size_of::<libc::c_ulong>();
+    Ok(buffer
+        .chunks_exact(long_size)
+        .map(|bytes| libc::c_ulong::from_ne_bytes(bytes.try_into().unwrap()) as u64)
+        .collect())
+}
+
+/// hw.intrnames: NUL-padded names in fixed-width slots (MAXCOMLEN + 1 on
+/// current kernels); falls back to plain NUL separation otherwise
+#[cfg(target_os = "freebsd")]
+fn read_names(count: usize) -> Result<Vec<String>> {
+    let buffer = sysctl_bytes("hw.intrnames")?;
+    let to_name = |bytes: &[u8]| {
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+    };
+
+    if count > 0 && buffer.len() % count == 0 {
+        Ok(buffer.chunks(buffer.len() / count).map(to_name).collect())
+    } else {
+        Ok(buffer.split(|&b| b == 0).map(to_name).collect())
+    }
+}
+
+/// Raw value of an opaque sysctl (the sysctl crate can't read arrays,
+/// see the note in cpu.rs)
+#[cfg(target_os = "freebsd")]
+fn sysctl_bytes(name: &str) -> Result<Vec<u8>> {
+    let cname = CString::new(name)?;
+
+    let mut size: libc::size_t = 0;
+    // SAFETY: sysctlbyname with null buffer is safe and returns required size
+    let ret = unsafe {
+        libc::sysctlbyname(cname.as_ptr(), std::ptr::null_mut(), &mut size, std::ptr::null(), 0)
+    };
+    if ret != 0 {
+        anyhow::bail!("sysctlbyname {} size query failed", name);
+    }
+
+    let mut buffer: Vec<u8> = vec![0; size];
+    // SAFETY: buffer is correctly sized from previous sysctlbyname call
+    let ret = unsafe {
+        libc::sysctlbyname(
+            cname.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            &mut size,
+            std::ptr::null(),
+            0,
+        )
+    };
+    if ret != 0 {
+        anyhow::bail!("sysctlbyname {} data query failed", name);
+    }
+    buffer.truncate(size);
+    Ok(buffer)
+}
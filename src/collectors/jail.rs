@@ -1,11 +1,17 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Clone, Debug)]
+/// jls parameters queried, in the column order of the plain-text fallback
+const JLS_PARAMS: [&str; 6] = ["jid", "name", "host.hostname", "ip4.addr", "ip6.addr", "path"];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct JailInfo {
     pub jid: u32,
     pub name: String,
     pub hostname: String,
-    pub ip_addresses: Vec<String>,
+    pub ip_addresses: Vec<String>,  // IPv4 then IPv6
     pub path: String,
 }
 
@@ -16,16 +22,31 @@ impl JailCollector {
         Self
     }
 
+    /// List running jails, preferring jls' libxo JSON output so names, paths
+    /// and hostnames containing spaces come through intact
     pub fn collect(&self) -> Result<Vec<JailInfo>> {
-        // Use jls to list running jails
+        let output = std::process::Command::new("jls")
+            .arg("--libxo")
+            .arg("json")
+            .args(JLS_PARAMS)
+            .output()?;
+
+        if output.status.success() {
+            match parse_jls_json(&String::from_utf8_lossy(&output.stdout)) {
+                Ok(jails) => return Ok(jails),
+                Err(e) => debug!("jls libxo output unusable, falling back to text: {:#}", e),
+            }
+        }
+
+        self.collect_text()
+    }
+
+    /// Whitespace-separated jls output, for systems without libxo support
+    fn collect_text(&self) -> Result<Vec<JailInfo>> {
         let output = std::process::Command::new("jls")
             .arg("-n")
             .arg("-h")
-            .arg("jid")
-            .arg("name")
-            .arg("host.hostname")
-            .arg("ip4.addr")
-            .arg("path")
+            .args(JLS_PARAMS)
             .output()?;
 
         let output_str = String::from_utf8_lossy(&output.stdout);
@@ -47,7 +68,7 @@ impl JailCollector {
     }
 
     fn parse_jls_line(&self, line: &str) -> Option<JailInfo> {
-        // Format: jid name host.hostname ip4.addr path
+        // Format: jid name host.hostname ip4.addr ip6.addr path
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() < 4 {
             return None;
@@ -57,15 +78,16 @@ impl JailCollector {
         let name = parts[1].to_string();
         let hostname = parts[2].to_string();
 
-        // IP addresses might be comma-separated or just one
-        let ip_str = parts.get(3).unwrap_or(&"-");
-        let ip_addresses: Vec<String> = if ip_str != &"-" {
-            ip_str.split(',').map(|s| s.to_string()).collect()
-        } else {
-            vec![]
-        };
+        // IP addresses might be comma-separated or just one; v4 then v6
+        let ip_addresses: Vec<String> = parts[3..]
+            .iter()
+            .take(2)
+            .filter(|s| **s != "-")
+            .flat_map(|s| s.split(','))
+            .map(|s| s.to_string())
+            .collect();
 
-        let path = parts.get(4).unwrap_or(&"-").to_string();
+        let path = parts.get(5).unwrap_or(&"-").to_string();
 
         Some(JailInfo {
             jid,
@@ -77,6 +99,70 @@ impl JailCollector {
     }
 }
 
+/// Parse `jls --libxo json` output: {"jail-information": {"jail": [{...}]}}
+fn parse_jls_json(json: &str) -> Result<Vec<JailInfo>> {
+    let root: Value = serde_json::from_str(json).context("Invalid JSON from jls")?;
+    let Some(entries) = root.pointer("/jail-information/jail") else {
+        // No running jails: libxo omits the list entirely
+        if root.get("jail-information").is_some() {
+            return Ok(Vec::new());
+        }
+        anyhow::bail!("No jail-information in jls output");
+    };
+    let entries = entries.as_array().context("jail-information.jail is not a list")?;
+
+    let jails = entries
+        .iter()
+        .filter_map(|jail| {
+            let jid = match jail.get("jid")? {
+                Value::Number(n) => u32::try_from(n.as_u64()?).ok()?,
+                Value::String(s) => s.parse().ok()?,
+                _ => return None,
+            };
+            Some(JailInfo {
+                jid,
+                name: json_string(jail.get("name")),
+                hostname: json_string(jail.get("host.hostname")),
+                ip_addresses: json_addresses(jail.get("ip4.addr"))
+                    .into_iter()
+                    .chain(json_addresses(jail.get("ip6.addr")))
+                    .collect(),
+                path: json_string(jail.get("path")),
+            })
+        })
+        .collect();
+
+    Ok(jails)
+}
+
+fn json_string(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => "-".to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Addresses as a list (leaf-list) or a comma-separated string; "-" means none
+fn json_addresses(value: Option<&Value>) -> Vec<String> {
+    let split = |s: &str| -> Vec<String> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty() && *a != "-")
+            .map(str::to_string)
+            .collect()
+    };
+    match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .flat_map(split)
+            .collect(),
+        Some(Value::String(s)) => split(s),
+        _ => Vec::new(),
+    }
+}
+
 impl Default for JailCollector {
     fn default() -> Self {
         Self::new()
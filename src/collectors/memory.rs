@@ -1,19 +1,40 @@
+use crate::config::MemoryPressureWeights;
+#[cfg(target_os = "freebsd")]
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "freebsd")]
+use std::collections::hash_map::Entry;
+#[cfg(target_os = "freebsd")]
+use std::collections::HashMap;
+#[cfg(target_os = "freebsd")]
+use std::time::Instant;
+#[cfg(target_os = "freebsd")]
 use sysctl::Sysctl;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct MemoryStats {
+    /// Installed RAM (hw.physmem). The page-queue segments below only cover
+    /// memory the VM system manages (managed_bytes); the remainder went to
+    /// the kernel at boot, so the segments sum to slightly less than this.
     pub total_bytes: u64,
+    #[serde(default)]
+    pub managed_bytes: u64,      // v_page_count * pagesize
     pub active_bytes: u64,
     pub inactive_bytes: u64,
     pub laundry_bytes: u64,
     pub wired_bytes: u64,
     pub buf_bytes: u64,
     pub free_bytes: u64,
-    pub used_pct: f64,
+    pub committed_bytes: u64,    // Memory that cannot be given back without swapping (laundry included)
+    pub reclaimable_bytes: u64,  // Inactive + ARC above its minimum
+    #[serde(default)]
+    pub arc_reclaimable_bytes: u64,  // ARC above arcstats.c_min (the rest is pinned)
+    pub used_pct: f64,           // committed_bytes as a share of total_bytes
     pub swap_total_bytes: u64,
     pub swap_used_bytes: u64,
     pub swap_used_pct: f64,
+    pub swap_in_pages_per_sec: f64,
+    pub swap_out_pages_per_sec: f64,
 
     // ZFS ARC stats
     pub arc_total_bytes: u64,
@@ -25,44 +46,149 @@ pub struct MemoryStats {
     pub arc_compressed_bytes: u64,
     pub arc_uncompressed_bytes: u64,
     pub arc_ratio: f64,
+    pub arc_hit_pct: Option<f64>,              // All ARC reads hit since last collect (None if idle)
+    pub arc_demand_data_hit_pct: Option<f64>,  // Demand data reads only (excludes prefetch and metadata)
+    pub l2arc_size_bytes: u64,                 // 0 when no cache device is attached
+    pub l2arc_hit_pct: Option<f64>,            // ARC misses served by the L2ARC
+}
+
+impl MemoryStats {
+    /// Memory pressure score, 0 (relaxed) to 100 (thrashing)
+    ///
+    /// The weighted mean of three shortages, each scaled to 0..1:
+    ///
+    /// - free: the share of RAM that is neither free nor clean cache,
+    ///   1 - (free + inactive) / total; laundry pages are dirty and have to
+    ///   go to swap before reuse, so they count as short
+    /// - arc: the share of the ARC pinned at or below arcstats.c_min,
+    ///   1 - reclaimable ARC / ARC size (left out without ZFS)
+    /// - swap: pages swapped in and out per second, against
+    ///   `swap_pages_full` as the rate that counts as fully thrashing
+    ///
+    /// A box with most of its RAM in a shrinkable ARC scores low even when
+    /// little is free; one that is actively paging scores high whatever the
+    /// other two say, since swap carries the most weight by default.
+    pub fn pressure_pct(&self, weights: &MemoryPressureWeights) -> f64 {
+        if self.total_bytes == 0 {
+            return 0.0;
+        }
+
+        let available = self.free_bytes + self.inactive_bytes;
+        let mut terms = vec![(
+            weights.free,
+            1.0 - available as f64 / self.total_bytes as f64,
+        )];
+        if self.arc_total_bytes > 0 {
+            terms.push((
+                weights.arc,
+                1.0 - self.arc_reclaimable_bytes as f64 / self.arc_total_bytes as f64,
+            ));
+        }
+        let paging = self.swap_in_pages_per_sec + self.swap_out_pages_per_sec;
+        terms.push((weights.swap, paging / weights.swap_pages_full.max(1.0)));
+
+        let total_weight: f64 = terms.iter().map(|(w, _)| w.max(0.0)).sum();
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+        let score: f64 = terms
+            .iter()
+            .map(|(w, shortage)| w.max(0.0) * shortage.clamp(0.0, 1.0))
+            .sum();
+        score / total_weight * 100.0
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+pub struct MemoryCollector {
+    previous_swap: Option<SwapCounters>,
+    previous_arc: Option<ArcCounters>,
+    ctls: HashMap<&'static str, sysctl::Ctl>,  // Resolved name -> MIB, reused every collect
+}
+
+/// Cumulative paging counters for delta-based swap rates
+#[cfg(target_os = "freebsd")]
+#[derive(Clone, Debug)]
+struct SwapCounters {
+    pages_in: u64,
+    pages_out: u64,
+    timestamp: Instant,
 }
 
-pub struct MemoryCollector;
+/// Cumulative arcstats hit/miss counters for interval hit rates
+#[cfg(target_os = "freebsd")]
+#[derive(Clone, Debug)]
+struct ArcCounters {
+    hits: u64,
+    misses: u64,
+    demand_data_hits: u64,
+    demand_data_misses: u64,
+    l2_hits: u64,
+    l2_misses: u64,
+}
 
+#[cfg(target_os = "freebsd")]
+impl ArcCounters {
+    /// Hit rates (ARC, demand data, L2ARC) for the interval since `prev`
+    fn hit_rates(&self, prev: &ArcCounters) -> (Option<f64>, Option<f64>, Option<f64>) {
+        fn ratio(hits: u64, misses: u64) -> Option<f64> {
+            let total = hits + misses;
+            (total > 0).then(|| hits as f64 / total as f64 * 100.0)
+        }
+        (
+            ratio(
+                self.hits.saturating_sub(prev.hits),
+                self.misses.saturating_sub(prev.misses),
+            ),
+            ratio(
+                self.demand_data_hits.saturating_sub(prev.demand_data_hits),
+                self.demand_data_misses.saturating_sub(prev.demand_data_misses),
+            ),
+            ratio(
+                self.l2_hits.saturating_sub(prev.l2_hits),
+                self.l2_misses.saturating_sub(prev.l2_misses),
+            ),
+        )
+    }
+}
+
+#[cfg(target_os = "freebsd")]
 impl MemoryCollector {
     pub fn new() -> Self {
-        Self
+        Self {
+            previous_swap: None,
+            previous_arc: None,
+            ctls: HashMap::new(),
+        }
     }
 
-    pub fn collect(&self) -> Result<MemoryStats> {
-        let page_size = sysctl_u64("hw.pagesize")?;
+    pub fn collect(&mut self) -> Result<MemoryStats> {
+        let page_size = self.sysctl_u64("hw.pagesize")?;
 
-        let total_pages = sysctl_u64("vm.stats.vm.v_page_count")?;
-        let active_pages = sysctl_u64("vm.stats.vm.v_active_count")?;
-        let inactive_pages = sysctl_u64("vm.stats.vm.v_inactive_count")?;
-        let laundry_pages = sysctl_u64("vm.stats.vm.v_laundry_count").unwrap_or(0);
-        let wired_pages = sysctl_u64("vm.stats.vm.v_wire_count")?;
-        let free_pages = sysctl_u64("vm.stats.vm.v_free_count")?;
+        let total_pages = self.sysctl_u64("vm.stats.vm.v_page_count")?;
+        let active_pages = self.sysctl_u64("vm.stats.vm.v_active_count")?;
+        let inactive_pages = self.sysctl_u64("vm.stats.vm.v_inactive_count")?;
+        let laundry_pages = self.sysctl_u64("vm.stats.vm.v_laundry_count").unwrap_or(0);
+        let wired_pages = self.sysctl_u64("vm.stats.vm.v_wire_count")?;
+        let free_pages = self.sysctl_u64("vm.stats.vm.v_free_count")?;
 
-        let total_bytes = total_pages * page_size;
+        let managed_bytes = total_pages * page_size;
+        let total_bytes = self
+            .sysctl_u64("hw.physmem")
+            .unwrap_or(managed_bytes)
+            .max(managed_bytes);
         let active_bytes = active_pages * page_size;
         let inactive_bytes = inactive_pages * page_size;
         let laundry_bytes = laundry_pages * page_size;
         let wired_bytes = wired_pages * page_size;
-        let buf_bytes = sysctl_u64("vfs.bufspace").unwrap_or(0);
+        let buf_bytes = self.sysctl_u64("vfs.bufspace").unwrap_or(0);
         let free_bytes = free_pages * page_size;
 
-        let used_bytes = total_bytes - free_bytes;
-        let used_pct = if total_bytes > 0 {
-            (used_bytes as f64 / total_bytes as f64) * 100.0
-        } else {
-            0.0
-        };
 
         // Swap statistics
-        let swap_total_bytes = sysctl_u64("vm.swap_total").unwrap_or(0);
+        let swap_total_bytes = self.sysctl_u64("vm.swap_total").unwrap_or(0);
         let swap_used_bytes = if swap_total_bytes > 0 {
-            let swap_free = sysctl_u64("vm.stats.vm.v_swappgsfree").unwrap_or(0) * page_size;
+            let swap_free = self.sysctl_u64("vm.stats.vm.v_swappgsfree").unwrap_or(0) * page_size;
             swap_total_bytes.saturating_sub(swap_free)
         } else {
             0
@@ -74,15 +200,37 @@ impl MemoryCollector {
             0.0
         };
 
+        // Paging activity (pages swapped in/out per second since last collection)
+        let current_swap = SwapCounters {
+            pages_in: self.sysctl_u64("vm.stats.vm.v_swappgsin").unwrap_or(0),
+            pages_out: self.sysctl_u64("vm.stats.vm.v_swappgsout").unwrap_or(0),
+            timestamp: Instant::now(),
+        };
+        let (swap_in_pages_per_sec, swap_out_pages_per_sec) = match self.previous_swap {
+            Some(ref prev) => {
+                let elapsed = current_swap.timestamp.duration_since(prev.timestamp).as_secs_f64();
+                if elapsed > 0.0 {
+                    (
+                        current_swap.pages_in.saturating_sub(prev.pages_in) as f64 / elapsed,
+                        current_swap.pages_out.saturating_sub(prev.pages_out) as f64 / elapsed,
+                    )
+                } else {
+                    (0.0, 0.0)
+                }
+            }
+            None => (0.0, 0.0),
+        };
+        self.previous_swap = Some(current_swap);
+
         // ZFS ARC statistics
-        let arc_total_bytes = sysctl_u64("kstat.zfs.misc.arcstats.size").unwrap_or(0);
-        let arc_mfu_bytes = sysctl_u64("kstat.zfs.misc.arcstats.mfu_size").unwrap_or(0);
-        let arc_mru_bytes = sysctl_u64("kstat.zfs.misc.arcstats.mru_size").unwrap_or(0);
-        let arc_anon_bytes = sysctl_u64("kstat.zfs.misc.arcstats.anon_size").unwrap_or(0);
-        let arc_header_bytes = sysctl_u64("kstat.zfs.misc.arcstats.hdr_size").unwrap_or(0);
-        let arc_other_bytes = sysctl_u64("kstat.zfs.misc.arcstats.other_size").unwrap_or(0);
-        let arc_compressed_bytes = sysctl_u64("kstat.zfs.misc.arcstats.compressed_size").unwrap_or(0);
-        let arc_uncompressed_bytes = sysctl_u64("kstat.zfs.misc.arcstats.uncompressed_size").unwrap_or(0);
+        let arc_total_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.size").unwrap_or(0);
+        let arc_mfu_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.mfu_size").unwrap_or(0);
+        let arc_mru_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.mru_size").unwrap_or(0);
+        let arc_anon_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.anon_size").unwrap_or(0);
+        let arc_header_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.hdr_size").unwrap_or(0);
+        let arc_other_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.other_size").unwrap_or(0);
+        let arc_compressed_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.compressed_size").unwrap_or(0);
+        let arc_uncompressed_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.uncompressed_size").unwrap_or(0);
 
         let arc_ratio = if arc_compressed_bytes > 0 {
             arc_uncompressed_bytes as f64 / arc_compressed_bytes as f64
@@ -90,18 +238,59 @@ impl MemoryCollector {
             1.0
         };
 
+        // Cache effectiveness over the last interval, from cumulative arcstats counters
+        let current_arc = ArcCounters {
+            hits: self.sysctl_u64("kstat.zfs.misc.arcstats.hits").unwrap_or(0),
+            misses: self.sysctl_u64("kstat.zfs.misc.arcstats.misses").unwrap_or(0),
+            demand_data_hits: self.sysctl_u64("kstat.zfs.misc.arcstats.demand_data_hits").unwrap_or(0),
+            demand_data_misses: self.sysctl_u64("kstat.zfs.misc.arcstats.demand_data_misses").unwrap_or(0),
+            l2_hits: self.sysctl_u64("kstat.zfs.misc.arcstats.l2_hits").unwrap_or(0),
+            l2_misses: self.sysctl_u64("kstat.zfs.misc.arcstats.l2_misses").unwrap_or(0),
+        };
+        let (arc_hit_pct, arc_demand_data_hit_pct, l2arc_hit_pct) = match self.previous_arc {
+            Some(ref prev) => current_arc.hit_rates(prev),
+            None => (None, None, None),
+        };
+        self.previous_arc = Some(current_arc);
+        let l2arc_size_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.l2_size").unwrap_or(0);
+
+        // "Used" means committed: everything except free pages and what the
+        // kernel can hand back under pressure without swapping. Inactive
+        // pages (vm.stats.vm.v_inactive_count) are clean cache, and the ARC
+        // (arcstats.size, counted in v_wire_count) shrinks down to
+        // arcstats.c_min when memory is short. Laundry pages (v_laundry_count)
+        // are dirty and must be written to swap before reuse, so they stay
+        // committed.
+        let arc_min_bytes = self.sysctl_u64("kstat.zfs.misc.arcstats.c_min").unwrap_or(0);
+        let arc_reclaimable_bytes = arc_total_bytes.saturating_sub(arc_min_bytes);
+        let reclaimable_bytes = inactive_bytes + arc_reclaimable_bytes;
+        let committed_bytes = managed_bytes
+            .saturating_sub(free_bytes)
+            .saturating_sub(reclaimable_bytes);
+        let used_pct = if total_bytes > 0 {
+            (committed_bytes as f64 / total_bytes as f64) * 100.0
+        } else {
+            0.0
+        };
+
         Ok(MemoryStats {
             total_bytes,
+            managed_bytes,
             active_bytes,
             inactive_bytes,
             laundry_bytes,
             wired_bytes,
             buf_bytes,
             free_bytes,
+            committed_bytes,
+            reclaimable_bytes,
+            arc_reclaimable_bytes,
             used_pct,
             swap_total_bytes,
             swap_used_bytes,
             swap_used_pct,
+            swap_in_pages_per_sec,
+            swap_out_pages_per_sec,
             arc_total_bytes,
             arc_mfu_bytes,
             arc_mru_bytes,
@@ -111,21 +300,38 @@ impl MemoryCollector {
             arc_compressed_bytes,
             arc_uncompressed_bytes,
             arc_ratio,
+            arc_hit_pct,
+            arc_demand_data_hit_pct,
+            l2arc_size_bytes,
+            l2arc_hit_pct,
         })
     }
+
+    /// Read a sysctl as u64, resolving its name to a MIB only on first use
+    ///
+    /// Names that fail to resolve (e.g. arcstats before zfs.ko is loaded)
+    /// are not cached and are looked up again next time.
+    fn sysctl_u64(&mut self, name: &'static str) -> Result<u64> {
+        let ctl = match self.ctls.entry(name) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(
+                sysctl::Ctl::new(name).with_context(|| format!("Failed to access sysctl {}", name))?,
+            ),
+        };
+        ctl_u64(name, ctl)
+    }
 }
 
+#[cfg(target_os = "freebsd")]
 impl Default for MemoryCollector {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// Read a sysctl value as u64 using the sysctl crate (safe)
-fn sysctl_u64(name: &str) -> Result<u64> {
-    let ctl = sysctl::Ctl::new(name)
-        .with_context(|| format!("Failed to access sysctl {}", name))?;
-
+/// Read a resolved sysctl value as u64 using the sysctl crate (safe)
+#[cfg(target_os = "freebsd")]
+pub(crate) fn ctl_u64(name: &str, ctl: &sysctl::Ctl) -> Result<u64> {
     let val = ctl.value()
         .with_context(|| format!("Failed to read sysctl {}", name))?;
 
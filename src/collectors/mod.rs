@@ -1,19 +1,45 @@
+//! Data collectors
+//!
+//! The collectors built on sysctl, libgeom and FreeBSD's kernel structs
+//! only exist on FreeBSD; their data types are portable, so snapshots can
+//! be read and shown anywhere (`--connect`, `--simulate`).
+
 pub mod bhyve;
 pub mod cpu;
+#[cfg(target_os = "freebsd")]
 pub mod geom;
+pub mod interrupts;
 pub mod jail;
 pub mod memory;
 pub mod multipath;
 pub mod network;
 pub mod ses;
 pub mod zfs;
+pub mod zil;
 
-pub use bhyve::{BhyveCollector, VmInfo};
-pub use cpu::{CoreStats, CpuCollector, CpuStats};
+#[cfg(target_os = "freebsd")]
+pub use bhyve::BhyveCollector;
+pub use bhyve::VmInfo;
+#[cfg(target_os = "freebsd")]
+pub use cpu::CpuCollector;
+pub use cpu::{CoreStats, CpuStats};
+#[cfg(target_os = "freebsd")]
 pub use geom::GeomCollector;
+#[cfg(target_os = "freebsd")]
+pub use interrupts::InterruptCollector;
+pub use interrupts::{InterruptSource, InterruptStats};
 pub use jail::{JailCollector, JailInfo};
-pub use memory::{MemoryCollector, MemoryStats};
+#[cfg(target_os = "freebsd")]
+pub use memory::MemoryCollector;
+pub use memory::MemoryStats;
 pub use multipath::{MultipathCollector, MultipathInfo, PathInfo};
-pub use network::{NetworkCollector, NetworkStats};
-pub use ses::{SesCollector, SesSlotInfo};
-pub use zfs::{ZfsCollector, ZfsDriveInfo, ZfsRole};
+#[cfg(target_os = "freebsd")]
+pub use network::NetworkCollector;
+pub use network::{InterfaceFilter, NetworkStats};
+pub use ses::{
+    ElementKind, ElementStatus, EnclosureElement, EnclosureHealth, SesAccessDenied, SesCollector, SesSlotInfo,
+};
+pub use zfs::{DatasetUsage, PoolUsage, ZfsCollector, ZfsDriveInfo, ZfsRole};
+#[cfg(target_os = "freebsd")]
+pub use zil::ZilCollector;
+pub use zil::ZilStats;
@@ -16,6 +16,7 @@ pub struct MultipathInfo {
 pub struct PathInfo {
     pub device_name: String,
     pub is_active: bool,
+    pub is_failed: bool,  // Consumer state FAIL (gmultipath stopped using it)
 }
 
 /// Cache duration for multipath topology (topology rarely changes)
@@ -34,6 +35,11 @@ impl MultipathCollector {
         }
     }
 
+    /// Drop the cached topology so the next collect() re-runs gmultipath
+    pub fn force_refresh(&mut self) {
+        self.last_update = None;
+    }
+
     /// Collect multipath topology using gmultipath list
     /// Results are cached for 30 seconds since topology rarely changes
     pub fn collect(&mut self) -> Result<HashMap<String, MultipathInfo>> {
@@ -66,8 +72,7 @@ impl MultipathCollector {
             anyhow::bail!("gmultipath command failed");
         }
 
-        Ok(String::from_utf8(output.stdout)
-            .context("Failed to parse gmultipath output as UTF-8")?)
+        String::from_utf8(output.stdout).context("Failed to parse gmultipath output as UTF-8")
     }
 
     fn parse_gmultipath_output(&self, output: &str) -> Result<HashMap<String, MultipathInfo>> {
@@ -78,6 +83,7 @@ impl MultipathCollector {
         let mut in_consumers = false;
         let mut current_consumer_name: Option<String> = None;
         let mut current_consumer_active = false;
+        let mut current_consumer_failed = false;
 
         for line in output.lines() {
             let trimmed = line.trim();
@@ -91,6 +97,7 @@ impl MultipathCollector {
                         current_paths.push(PathInfo {
                             device_name: consumer_name,
                             is_active: current_consumer_active,
+                            is_failed: current_consumer_failed,
                         });
                     }
 
@@ -99,7 +106,7 @@ impl MultipathCollector {
                         mp_name.clone(),
                         MultipathInfo {
                             name: mp_name,
-                            serial: geom_name,
+                            serial: serial_from_geom_name(&geom_name),
                             state: current_state.clone(),
                             paths: current_paths.clone(),
                         },
@@ -107,7 +114,11 @@ impl MultipathCollector {
                     current_paths.clear();
                 }
 
-                current_geom = Some(name.to_string());
+                current_geom = normalize_geom_name(name);
+                if current_geom.is_none() {
+                    debug!("Skipping multipath geom with unusable name {:?}", name);
+                }
+                current_paths.clear();
                 current_state = MultipathState::Unknown;
                 in_consumers = false;
                 current_consumer_name = None;
@@ -126,10 +137,12 @@ impl MultipathCollector {
                 } else if let Some(ref name) = current_consumer_name {
                     // This is consumer state
                     current_consumer_active = state_str == "ACTIVE";
+                    current_consumer_failed = state_str == "FAIL";
                     // Save this consumer
                     current_paths.push(PathInfo {
                         device_name: name.clone(),
                         is_active: current_consumer_active,
+                        is_failed: current_consumer_failed,
                     });
                     current_consumer_name = None;
                 }
@@ -151,10 +164,12 @@ impl MultipathCollector {
                         current_paths.push(PathInfo {
                             device_name: prev_name,
                             is_active: current_consumer_active,
+                            is_failed: current_consumer_failed,
                         });
                     }
                     current_consumer_name = Some(rest.to_string());
                     current_consumer_active = false;
+                    current_consumer_failed = false;
                 }
             }
         }
@@ -166,6 +181,7 @@ impl MultipathCollector {
                 current_paths.push(PathInfo {
                     device_name: consumer_name,
                     is_active: current_consumer_active,
+                    is_failed: current_consumer_failed,
                 });
             }
 
@@ -174,7 +190,7 @@ impl MultipathCollector {
                 mp_name.clone(),
                 MultipathInfo {
                     name: mp_name,
-                    serial: geom_name,
+                    serial: serial_from_geom_name(&geom_name),
                     state: current_state,
                     paths: current_paths,
                 },
@@ -186,8 +202,49 @@ impl MultipathCollector {
     }
 }
 
+/// Clean up a geom name from `gmultipath list` for use as a device key
+///
+/// Surrounding whitespace and a redundant "multipath/" prefix are dropped so
+/// the provider is always "multipath/<name>", matching /dev and zpool.
+/// Embedded slashes and spaces are kept; control characters make the name
+/// unusable.
+fn normalize_geom_name(raw: &str) -> Option<String> {
+    let name = raw.trim();
+    let name = name.strip_prefix("multipath/").unwrap_or(name).trim_matches('/');
+    if name.is_empty() || name.chars().any(|c| c.is_control()) {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Serial for a geom name: its last path component ("enc0/2MVULJ1A" -> "2MVULJ1A")
+fn serial_from_geom_name(name: &str) -> String {
+    name.rsplit('/').next().unwrap_or(name).trim().to_string()
+}
+
 impl Default for MultipathCollector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geom_names_keep_spaces_and_slashes() {
+        assert_eq!(normalize_geom_name("  shelf A bay 3 ").as_deref(), Some("shelf A bay 3"));
+        assert_eq!(normalize_geom_name("enc0/2MVULJ1A").as_deref(), Some("enc0/2MVULJ1A"));
+        assert_eq!(normalize_geom_name("multipath/enc0/2MVULJ1A/").as_deref(), Some("enc0/2MVULJ1A"));
+        assert_eq!(normalize_geom_name("multipath/"), None);
+        assert_eq!(normalize_geom_name("   "), None);
+        assert_eq!(normalize_geom_name("bad\u{1b}[0mname"), None);
+    }
+
+    #[test]
+    fn serial_is_the_last_path_component() {
+        assert_eq!(serial_from_geom_name("enc0/2MVULJ1A"), "2MVULJ1A");
+        assert_eq!(serial_from_geom_name("shelf A bay 3"), "shelf A bay 3");
+    }
+}
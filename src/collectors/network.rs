@@ -1,10 +1,17 @@
+#[cfg(target_os = "freebsd")]
 use anyhow::{Context, Result};
+#[cfg(target_os = "freebsd")]
 use log::debug;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "freebsd")]
 use std::collections::HashMap;
+#[cfg(target_os = "freebsd")]
 use std::ffi::CStr;
+#[cfg(target_os = "freebsd")]
 use std::process::Command;
 
 // FreeBSD if_data structure (from net/if.h)
+#[cfg(target_os = "freebsd")]
 #[repr(C)]
 #[allow(non_camel_case_types)]
 struct if_data {
@@ -35,6 +42,7 @@ struct if_data {
     ifi_lastchange: [u64; 2],
 }
 
+#[cfg(target_os = "freebsd")]
 #[derive(Clone, Debug)]
 pub struct NetworkInterface {
     pub name: String,
@@ -52,7 +60,7 @@ pub struct NetworkInterface {
     pub parent_aggregate: Option<String>,
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct NetworkStats {
     pub name: String,
     /// Smoothed rates for display (EMA)
@@ -70,6 +78,7 @@ pub struct NetworkStats {
 }
 
 /// Smoothed rate values for EMA calculation
+#[cfg(target_os = "freebsd")]
 #[derive(Clone, Default)]
 struct SmoothedRates {
     rx_bytes_per_sec: f64,
@@ -78,7 +87,65 @@ struct SmoothedRates {
     tx_packets_per_sec: f64,
 }
 
+/// Interfaces hidden unless the user overrides the exclude list
+pub const DEFAULT_EXCLUDES: &[&str] = &["lo*", "pflog*", "enc*", "tap*", "epair*", "bridge*", "gif*", "stf*"];
+
+/// Which interfaces to collect, as shell-style globs (`*` and `?`)
+///
+/// An interface is collected if it matches an include pattern (or the
+/// include list is empty) and matches no exclude pattern.
+#[derive(Clone, Debug)]
+pub struct InterfaceFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl InterfaceFilter {
+    pub fn matches(&self, name: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| glob_match(p, name));
+        included && !self.exclude.iter().any(|p| glob_match(p, name))
+    }
+}
+
+impl Default for InterfaceFilter {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Match `name` against a glob where `*` is any run of characters and `?` is one
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi, ni));
+            pi += 1;
+        } else if let Some((star_pi, star_ni)) = backtrack {
+            // Let the last '*' absorb one more character and retry
+            pi = star_pi + 1;
+            ni = star_ni + 1;
+            backtrack = Some((star_pi, star_ni + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(target_os = "freebsd")]
 pub struct NetworkCollector {
+    filter: InterfaceFilter,
     previous: HashMap<String, NetworkInterface>,
     last_collection: std::time::Instant,
     lagg_members: HashMap<String, Vec<String>>,
@@ -88,11 +155,14 @@ pub struct NetworkCollector {
 
 /// EMA smoothing factor: 0.3 means new values contribute 30%, old values 70%
 /// This provides ~3-4 sample decay time (smooth but responsive)
+#[cfg(target_os = "freebsd")]
 const EMA_ALPHA: f64 = 0.3;
 
 /// RAII guard for ifaddrs - ensures freeifaddrs is called on drop
+#[cfg(target_os = "freebsd")]
 struct IfAddrsGuard(*mut libc::ifaddrs);
 
+#[cfg(target_os = "freebsd")]
 impl Drop for IfAddrsGuard {
     fn drop(&mut self) {
         if !self.0.is_null() {
@@ -102,9 +172,15 @@ impl Drop for IfAddrsGuard {
     }
 }
 
+#[cfg(target_os = "freebsd")]
 impl NetworkCollector {
     pub fn new() -> Self {
+        Self::with_filter(InterfaceFilter::default())
+    }
+
+    pub fn with_filter(filter: InterfaceFilter) -> Self {
         Self {
+            filter,
             previous: HashMap::new(),
             last_collection: std::time::Instant::now(),
             lagg_members: HashMap::new(),
@@ -207,9 +283,6 @@ impl NetworkCollector {
     fn collect_interfaces(&self, member_to_aggregate: &HashMap<String, String>) -> Result<HashMap<String, NetworkInterface>> {
         let mut interfaces: HashMap<String, NetworkInterface> = HashMap::new();
 
-        // Skip interfaces we don't care about
-        let skip_prefixes = ["lo", "pflog", "enc", "tap", "epair", "bridge", "gif", "stf"];
-
         // SAFETY: getifaddrs is a standard POSIX function
         let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
         let ret = unsafe { libc::getifaddrs(&mut ifap) };
@@ -236,8 +309,8 @@ impl NetworkCollector {
                 let sa_family = unsafe { (*ifaddrs.ifa_addr).sa_family } as i32;
 
                 if sa_family == libc::AF_LINK && !ifaddrs.ifa_data.is_null() {
-                    // Skip unwanted interfaces
-                    if !skip_prefixes.iter().any(|p| name.starts_with(p)) {
+                    // Skip filtered-out interfaces before any rate bookkeeping
+                    if self.filter.matches(&name) {
                         // SAFETY: For AF_LINK addresses, ifa_data points to if_data struct
                         let data = unsafe { &*(ifaddrs.ifa_data as *const if_data) };
 
@@ -314,6 +387,40 @@ impl NetworkCollector {
     }
 }
 
+/// Compare our if_data layout against the running kernel's
+///
+/// Returns (expected, actual) sizes; actual is ifi_datalen from the first
+/// link-level address, which the kernel sets to sizeof(struct if_data).
+#[cfg(target_os = "freebsd")]
+pub fn if_data_size() -> Result<(usize, usize)> {
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+    // SAFETY: getifaddrs is a standard POSIX function
+    let ret = unsafe { libc::getifaddrs(&mut ifap) };
+    if ret != 0 {
+        anyhow::bail!("getifaddrs failed: {}", std::io::Error::last_os_error());
+    }
+    let _guard = IfAddrsGuard(ifap);
+
+    let mut ifa = ifap;
+    while !ifa.is_null() {
+        // SAFETY: ifa is non-null and part of the list returned by getifaddrs
+        let ifaddrs = unsafe { &*ifa };
+        if !ifaddrs.ifa_addr.is_null() && !ifaddrs.ifa_data.is_null() {
+            // SAFETY: We checked ifa_addr is not null
+            let sa_family = unsafe { (*ifaddrs.ifa_addr).sa_family } as i32;
+            if sa_family == libc::AF_LINK {
+                // SAFETY: ifi_datalen sits at the same offset in every if_data revision
+                let data = unsafe { &*(ifaddrs.ifa_data as *const if_data) };
+                return Ok((std::mem::size_of::<if_data>(), data.ifi_datalen as usize));
+            }
+        }
+        ifa = ifaddrs.ifa_next;
+    }
+
+    anyhow::bail!("No link-level interface found")
+}
+
+#[cfg(target_os = "freebsd")]
 impl Default for NetworkCollector {
     fn default() -> Self {
         Self::new()
@@ -1,12 +1,15 @@
-/// SES (SCSI Enclosure Services) collector for disk slot mapping
-///
-/// Uses FreeBSD SES ioctls to map disks to their physical enclosure slots
-/// Reference: ses(4), scsi_enc.h
+//! SES (SCSI Enclosure Services) collector for disk slot mapping
+//!
+//! Uses FreeBSD SES ioctls to map disks to their physical enclosure slots
+//! Reference: ses(4), scsi_enc.h
 
+use crate::config::EnclosureCalibration;
 use anyhow::{Context, Result};
 use log::{debug, warn};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs::{self, File};
+use std::fs::{self, File, OpenOptions};
+use std::io::ErrorKind;
 use std::os::unix::io::AsRawFd;
 
 // SES ioctl constants from /usr/include/cam/scsi/scsi_enc.h
@@ -20,12 +23,28 @@ const fn _IO(group: u8, num: u8) -> libc::c_ulong {
 
 const ENCIOC_GETNELM: libc::c_ulong = _IO(ENCIOC, 1);
 const ENCIOC_GETELMMAP: libc::c_ulong = _IO(ENCIOC, 2);
+const ENCIOC_GETELMSTAT: libc::c_ulong = _IO(ENCIOC, 5);
+const ENCIOC_SETELMSTAT: libc::c_ulong = _IO(ENCIOC, 6);
 const ENCIOC_GETELMDEVNAMES: libc::c_ulong = _IO(ENCIOC, 10);
+const ENCIOC_GETENCID: libc::c_ulong = _IO(ENCIOC, 14);
 
 // Element types from scsi_enc.h
 const ELMTYP_DEVICE: u32 = 0x01;        // Device Slot
+const ELMTYP_POWER: u32 = 0x02;         // Power Supply
+const ELMTYP_FAN: u32 = 0x03;           // Cooling
+const ELMTYP_THERM: u32 = 0x04;         // Temperature Sensor
+const ELMTYP_VOM: u32 = 0x12;           // Voltage Sensor
 const ELMTYP_ARRAY_DEV: u32 = 0x17;     // Array Device Slot
 
+// Element control bits from scsi_ses.h
+const SESCTL_CSEL: u8 = 0x80;           // Select: apply this control (byte 0)
+const SESCTL_PRDFAIL: u8 = 0x40;        // Predicted failure (byte 0)
+const SESCTL_RQSID: u8 = 0x02;          // Request identify (locate LED, byte 2)
+const SESCTL_RQSRMV: u8 = 0x04;         // Request remove (byte 2)
+const SESCTL_RQSINS: u8 = 0x08;         // Request insert (byte 2)
+const SESCTL_DRVLCK: u8 = 0x40;         // Do not remove (byte 2)
+const SESCTL_RQSFLT: u8 = 0x20;         // Request fault LED (byte 3)
+
 // FFI structures matching /usr/include/cam/scsi/scsi_enc.h
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -43,6 +62,18 @@ struct EnciocElmDevnames {
     elm_devnames: *mut libc::c_char,
 }
 
+#[repr(C)]
+struct EnciocElmStatus {
+    elm_idx: libc::c_uint,
+    cstat: [u8; 4],
+}
+
+#[repr(C)]
+struct EnciocString {
+    bufsiz: libc::size_t,
+    buf: *mut u8,
+}
+
 #[derive(Debug, Clone)]
 pub struct SesSlotInfo {
     pub slot: usize,           // Physical slot number
@@ -50,11 +81,113 @@ pub struct SesSlotInfo {
     pub enclosure: String,     // Enclosure identifier (e.g., "ses0")
 }
 
-pub struct SesCollector;
+/// Environmental element types shown in the enclosure health line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ElementKind {
+    PowerSupply,
+    Fan,
+    Temperature,
+    Voltage,
+}
+
+/// SES element status code (low nibble of the first status byte)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ElementStatus {
+    Ok,
+    NotInstalled,
+    Unknown,
+    NonCritical,
+    Critical,
+    Unrecoverable,
+}
+
+impl ElementStatus {
+    fn from_code(code: u8) -> Self {
+        match code & 0x0f {
+            1 => ElementStatus::Ok,
+            2 => ElementStatus::Critical,
+            3 => ElementStatus::NonCritical,
+            4 => ElementStatus::Unrecoverable,
+            5 => ElementStatus::NotInstalled,
+            _ => ElementStatus::Unknown,
+        }
+    }
+
+    /// Anything the operator should look at
+    pub fn is_fault(self) -> bool {
+        self >= ElementStatus::NonCritical
+    }
+}
+
+/// One PSU, fan or sensor of an enclosure
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnclosureElement {
+    pub kind: ElementKind,
+    pub index: usize,              // SES element index
+    pub status: ElementStatus,
+    pub reading: Option<f64>,      // Fan RPM, degrees C or volts; None if not reported
+}
+
+/// Environmental status of one enclosure (PSUs, fans, temperatures, voltages)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EnclosureHealth {
+    pub enclosure: String,         // e.g. "ses0"
+    pub elements: Vec<EnclosureElement>,
+}
+
+impl EnclosureHealth {
+    /// Elements of one kind
+    pub fn of_kind(&self, kind: ElementKind) -> impl Iterator<Item = &EnclosureElement> {
+        self.elements.iter().filter(move |e| e.kind == kind)
+    }
+
+    /// Worst status across all elements (Ok for an enclosure without any)
+    pub fn worst_status(&self) -> ElementStatus {
+        self.elements
+            .iter()
+            .map(|e| e.status)
+            .filter(|s| *s != ElementStatus::NotInstalled)
+            .max()
+            .unwrap_or(ElementStatus::Ok)
+    }
+}
+
+/// Enclosures exist but none of them could be opened (not running as
+/// root, or a devfs rule hides them), as opposed to there being none
+#[derive(Debug)]
+pub struct SesAccessDenied {
+    pub devices: usize,
+}
+
+impl std::fmt::Display for SesAccessDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "permission denied opening {} SES device(s)", self.devices)
+    }
+}
+
+impl std::error::Error for SesAccessDenied {}
+
+/// Whether an open or ioctl failed with EACCES/EPERM
+fn is_permission_denied(error: &anyhow::Error) -> bool {
+    error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<std::io::Error>())
+        .any(|e| e.kind() == ErrorKind::PermissionDenied)
+}
+
+pub struct SesCollector {
+    calibration: Vec<EnclosureCalibration>,
+}
 
 impl SesCollector {
     pub fn new() -> Self {
-        Self
+        Self::with_calibration(Vec::new())
+    }
+
+    /// Collector that renumbers slots of the listed enclosures (from the
+    /// config file); others keep the SES element index
+    pub fn with_calibration(calibration: Vec<EnclosureCalibration>) -> Self {
+        Self { calibration }
     }
 
     /// Collect slot mappings from all SES devices
@@ -63,8 +196,12 @@ impl SesCollector {
     /// Note: For dual-controller arrays, both controllers see the same physical
     /// enclosure but report different device names (different paths). We scan all
     /// controllers to get complete coverage, but only keep one slot assignment per device.
+    ///
+    /// Fails with [`SesAccessDenied`] when there are enclosures but every
+    /// one refused access; no enclosures at all is an empty map.
     pub fn collect(&self) -> Result<HashMap<String, SesSlotInfo>> {
         let mut slot_map = HashMap::new();
+        let mut denied = 0;
 
         // Find all /dev/ses* devices
         let ses_devices = self.find_ses_devices()?;
@@ -79,17 +216,168 @@ impl SesCollector {
                         slot_map.entry(device_name).or_insert(slot_info);
                     }
                 }
+                Err(e) if is_permission_denied(&e) => {
+                    debug!("Failed to scan {}: {:#}", ses_dev, e);
+                    denied += 1;
+                }
                 Err(e) => {
                     warn!("Failed to scan {}: {}", ses_dev, e);
                 }
             }
         }
+        if denied > 0 && denied == ses_devices.len() {
+            return Err(SesAccessDenied { devices: denied }.into());
+        }
 
         debug!("Collected slot mappings for {} devices from {} enclosures",
                slot_map.len(), ses_devices.len());
         Ok(slot_map)
     }
 
+    /// Read PSU, fan, temperature and voltage elements of every enclosure
+    ///
+    /// Both controllers of a dual-controller shelf report the same
+    /// enclosure, so enclosures are deduplicated by logical id.
+    pub fn collect_health(&self) -> Result<Vec<EnclosureHealth>> {
+        let mut enclosures = Vec::new();
+        let mut seen_ids = Vec::new();
+
+        for ses_dev in self.find_ses_devices()? {
+            let file = match File::open(&ses_dev) {
+                Ok(file) => file,
+                Err(e) => {
+                    debug!("Failed to open {}: {}", ses_dev, e);
+                    continue;
+                }
+            };
+            let fd = file.as_raw_fd();
+
+            if let Some(id) = self.get_enclosure_id(fd) {
+                if seen_ids.contains(&id) {
+                    continue;
+                }
+                seen_ids.push(id);
+            }
+
+            let elements = match self.get_element_map(fd, &ses_dev) {
+                Ok(elements) => elements,
+                Err(e) => {
+                    debug!("Failed to read element map of {}: {}", ses_dev, e);
+                    continue;
+                }
+            };
+
+            let mut health = EnclosureHealth {
+                enclosure: ses_dev.strip_prefix("/dev/").unwrap_or(&ses_dev).to_string(),
+                elements: Vec::new(),
+            };
+            for element in &elements {
+                let kind = match element.elm_type {
+                    ELMTYP_POWER => ElementKind::PowerSupply,
+                    ELMTYP_FAN => ElementKind::Fan,
+                    ELMTYP_THERM => ElementKind::Temperature,
+                    ELMTYP_VOM => ElementKind::Voltage,
+                    _ => continue,
+                };
+                let Some(cstat) = self.get_element_status(fd, element.elm_idx) else {
+                    continue;
+                };
+                health.elements.push(EnclosureElement {
+                    kind,
+                    index: element.elm_idx as usize,
+                    status: ElementStatus::from_code(cstat[0]),
+                    reading: element_reading(kind, cstat),
+                });
+            }
+            enclosures.push(health);
+        }
+
+        Ok(enclosures)
+    }
+
+    /// Switch the locate (identify) LED of the bay holding any of `devices`
+    ///
+    /// Returns the enclosure and element that was changed. This writes to
+    /// /dev/ses*, which normally needs root.
+    pub fn set_locate(&self, devices: &[String], on: bool) -> Result<String> {
+        for ses_dev in self.find_ses_devices()? {
+            let file = match OpenOptions::new().read(true).write(true).open(&ses_dev) {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                    anyhow::bail!("permission denied opening {} for writing (run as root)", ses_dev)
+                }
+                Err(e) => {
+                    debug!("Failed to open {}: {}", ses_dev, e);
+                    continue;
+                }
+            };
+            let fd = file.as_raw_fd();
+            let elements = match self.get_element_map(fd, &ses_dev) {
+                Ok(elements) => elements,
+                Err(e) => {
+                    debug!("Failed to read element map of {}: {}", ses_dev, e);
+                    continue;
+                }
+            };
+            let enc_name = ses_dev.strip_prefix("/dev/").unwrap_or(&ses_dev);
+
+            for element in elements.iter() {
+                if element.elm_type != ELMTYP_DEVICE && element.elm_type != ELMTYP_ARRAY_DEV {
+                    continue;
+                }
+                let names = self.get_element_devnames(fd, element.elm_idx).unwrap_or_default();
+                if !names.iter().any(|name| devices.contains(name)) {
+                    continue;
+                }
+
+                // Start from the current status so the fault LED and the other
+                // device slot requests stay as they are. Status and control
+                // pages share a layout but not their bits: mask each byte down
+                // to the control bits, as sesutil's ses_status_to_ctrl() does,
+                // so the status code, slot address and bypass/off state are
+                // never written back as requests
+                let status = self
+                    .get_element_status(fd, element.elm_idx)
+                    .with_context(|| format!("ENCIOC_GETELMSTAT failed on {}", ses_dev))?;
+                let mut control = EnciocElmStatus {
+                    elm_idx: element.elm_idx,
+                    cstat: [
+                        (status[0] & SESCTL_PRDFAIL) | SESCTL_CSEL,
+                        0,
+                        status[2] & (SESCTL_RQSID | SESCTL_RQSRMV | SESCTL_RQSINS | SESCTL_DRVLCK),
+                        status[3] & SESCTL_RQSFLT,
+                    ],
+                };
+                if on {
+                    control.cstat[2] |= SESCTL_RQSID;
+                } else {
+                    control.cstat[2] &= !SESCTL_RQSID;
+                }
+
+                let ret = unsafe { libc::ioctl(fd, ENCIOC_SETELMSTAT, &mut control) };
+                if ret < 0 {
+                    let err = std::io::Error::last_os_error();
+                    if err.kind() == ErrorKind::PermissionDenied {
+                        anyhow::bail!("permission denied setting {} element {} (run as root)",
+                                      enc_name, element.elm_idx);
+                    }
+                    return Err(err).with_context(|| {
+                        format!("ENCIOC_SETELMSTAT failed on {} element {}", enc_name, element.elm_idx)
+                    });
+                }
+                return Ok(format!("{} element {}", enc_name, element.elm_idx));
+            }
+        }
+
+        anyhow::bail!("no enclosure slot reports {}", devices.join("/"))
+    }
+
+    /// Paths of the enclosure devices present now, sorted (e.g. to notice a
+    /// hot-added shelf without re-reading every element)
+    pub fn enclosure_devices(&self) -> Result<Vec<String>> {
+        self.find_ses_devices()
+    }
+
     fn find_ses_devices(&self) -> Result<Vec<String>> {
         let mut devices = Vec::new();
 
@@ -103,6 +391,7 @@ impl SesCollector {
             }
         }
 
+        devices.sort();
         debug!("Found {} SES devices", devices.len());
         Ok(devices)
     }
@@ -113,43 +402,33 @@ impl SesCollector {
         let file = File::open(dev_path)
             .with_context(|| format!("Failed to open {}", dev_path))?;
         let fd = file.as_raw_fd();
-
-        // Get number of elements
-        let mut nelm: libc::c_uint = 0;
-        let ret = unsafe { libc::ioctl(fd, ENCIOC_GETNELM, &mut nelm) };
-        if ret < 0 {
-            return Err(anyhow::anyhow!("ENCIOC_GETNELM failed"));
-        }
-
-        debug!("{}: {} elements", dev_path, nelm);
-
-        // Get element map
-        let mut elements: Vec<EnciocElement> = vec![
-            EnciocElement {
-                elm_idx: 0,
-                elm_subenc_id: 0,
-                elm_type: 0,
-            };
-            nelm as usize
-        ];
-
-        let ret = unsafe { libc::ioctl(fd, ENCIOC_GETELMMAP, elements.as_mut_ptr()) };
-        if ret < 0 {
-            return Err(anyhow::anyhow!("ENCIOC_GETELMMAP failed"));
-        }
+        let elements = self.get_element_map(fd, dev_path)?;
 
         // Extract enclosure name for logging
         let enc_name = dev_path.strip_prefix("/dev/").unwrap_or(dev_path);
 
-        // Scan device elements and use element index as slot number
+        let enc_id = self.get_enclosure_id(fd);
+        let calibration = enc_id
+            .as_deref()
+            .and_then(|id| self.calibration.iter().find(|c| c.id.eq_ignore_ascii_case(id)));
+        debug!("{}: enclosure id {:?}{}", enc_name, enc_id,
+               if calibration.is_some() { " (calibrated)" } else { "" });
+
+        // Scan device elements; the element index is the slot number unless
+        // the config recalibrates this enclosure
         for element in elements.iter() {
             // Only interested in device slots
             if element.elm_type != ELMTYP_DEVICE && element.elm_type != ELMTYP_ARRAY_DEV {
                 continue;
             }
 
-            // Use element index as slot number (matches physical slot labeling)
-            let slot = element.elm_idx as usize;
+            let slot = match calibration {
+                Some(c) => match c.slot(element.elm_idx as usize) {
+                    Some(slot) => slot,
+                    None => continue,
+                },
+                None => element.elm_idx as usize,
+            };
 
             // Get device names for this element
             if let Ok(dev_names) = self.get_element_devnames(fd, element.elm_idx) {
@@ -175,6 +454,60 @@ impl SesCollector {
         Ok(mappings)
     }
 
+    /// Every element of the enclosure (index, subenclosure, type)
+    fn get_element_map(&self, fd: libc::c_int, dev_path: &str) -> Result<Vec<EnciocElement>> {
+        // Get number of elements
+        let mut nelm: libc::c_uint = 0;
+        let ret = unsafe { libc::ioctl(fd, ENCIOC_GETNELM, &mut nelm) };
+        if ret < 0 {
+            return Err(anyhow::anyhow!("ENCIOC_GETNELM failed"));
+        }
+
+        debug!("{}: {} elements", dev_path, nelm);
+
+        // Get element map
+        let mut elements: Vec<EnciocElement> = vec![
+            EnciocElement {
+                elm_idx: 0,
+                elm_subenc_id: 0,
+                elm_type: 0,
+            };
+            nelm as usize
+        ];
+
+        let ret = unsafe { libc::ioctl(fd, ENCIOC_GETELMMAP, elements.as_mut_ptr()) };
+        if ret < 0 {
+            return Err(anyhow::anyhow!("ENCIOC_GETELMMAP failed"));
+        }
+
+        Ok(elements)
+    }
+
+    /// The four status bytes of one element
+    fn get_element_status(&self, fd: libc::c_int, elm_idx: libc::c_uint) -> Option<[u8; 4]> {
+        let mut status = EnciocElmStatus { elm_idx, cstat: [0; 4] };
+        let ret = unsafe { libc::ioctl(fd, ENCIOC_GETELMSTAT, &mut status) };
+        (ret >= 0).then_some(status.cstat)
+    }
+
+    /// Enclosure logical identifier (e.g. "500304801f6ab37f"), if reported
+    fn get_enclosure_id(&self, fd: libc::c_int) -> Option<String> {
+        let mut buffer = [0u8; 64];
+        let mut request = EnciocString {
+            bufsiz: buffer.len(),
+            buf: buffer.as_mut_ptr(),
+        };
+
+        let ret = unsafe { libc::ioctl(fd, ENCIOC_GETENCID, &mut request) };
+        if ret < 0 {
+            return None;
+        }
+
+        let id = std::ffi::CStr::from_bytes_until_nul(&buffer).ok()?.to_string_lossy();
+        let id = id.trim();
+        (!id.is_empty()).then(|| id.to_string())
+    }
+
     fn get_element_devnames(&self, fd: libc::c_int, elm_idx: libc::c_uint)
         -> Result<Vec<String>> {
 
@@ -213,6 +546,19 @@ impl SesCollector {
     }
 }
 
+/// Sensor value from the type-specific status bytes (SES-3 7.3)
+fn element_reading(kind: ElementKind, cstat: [u8; 4]) -> Option<f64> {
+    match kind {
+        // Actual fan speed in units of 10 RPM, 11 bits
+        ElementKind::Fan => Some(((((cstat[1] & 0x07) as u16) << 8 | cstat[2] as u16) * 10) as f64),
+        // Degrees C offset by 20; 0 means not reported
+        ElementKind::Temperature => (cstat[2] != 0).then(|| cstat[2] as f64 - 20.0),
+        // Signed, in units of 10 mV
+        ElementKind::Voltage => Some(i16::from_be_bytes([cstat[2], cstat[3]]) as f64 / 100.0),
+        ElementKind::PowerSupply => None,
+    }
+}
+
 impl Default for SesCollector {
     fn default() -> Self {
         Self::new()
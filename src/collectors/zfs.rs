@@ -1,9 +1,11 @@
 use anyhow::Result;
+use log::warn;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::process::Command;
 use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ZfsRole {
     Data,
     Slog,
@@ -11,7 +13,7 @@ pub enum ZfsRole {
     Spare,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZfsDriveInfo {
     pub pool: String,
     pub vdev: String,
@@ -19,6 +21,42 @@ pub struct ZfsDriveInfo {
     pub state: String,
 }
 
+/// Space used in one pool (`zpool list -Hp`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolUsage {
+    pub pool: String,
+    pub size_bytes: u64,
+    pub alloc_bytes: u64,
+    pub free_bytes: u64,
+    pub fragmentation_pct: Option<f64>,  // None when the pool doesn't report it
+}
+
+impl PoolUsage {
+    pub fn capacity_pct(&self) -> f64 {
+        if self.size_bytes == 0 {
+            0.0
+        } else {
+            self.alloc_bytes as f64 / self.size_bytes as f64 * 100.0
+        }
+    }
+}
+
+/// Space used by one filesystem or volume (`zfs list -Hp`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetUsage {
+    pub name: String,
+    pub used_bytes: u64,            // Including snapshots and children
+    pub avail_bytes: u64,
+    pub refer_bytes: u64,           // Data this dataset references itself
+    pub compress_ratio: Option<f64>,
+}
+
+impl DatasetUsage {
+    pub fn pool(&self) -> &str {
+        self.name.split('/').next().unwrap_or(&self.name)
+    }
+}
+
 /// Cache duration for ZFS topology (topology rarely changes)
 const CACHE_DURATION: Duration = Duration::from_secs(30);
 
@@ -35,6 +73,11 @@ impl ZfsCollector {
         }
     }
 
+    /// Drop the cached topology so the next collect() re-runs zpool
+    pub fn force_refresh(&mut self) {
+        self.last_update = None;
+    }
+
     /// Collect ZFS topology information for all pools
     /// Returns a map of device name -> ZFS info
     /// Results are cached for 30 seconds since topology rarely changes
@@ -53,9 +96,12 @@ impl ZfsCollector {
         let pools = self.get_pools()?;
 
         // Parse each pool's status
+        // One unreadable pool shouldn't hide the rest
         for pool in pools {
-            let pool_info = self.parse_pool_status(&pool)?;
-            drive_map.extend(pool_info);
+            match self.parse_pool_status(&pool) {
+                Ok(pool_info) => drive_map.extend(pool_info),
+                Err(e) => warn!("Failed to read zpool status for {}: {}", pool, e),
+            }
         }
 
         self.cache = Some(drive_map.clone());
@@ -64,6 +110,32 @@ impl ZfsCollector {
         Ok(drive_map)
     }
 
+    /// Size, allocation and fragmentation of every pool (not cached; run
+    /// it on a slow cadence, it forks zpool)
+    pub fn collect_usage(&self) -> Result<Vec<PoolUsage>> {
+        let output = Command::new("zpool")
+            .args(["list", "-Hp", "-o", "name,size,allocated,free,fragmentation"])
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("zpool list failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        Ok(parse_list_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Space used by every filesystem and volume (not cached; run it on a
+    /// slow cadence, it forks zfs)
+    pub fn collect_datasets(&self) -> Result<Vec<DatasetUsage>> {
+        let output = Command::new("zfs")
+            .args(["list", "-Hp", "-t", "filesystem,volume", "-o", "name,used,avail,refer,compressratio"])
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("zfs list failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        Ok(parse_dataset_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
     fn get_pools(&self) -> Result<Vec<String>> {
         let output = Command::new("zpool")
             .arg("list")
@@ -82,99 +154,215 @@ impl ZfsCollector {
             .arg(pool)
             .output()?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut drive_map = HashMap::new();
+        Ok(parse_status_output(pool, &String::from_utf8_lossy(&output.stdout)))
+    }
+}
 
-        let mut current_role = ZfsRole::Data;
-        let mut current_vdev = String::new();
-        let mut in_config = false;
+/// Parse tab-separated `zpool list -Hp -o name,size,allocated,free,fragmentation`
+/// lines; fragmentation is "-" for pools without the spacemap_histogram feature
+fn parse_list_output(stdout: &str) -> Vec<PoolUsage> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [pool, size, alloc, free, frag] = fields[..] else {
+                return None;
+            };
+            Some(PoolUsage {
+                pool: pool.to_string(),
+                size_bytes: size.parse().ok()?,
+                alloc_bytes: alloc.parse().ok()?,
+                free_bytes: free.parse().ok()?,
+                fragmentation_pct: frag.trim_end_matches('%').parse().ok(),
+            })
+        })
+        .collect()
+}
 
-        for line in stdout.lines() {
-            let trimmed = line.trim_start();
+/// Parse tab-separated `zfs list -Hp -o name,used,avail,refer,compressratio`
+/// lines; the ratio is printed as "1.52" or "1.52x" depending on the release
+fn parse_dataset_output(stdout: &str) -> Vec<DatasetUsage> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [name, used, avail, refer, ratio] = fields[..] else {
+                return None;
+            };
+            Some(DatasetUsage {
+                name: name.to_string(),
+                used_bytes: used.parse().ok()?,
+                avail_bytes: avail.parse().ok()?,
+                refer_bytes: refer.parse().ok()?,
+                compress_ratio: ratio.trim_end_matches('x').parse().ok(),
+            })
+        })
+        .collect()
+}
 
-            // Skip until we reach config section
-            if trimmed.starts_with("config:") {
-                in_config = true;
-                continue;
-            }
+/// States a vdev or leaf can report in the config section
+const VDEV_STATES: &[&str] = &[
+    "ONLINE", "DEGRADED", "FAULTED", "OFFLINE", "UNAVAIL", "REMOVED", "AVAIL", "INUSE",
+];
 
-            if !in_config {
-                continue;
-            }
+/// Map leaf devices in `zpool status <pool>` output to their pool/vdev/role
+///
+/// Only tab-indented lines after "config:" are read, so the status:/action:
+/// blocks of an unhealthy pool and trailing notes ("cannot open", "was
+/// /dev/da5") can't leak into the topology. Each leaf's vdev is found from
+/// the indentation rather than from whichever vdev line came last, so the
+/// replacing-N/spare-N levels of a degraded pool don't steal its disks.
+fn parse_status_output(pool: &str, stdout: &str) -> HashMap<String, ZfsDriveInfo> {
+    let mut drive_map = HashMap::new();
 
-            // Stop at errors section
-            if trimmed.starts_with("errors:") {
-                break;
-            }
+    let mut current_role = ZfsRole::Data;
+    // Lines above the current one in the tree, as (indent, name)
+    let mut ancestors: Vec<(usize, String)> = Vec::new();
+    // A name too long for its column is printed alone, with the state on the next line
+    let mut pending_name: Option<(usize, String)> = None;
+    let mut in_config = false;
 
-            // Skip header lines
-            if trimmed.starts_with("NAME") || trimmed.starts_with(pool) {
-                continue;
-            }
+    for line in stdout.lines() {
+        if !in_config {
+            in_config = !line.starts_with('\t') && line.trim_start().starts_with("config:");
+            continue;
+        }
 
-            // Check for role sections - reset vdev when entering new section
-            // Use first word only to handle trailing whitespace
-            let first_word = trimmed.split_whitespace().next().unwrap_or("");
-            if first_word == "logs" {
-                current_role = ZfsRole::Slog;
-                current_vdev = String::new();
-                continue;
-            } else if first_word == "cache" {
-                current_role = ZfsRole::Cache;
-                current_vdev = String::new();
-                continue;
-            } else if first_word == "spares" {
-                current_role = ZfsRole::Spare;
-                current_vdev = String::new();
-                continue;
-            }
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Config lines are tab-indented; "errors:" or any other top-level key ends it
+        let Some(body) = line.strip_prefix('\t') else {
+            break;
+        };
+        let trimmed = body.trim();
+        let mut indent = body.len() - body.trim_start().len();
 
-            // Parse device lines
-            let parts: Vec<&str> = trimmed.split_whitespace().collect();
-            if parts.len() < 2 {
+        // Check for role sections - reset the tree when entering a new section
+        // Use first word only to handle trailing whitespace
+        let first_word = trimmed.split_whitespace().next().unwrap_or("");
+        let section = match first_word {
+            "logs" => Some(ZfsRole::Slog),
+            "cache" => Some(ZfsRole::Cache),
+            "spares" => Some(ZfsRole::Spare),
+            "special" | "dedup" => Some(ZfsRole::Data),
+            _ => None,
+        };
+        if indent == 0 && trimmed == first_word {
+            if let Some(role) = section {
+                current_role = role;
+                ancestors.clear();
+                pending_name = None;
                 continue;
             }
+        }
 
-            let device_name = parts[0];
-            let state = parts[1].to_string();
+        let joined;
+        let mut trimmed = trimmed;
+        if let Some((pending_indent, name)) = pending_name.take() {
+            joined = format!("{} {}", name, trimmed);
+            trimmed = &joined;
+            indent = pending_indent;
+        } else if !trimmed.contains(char::is_whitespace) {
+            pending_name = Some((indent, trimmed.to_string()));
+            continue;
+        }
 
-            // Track vdev names (raidz1-0, mirror-5, etc.)
-            if device_name.starts_with("raidz") || device_name.starts_with("mirror") {
-                current_vdev = device_name.to_string();
-                continue;
-            }
+        // Parse device lines
+        let Some((device_path, state)) = split_device_line(trimmed) else {
+            continue;
+        };
+        if !VDEV_STATES.contains(&state) {
+            continue;
+        }
+        let device_name = device_key(device_path);
 
-            // Skip if not a multipath device
-            if !device_name.starts_with("multipath/") {
-                continue;
-            }
+        while ancestors.last().is_some_and(|(i, _)| *i >= indent) {
+            ancestors.pop();
+        }
+        ancestors.push((indent, device_name.to_string()));
 
-            // Extract base device name (remove partition suffix if present)
-            let base_name = if let Some(idx) = device_name.rfind('p') {
-                // Check if what follows 'p' is a number (partition)
-                let after_p = &device_name[idx + 1..];
-                if after_p.chars().all(|c| c.is_ascii_digit()) {
-                    &device_name[..idx]
-                } else {
-                    device_name
-                }
+        // Column 0 holds the header and the pool itself
+        if indent == 0 {
+            continue;
+        }
+
+        // Only track multipath devices and plain da/nda disks (e.g. a SLOG
+        // or L2ARC that isn't wrapped in gmultipath)
+        if is_grouping_vdev(device_name)
+            || (!device_name.starts_with("multipath/")
+                && !device_name.starts_with("da")
+                && !device_name.starts_with("nda"))
+        {
+            continue;
+        }
+
+        // Extract base device name (remove partition suffix if present)
+        let base_name = if let Some(idx) = device_name.rfind('p') {
+            // Check if what follows 'p' is a number (partition)
+            let after_p = &device_name[idx + 1..];
+            if after_p.chars().all(|c| c.is_ascii_digit()) {
+                &device_name[..idx]
             } else {
                 device_name
-            };
+            }
+        } else {
+            device_name
+        };
 
-            drive_map.insert(
-                base_name.to_string(),
-                ZfsDriveInfo {
-                    pool: pool.to_string(),
-                    vdev: current_vdev.clone(),
-                    role: current_role.clone(),
-                    state,
-                },
-            );
-        }
+        let vdev = ancestors
+            .iter()
+            .rev()
+            .find(|(_, name)| is_grouping_vdev(name))
+            .map(|(_, name)| name.clone())
+            .unwrap_or_default();
 
-        Ok(drive_map)
+        drive_map.insert(
+            base_name.to_string(),
+            ZfsDriveInfo {
+                pool: pool.to_string(),
+                vdev,
+                role: current_role.clone(),
+                state: state.to_string(),
+            },
+        );
+    }
+
+    drive_map
+}
+
+/// Redundancy groups (raidz1-0, mirror-5, draid2:4d:8c:1s-0) as opposed to
+/// leaves and the transient replacing-N/spare-N levels
+fn is_grouping_vdev(name: &str) -> bool {
+    name.starts_with("raidz") || name.starts_with("mirror") || name.starts_with("draid")
+}
+
+/// Split a config line into (device, state)
+///
+/// The name is everything before the state and the READ/WRITE/CKSUM counters,
+/// so labels containing spaces stay intact. Lines without counters (e.g.
+/// "UNAVAIL ... was /dev/da5") fall back to the first two columns.
+fn split_device_line(line: &str) -> Option<(&str, &str)> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    // Counters may be abbreviated ("1.2K") but always start with a digit
+    let is_count = |p: &&str| p.starts_with(|c: char| c.is_ascii_digit());
+    if parts.len() >= 5 && parts[parts.len() - 3..].iter().all(is_count) {
+        let state = parts[parts.len() - 4];
+        let state_pos = line.rfind(state)?;
+        let name = line[..state_pos].trim_end();
+        if !name.is_empty() {
+            return Some((name, state));
+        }
+    }
+    if parts.len() < 2 {
+        return None;
     }
+    Some((parts[0], parts[1]))
+}
+
+/// Device name as GEOM knows it: zpool may print full /dev paths
+fn device_key(path: &str) -> &str {
+    path.strip_prefix("/dev/").unwrap_or(path)
 }
 
 impl Default for ZfsCollector {
@@ -182,3 +370,152 @@ impl Default for ZfsCollector {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unavailable_pool_skips_status_blocks_and_trailers() {
+        let stdout = concat!(
+            "  pool: dead\n",
+            " state: UNAVAIL\n",
+            "status: One or more devices could not be opened.  There are insufficient\n",
+            "\treplicas for the pool to continue functioning.\n",
+            "action: Attach the missing device and online it using 'zpool online'.\n",
+            "   see: https://openzfs.github.io/openzfs-docs/msg/ZFS-8000-3C\n",
+            "config:\n",
+            "\n",
+            "\tNAME                     STATE     READ WRITE CKSUM\n",
+            "\tdead                     UNAVAIL      0     0     0  insufficient replicas\n",
+            "\t  mirror-0               UNAVAIL      0     0     0  insufficient replicas\n",
+            "\t    da2                  FAULTED      0     0     0  corrupted data\n",
+            "\t    9876543210123456789  UNAVAIL      0     0     0  was /dev/da3\n",
+            "\n",
+            "errors: No known data errors\n",
+        );
+        let map = parse_status_output("dead", stdout);
+
+        assert_eq!(map.len(), 1, "{:?}", map.keys());
+        let da2 = &map["da2"];
+        assert_eq!(da2.pool, "dead");
+        assert_eq!(da2.vdev, "mirror-0");
+        assert_eq!(da2.role, ZfsRole::Data);
+        assert_eq!(da2.state, "FAULTED");
+        assert!(!map.contains_key("da3"));
+    }
+
+    #[test]
+    fn long_name_continues_on_the_next_line() {
+        let stdout = concat!(
+            "config:\n",
+            "\n",
+            "\tNAME                                STATE     READ WRITE CKSUM\n",
+            "\ttank                                ONLINE       0     0     0\n",
+            "\t  mirror-0                          ONLINE       0     0     0\n",
+            "\t    multipath/enc0-slot17-8XH2KL9R\n",
+            "\t                                    ONLINE       0     0     0\n",
+            "\t    da4                             ONLINE       0     0     0\n",
+        );
+        let map = parse_status_output("tank", stdout);
+
+        let long = &map["multipath/enc0-slot17-8XH2KL9R"];
+        assert_eq!(long.vdev, "mirror-0");
+        assert_eq!(long.state, "ONLINE");
+        assert_eq!(map["da4"].vdev, "mirror-0");
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn replacing_and_spare_levels_keep_the_redundancy_group() {
+        let stdout = concat!(
+            "  pool: tank\n",
+            " state: DEGRADED\n",
+            "status: One or more devices is currently being resilvered.\n",
+            "action: Wait for the resilver to complete.\n",
+            "config:\n",
+            "\n",
+            "\tNAME                STATE     READ WRITE CKSUM\n",
+            "\ttank                DEGRADED     0     0     0\n",
+            "\t  raidz2-0          DEGRADED     0     0     0\n",
+            "\t    multipath/disk1 ONLINE       0     0     0\n",
+            "\t    replacing-1     DEGRADED     0     0     0\n",
+            "\t      da5           OFFLINE      0     0     0\n",
+            "\t      da6           ONLINE       0     0     0  (resilvering)\n",
+            "\t  mirror-1          DEGRADED     0     0     0\n",
+            "\t    spare-0         DEGRADED     0     0     0\n",
+            "\t      da7           FAULTED      3   112     0  too many errors\n",
+            "\t      da9           ONLINE       0     0     0\n",
+            "\t    da8             ONLINE       0     0     0\n",
+            "\tlogs\n",
+            "\t  nda0p1            ONLINE       0     0     0\n",
+            "\tcache\n",
+            "\t  nda1              ONLINE       0     0     0\n",
+            "\tspares\n",
+            "\t  da10              AVAIL\n",
+            "\n",
+            "errors: No known data errors\n",
+        );
+        let map = parse_status_output("tank", stdout);
+
+        for disk in ["multipath/disk1", "da5", "da6"] {
+            assert_eq!(map[disk].vdev, "raidz2-0", "{}", disk);
+            assert_eq!(map[disk].role, ZfsRole::Data, "{}", disk);
+        }
+        for disk in ["da7", "da9", "da8"] {
+            assert_eq!(map[disk].vdev, "mirror-1", "{}", disk);
+        }
+        assert_eq!(map["da5"].state, "OFFLINE");
+        assert_eq!(map["da7"].state, "FAULTED");
+        assert!(!map.contains_key("replacing-1"));
+        assert!(!map.contains_key("spare-0"));
+
+        // Partition suffixes are dropped; the sections set the role
+        assert_eq!(map["nda0"].role, ZfsRole::Slog);
+        assert_eq!(map["nda0"].vdev, "");
+        assert_eq!(map["nda1"].role, ZfsRole::Cache);
+        assert_eq!(map["da10"].role, ZfsRole::Spare);
+        assert_eq!(map["da10"].state, "AVAIL");
+    }
+
+    #[test]
+    fn split_keeps_spaces_and_slashes_in_names() {
+        assert_eq!(
+            split_device_line("multipath/shelf A bay 3  ONLINE  0  0  0"),
+            Some(("multipath/shelf A bay 3", "ONLINE"))
+        );
+        assert_eq!(
+            split_device_line("multipath/enc0/2MVULJ1A  DEGRADED  0  1.2K  0"),
+            Some(("multipath/enc0/2MVULJ1A", "DEGRADED"))
+        );
+        // The state is found from the right, past a label that contains it
+        assert_eq!(
+            split_device_line("multipath/ONLINE spare  OFFLINE  0  0  0"),
+            Some(("multipath/ONLINE spare", "OFFLINE"))
+        );
+        assert_eq!(split_device_line("/dev/da3p2  ONLINE  0  0  0"), Some(("/dev/da3p2", "ONLINE")));
+        // Without counters only the first two columns are meaningful
+        assert_eq!(split_device_line("da10  AVAIL"), Some(("da10", "AVAIL")));
+        assert_eq!(split_device_line("logs"), None);
+    }
+
+    #[test]
+    fn dev_paths_and_spaced_labels_map_to_geom_names() {
+        let stdout = concat!(
+            "config:\n",
+            "\n",
+            "\tNAME                          STATE     READ WRITE CKSUM\n",
+            "\ttank                          ONLINE       0     0     0\n",
+            "\t  mirror-0                    ONLINE       0     0     0\n",
+            "\t    /dev/da3p2                ONLINE       0     0     0\n",
+            "\t    multipath/shelf A bay 3   ONLINE       0     0     0\n",
+            "\t    multipath/enc0/2MVULJ1A   ONLINE       0     0     0\n",
+        );
+        let map = parse_status_output("tank", stdout);
+
+        assert_eq!(map["da3"].vdev, "mirror-0");
+        assert_eq!(map["multipath/shelf A bay 3"].vdev, "mirror-0");
+        assert_eq!(map["multipath/enc0/2MVULJ1A"].vdev, "mirror-0");
+        assert_eq!(map.len(), 3);
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(target_os = "freebsd")]
+use super::memory::ctl_u64;
+#[cfg(target_os = "freebsd")]
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "freebsd")]
+use std::collections::hash_map::Entry;
+#[cfg(target_os = "freebsd")]
+use std::collections::HashMap;
+#[cfg(target_os = "freebsd")]
+use std::time::Instant;
+#[cfg(target_os = "freebsd")]
+use sysctl::Sysctl;
+
+/// ZFS intent log activity since the last collection
+///
+/// Read from the global `kstat.zfs.misc.zil.*` counters: every fsync/O_SYNC
+/// write becomes a ZIL commit, and its log records land either on a
+/// dedicated SLOG ("slog") or in the pool's main vdevs ("normal").
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ZilStats {
+    pub commits_per_sec: f64,
+    pub slog_writes_per_sec: f64,
+    pub slog_mbps: f64,
+    pub normal_writes_per_sec: f64,
+}
+
+impl ZilStats {
+    /// Share of log writes that went to a SLOG (None if the ZIL was idle)
+    pub fn slog_pct(&self) -> Option<f64> {
+        let total = self.slog_writes_per_sec + self.normal_writes_per_sec;
+        (total > 0.0).then(|| self.slog_writes_per_sec / total * 100.0)
+    }
+}
+
+/// Cumulative ZIL counters for delta-based rates
+#[cfg(target_os = "freebsd")]
+#[derive(Clone, Debug)]
+struct ZilCounters {
+    commits: u64,
+    slog_count: u64,
+    slog_bytes: u64,
+    normal_count: u64,
+    timestamp: Instant,
+}
+
+#[cfg(target_os = "freebsd")]
+pub struct ZilCollector {
+    previous: Option<ZilCounters>,
+    ctls: HashMap<&'static str, sysctl::Ctl>,
+}
+
+#[cfg(target_os = "freebsd")]
+impl ZilCollector {
+    pub fn new() -> Self {
+        Self {
+            previous: None,
+            ctls: HashMap::new(),
+        }
+    }
+
+    /// Rates since the previous call; zero on the first call
+    ///
+    /// Fails when zfs.ko isn't loaded (the kstat node doesn't exist).
+    pub fn collect(&mut self) -> Result<ZilStats> {
+        let current = ZilCounters {
+            commits: self.sysctl_u64("kstat.zfs.misc.zil.zil_commit_count")?,
+            slog_count: self.sysctl_u64("kstat.zfs.misc.zil.zil_itx_metaslab_slog_count")?,
+            slog_bytes: self.sysctl_u64("kstat.zfs.misc.zil.zil_itx_metaslab_slog_bytes")?,
+            normal_count: self.sysctl_u64("kstat.zfs.misc.zil.zil_itx_metaslab_normal_count")?,
+            timestamp: Instant::now(),
+        };
+
+        let stats = match self.previous {
+            Some(ref prev) => {
+                let elapsed = current.timestamp.duration_since(prev.timestamp).as_secs_f64();
+                if elapsed > 0.0 {
+                    let rate = |cur: u64, prev: u64| cur.saturating_sub(prev) as f64 / elapsed;
+                    ZilStats {
+                        commits_per_sec: rate(current.commits, prev.commits),
+                        slog_writes_per_sec: rate(current.slog_count, prev.slog_count),
+                        slog_mbps: rate(current.slog_bytes, prev.slog_bytes) / 1_000_000.0,
+                        normal_writes_per_sec: rate(current.normal_count, prev.normal_count),
+                    }
+                } else {
+                    ZilStats::default()
+                }
+            }
+            None => ZilStats::default(),
+        };
+        self.previous = Some(current);
+
+        Ok(stats)
+    }
+
+    fn sysctl_u64(&mut self, name: &'static str) -> Result<u64> {
+        let ctl = match self.ctls.entry(name) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(
+                sysctl::Ctl::new(name).with_context(|| format!("Failed to access sysctl {}", name))?,
+            ),
+        };
+        ctl_u64(name, ctl)
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+impl Default for ZilCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
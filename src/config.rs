@@ -0,0 +1,223 @@
+//! Optional TOML configuration file
+//!
+//! Read from `--config <PATH>`, or from `/usr/local/etc/sanview.toml` when
+//! that exists. Everything in it is optional; a missing default file is the
+//! same as an empty one.
+//!
+//! ```toml
+//! units = "iec"        # optional, sizes and rates in KiB/MiB/GiB
+//! clock = "absolute"   # optional, header shows HH:MM:SS ("relative", "both")
+//!
+//! [queue_depth]        # optional, drive row QD coloring
+//! warn = 8
+//! critical = 32
+//!
+//! [multipath]          # optional
+//! expected_paths = 2   # fewer working paths than this is flagged as lost redundancy
+//!
+//! [idle_drives]        # optional, drives the drive list hides when 'f' is on
+//! min_activity = 0.1   # IOPS and busy% at or below this count as idle
+//! hide = true          # start with idle drives hidden
+//!
+//! [memory_pressure]    # optional, weights of the memory panel's pressure score
+//! free = 1.0
+//! arc = 1.0
+//! swap = 2.0
+//! swap_pages_full = 1000   # paging rate (pages/s in + out) that scores as thrashing
+//!
+//! [colors]             # optional, chart series colors (name, 0-255 index or "#rrggbb")
+//! read_iops = "lightgreen"  # also write_iops, throughput, latency, queue_depth,
+//! network = "#ff8700"       # busy_max, busy_mean and cpu
+//!
+//! [aliases]          # optional, drive names for the drive list, by GEOM ident or device name
+//! "2MVULJ1A" = "tank-d3"
+//! "nda0" = "slog"
+//!
+//! [[slot]]
+//! enclosure = "ses0"   # optional, matches any enclosure when omitted
+//! slot = 3
+//! note = "hot spare - do not remove"
+//!
+//! [[enclosure]]
+//! id = "500304801f6ab37f"  # SES logical id ("Enclosure ID" in sesutil show)
+//! offset = -2              # element 2 is the bay labelled 0
+//! map = [[9, 6]]           # explicit element -> bay pairs, checked first
+//! ```
+
+use anyhow::{Context, Result};
+use crate::ui::options::ClockStyle;
+use crate::ui::theme::ChartColors;
+use crate::util::format::Units;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Used when --config isn't given
+pub const DEFAULT_CONFIG_PATH: &str = "/usr/local/etc/sanview.toml";
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// "si" or "iec"; --units takes precedence
+    pub units: Option<Units>,
+    /// "relative", "absolute" or "both"; --clock takes precedence
+    pub clock: Option<ClockStyle>,
+    /// Per-drive queue depth levels for the drive stats QD column
+    pub queue_depth: QueueDepthThresholds,
+    /// Path count every multipath device should have
+    pub multipath: MultipathSettings,
+    /// Which drives the drive list can hide as idle
+    pub idle_drives: IdleDriveSettings,
+    /// Weights of the memory panel's pressure score
+    pub memory_pressure: MemoryPressureWeights,
+    /// Operator notes attached to physical slots
+    #[serde(rename = "slot")]
+    pub slots: Vec<SlotAnnotation>,
+    /// Per-enclosure corrections from SES element index to bay label
+    #[serde(rename = "enclosure")]
+    pub enclosures: Vec<EnclosureCalibration>,
+    /// Chart series colors, over the theme's
+    pub colors: ChartColors,
+    /// Operators' names for drives, keyed by GEOM ident (serial) or device name
+    pub aliases: HashMap<String, String>,
+}
+
+/// Outstanding I/Os at which a drive's QD turns amber and then red; a
+/// queue that stays deep on one drive while its vdev peers drain is the
+/// usual sign of a disk that is slowing the whole vdev down
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct QueueDepthThresholds {
+    pub warn: f64,
+    pub critical: f64,
+}
+
+impl Default for QueueDepthThresholds {
+    fn default() -> Self {
+        Self {
+            warn: 8.0,
+            critical: 32.0,
+        }
+    }
+}
+
+/// Multipath expectations; a device with fewer working paths than
+/// `expected_paths` has lost its redundancy, whatever gmultipath's state says
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct MultipathSettings {
+    pub expected_paths: usize,
+}
+
+impl Default for MultipathSettings {
+    fn default() -> Self {
+        Self { expected_paths: 2 }
+    }
+}
+
+/// Default activity cutoff (IOPS or busy%) below which a drive counts as idle
+pub const DEFAULT_MIN_ACTIVITY: f64 = 0.1;
+
+/// Activity cutoff for hiding idle drives from the drive list, e.g. to
+/// check that a new spare really sees no traffic with `min_activity = 0`
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct IdleDriveSettings {
+    pub min_activity: f64,
+    pub hide: bool,
+}
+
+impl Default for IdleDriveSettings {
+    fn default() -> Self {
+        Self {
+            min_activity: DEFAULT_MIN_ACTIVITY,
+            hide: false,
+        }
+    }
+}
+
+/// How much each shortage counts toward the memory pressure score (see
+/// `MemoryStats::pressure_pct` for the formula); a weight of 0 leaves that
+/// term out
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default)]
+pub struct MemoryPressureWeights {
+    pub free: f64,
+    pub arc: f64,
+    pub swap: f64,
+    pub swap_pages_full: f64,
+}
+
+impl Default for MemoryPressureWeights {
+    fn default() -> Self {
+        Self {
+            free: 1.0,
+            arc: 1.0,
+            swap: 2.0,
+            swap_pages_full: 1000.0,
+        }
+    }
+}
+
+/// A human label for one bay, e.g. "pool tank mirror-0 left"
+#[derive(Clone, Debug, Deserialize)]
+pub struct SlotAnnotation {
+    pub enclosure: Option<String>,
+    pub slot: usize,
+    pub note: String,
+}
+
+/// Bay numbering for one enclosure whose SES element indices don't match
+/// the silkscreen, keyed by the enclosure's logical id (stable across
+/// reboots, unlike the sesN unit number)
+#[derive(Clone, Debug, Deserialize)]
+pub struct EnclosureCalibration {
+    pub id: String,
+    #[serde(default)]
+    pub offset: i64,
+    #[serde(default)]
+    pub map: Vec<(usize, usize)>,
+}
+
+impl EnclosureCalibration {
+    /// Bay label for an element index; None for elements before the first bay
+    pub fn slot(&self, element: usize) -> Option<usize> {
+        match self.map.iter().find(|(from, _)| *from == element) {
+            Some(&(_, to)) => Some(to),
+            None => usize::try_from(element as i64 + self.offset).ok(),
+        }
+    }
+}
+
+impl Config {
+    /// Load `path`, or the default location if it exists
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => Path::new(DEFAULT_CONFIG_PATH),
+            None => return Ok(Self::default()),
+        };
+
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    /// Note for a slot: an entry naming this enclosure wins over one without
+    pub fn slot_note(&self, enclosure: Option<&str>, slot: usize) -> Option<&str> {
+        let matching = || self.slots.iter().filter(|a| a.slot == slot);
+        matching()
+            .find(|a| a.enclosure.is_some() && a.enclosure.as_deref() == enclosure)
+            .or_else(|| matching().find(|a| a.enclosure.is_none()))
+            .map(|a| a.note.as_str())
+    }
+
+    /// Name to show for a drive: an alias for its ident wins over one for
+    /// its device name ("multipath/2MVULJ1A", "da5")
+    pub fn alias(&self, ident: Option<&str>, name: &str) -> Option<&str> {
+        ident
+            .and_then(|ident| self.aliases.get(ident))
+            .or_else(|| self.aliases.get(name))
+            .map(String::as_str)
+    }
+}
@@ -0,0 +1,266 @@
+//! Correlation report for `sanview --list-devices`
+//!
+//! Runs one collection cycle without the TUI and prints what every
+//! collector saw and what the topology correlator made of it: one line per
+//! GEOM disk, multipath device and standalone disk, as `key=value` fields
+//! so the output can be grepped or attached to a bug report.
+
+use crate::anonymize::{self, Anonymizer};
+use crate::collectors::multipath::MultipathInfo;
+use crate::collectors::ses::SesSlotInfo;
+use crate::collectors::{GeomCollector, MultipathCollector, SesCollector, ZfsCollector, ZfsDriveInfo};
+use crate::config::Config;
+use crate::domain::device::{partition_summary, MultipathDevice, Partition, PhysicalDisk};
+use crate::domain::TopologyCorrelator;
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Print the report to stdout
+///
+/// SES and ZFS are skipped when disabled; a collector that fails is
+/// reported in the output and treated as having found nothing, the same
+/// way the main loop carries on without it. With `anonymize`, serials,
+/// labels and pool names are scrubbed after correlation, so the report
+/// shows the same relationships under placeholder names.
+pub fn run(config: &Config, use_ses: bool, use_zfs: bool, anonymize: bool) -> Result<()> {
+    // GEOM statistics need two snapshots before any disk is reported
+    let mut geom = GeomCollector::new().context("Failed to initialize GEOM collector")?;
+    geom.collect()?;
+    std::thread::sleep(Duration::from_millis(250));
+    let disks = geom.collect().context("Failed to collect GEOM statistics")?;
+
+    let multipath = collected("multipath", MultipathCollector::new().collect());
+    let ses = if use_ses {
+        collected("ses", SesCollector::with_calibration(config.enclosures.clone()).collect())
+    } else {
+        println!("# ses: disabled");
+        HashMap::new()
+    };
+    let zfs = if use_zfs {
+        collected("zfs", ZfsCollector::new().collect())
+    } else {
+        println!("# zfs: disabled");
+        HashMap::new()
+    };
+
+    let (mut multipath_devices, mut standalone_disks) = TopologyCorrelator::new().correlate(
+        disks.clone(),
+        multipath.clone(),
+        ses.clone(),
+        zfs.clone(),
+    );
+
+    let (disks, multipath, zfs) = if anonymize {
+        let mut anonymizer = Anonymizer::new();
+        multipath_devices.iter_mut().for_each(|d| anonymizer.multipath_device(d));
+        standalone_disks.iter_mut().for_each(|d| anonymizer.disk(d));
+        let disks = disks
+            .into_iter()
+            .map(|mut d| {
+                anonymizer.disk(&mut d);
+                d
+            })
+            .collect();
+        let multipath = multipath
+            .into_values()
+            .map(|mut info| {
+                anonymizer.multipath_info(&mut info);
+                (info.name.clone(), info)
+            })
+            .collect();
+        let zfs = zfs
+            .into_iter()
+            .map(|(device, mut info)| {
+                anonymizer.zfs_info(&mut info);
+                (anonymize::device_name(&device), info)
+            })
+            .collect();
+        (disks, multipath, zfs)
+    } else {
+        (disks, multipath, zfs)
+    };
+
+    // Path disk -> (multipath device, active path), from the gmultipath listing
+    let paths: HashMap<&str, (&str, bool)> = multipath
+        .values()
+        .flat_map(|mp| mp.paths.iter().map(move |p| (p.device_name.as_str(), (mp.name.as_str(), p.is_active))))
+        .collect();
+    let standalone: HashSet<&str> = standalone_disks.iter().map(|d| d.device_name.as_str()).collect();
+
+    println!("# geom: {} disks", disks.len());
+    let mut sorted: Vec<&PhysicalDisk> = disks.iter().collect();
+    sorted.sort_by(|a, b| a.device_name.cmp(&b.device_name));
+    for disk in sorted {
+        let classification = classify(disk, &paths, &standalone, &multipath_devices, &standalone_disks);
+        println!(
+            "geom {} class={} rank={} ident={} multipath_parent={} {} {} -> {}",
+            disk.device_name,
+            disk.device_class(),
+            rank(disk),
+            disk.ident.as_deref().unwrap_or("-"),
+            disk.multipath_parent.as_deref().unwrap_or("-"),
+            ses_fields(ses.get(&disk.device_name)),
+            zfs_fields(zfs.get(&disk.device_name)),
+            classification
+        );
+        print_partitions(&disk.device_name, &disk.partitions);
+    }
+
+    println!("# multipath: {} devices", multipath_devices.len());
+    for mp in &multipath_devices {
+        print_multipath(mp, multipath.get(&mp.name), &disks);
+    }
+
+    println!("# standalone: {} disks", standalone_disks.len());
+    for disk in &standalone_disks {
+        println!(
+            "standalone {} class={} rank={} slot={} enclosure={} ident={} {}",
+            disk.device_name,
+            disk.device_class(),
+            rank(disk),
+            disk.slot.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()),
+            disk.enclosure.as_deref().unwrap_or("-"),
+            disk.ident.as_deref().unwrap_or("-"),
+            zfs_fields(disk.zfs_info.as_ref())
+        );
+    }
+
+    Ok(())
+}
+
+/// Collector result, or nothing after printing why
+fn collected<T: Default>(name: &str, result: Result<T>) -> T {
+    result.unwrap_or_else(|e| {
+        println!("# {}: failed: {:#}", name, e);
+        T::default()
+    })
+}
+
+/// Where a GEOM disk ended up, and why
+fn classify(
+    disk: &PhysicalDisk,
+    paths: &HashMap<&str, (&str, bool)>,
+    standalone: &HashSet<&str>,
+    multipath_devices: &[MultipathDevice],
+    standalone_disks: &[PhysicalDisk],
+) -> String {
+    let name = disk.device_name.as_str();
+    if let Some(&(mp, active)) = paths.get(name) {
+        let role = if active { "active" } else { "passive" };
+        return format!("multipath {} ({} path in gmultipath list)", mp, role);
+    }
+    if multipath_devices.iter().any(|mp| mp.name == name) {
+        return "multipath provider (stats source for its device)".to_string();
+    }
+    if let Some(ref parent) = disk.multipath_parent {
+        return format!("hidden (consumer of {} in GEOM, not in gmultipath list)", parent);
+    }
+    if standalone.contains(name) {
+        return "standalone (no multipath membership)".to_string();
+    }
+    // Standalone disks are deduplicated by ident; name the one that was kept
+    let kept = disk.ident.as_ref().and_then(|ident| {
+        standalone_disks
+            .iter()
+            .find(|d| d.ident.as_ref() == Some(ident))
+            .map(|d| d.device_name.as_str())
+    });
+    match kept {
+        Some(kept) => format!("hidden (same ident as {})", kept),
+        None => "hidden (multipath provider without a gmultipath entry)".to_string(),
+    }
+}
+
+fn print_multipath(mp: &MultipathDevice, info: Option<&MultipathInfo>, disks: &[PhysicalDisk]) {
+    // The ident comes from a path disk's GEOM ident when one has it, else
+    // from the serial in the multipath label
+    let geom_ident = mp
+        .paths
+        .iter()
+        .filter_map(|p| disks.iter().find(|d| &d.device_name == p))
+        .any(|d| d.ident.is_some());
+    let ident_source = if geom_ident { "geom" } else { "label" };
+
+    let paths: Vec<String> = mp
+        .paths
+        .iter()
+        .map(|p| if mp.active_path.as_ref() == Some(p) { format!("{}*", p) } else { p.clone() })
+        .collect();
+    // gmultipath paths that GEOM didn't report (pulled or failed)
+    let missing: Vec<&str> = info
+        .map(|i| {
+            i.paths
+                .iter()
+                .map(|p| p.device_name.as_str())
+                .filter(|p| !mp.paths.iter().any(|q| q == p))
+                .collect()
+        })
+        .unwrap_or_default();
+    // The provider is matched by name, or as the GEOM parent of a path disk
+    let parent = disks
+        .iter()
+        .filter(|d| mp.paths.contains(&d.device_name))
+        .find_map(|d| d.multipath_parent.as_deref());
+    let stats_source = if disks.iter().any(|d| d.device_name == mp.name || Some(d.device_name.as_str()) == parent) {
+        "provider"
+    } else if mp.paths.is_empty() {
+        "none"
+    } else {
+        "path"
+    };
+
+    println!(
+        "multipath {} state={:?} ident={} ident_source={} paths={} missing_paths={} slot={} enclosure={} stats={} {}",
+        mp.name,
+        mp.state,
+        mp.ident.as_deref().unwrap_or("-"),
+        ident_source,
+        list(&paths),
+        list(&missing),
+        mp.slot.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()),
+        mp.enclosure.as_deref().unwrap_or("-"),
+        stats_source,
+        zfs_fields(mp.zfs_info.as_ref())
+    );
+    print_partitions(&mp.name, &mp.partitions);
+}
+
+/// "partitions da5 -> da5p1 (freebsd-zfs), da5p2 (freebsd-swap)", for a
+/// device that has any
+fn print_partitions(device: &str, partitions: &[Partition]) {
+    if !partitions.is_empty() {
+        println!("partitions {} -> {}", device, partition_summary(partitions));
+    }
+}
+
+/// GEOM rank (1 for a physical disk), or "-" when GEOM didn't report one
+fn rank(disk: &PhysicalDisk) -> String {
+    disk.rank.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn ses_fields(ses: Option<&SesSlotInfo>) -> String {
+    match ses {
+        Some(ses) => format!("slot={} enclosure={}", ses.slot, ses.enclosure),
+        None => "slot=- enclosure=-".to_string(),
+    }
+}
+
+fn zfs_fields(zfs: Option<&ZfsDriveInfo>) -> String {
+    match zfs {
+        Some(zfs) => {
+            let vdev = if zfs.vdev.is_empty() { "-" } else { zfs.vdev.as_str() };
+            format!("pool={} vdev={} role={:?} state={}", zfs.pool, vdev, zfs.role, zfs.state)
+        }
+        None => "pool=-".to_string(),
+    }
+}
+
+/// Comma-separated, or "-" when empty
+fn list<S: AsRef<str>>(items: &[S]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.iter().map(AsRef::as_ref).collect::<Vec<_>>().join(",")
+    }
+}
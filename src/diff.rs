@@ -0,0 +1,233 @@
+//! Before/after comparison for `sanview --diff`
+//!
+//! Loads two snapshots written by `--json` (a capture with several lines
+//! contributes its last one) and prints how the aggregate figures and each
+//! drive's figures moved between them, for judging a tuning change against
+//! a benchmark run. Changes in the bad direction (less throughput, more
+//! latency or busy time) beyond `REGRESSION_PCT` are flagged with "!" and,
+//! on a terminal, drawn in red; improvements are drawn in green.
+
+use crate::domain::device::DiskStatistics;
+use crate::remote::Snapshot;
+use crate::spool::SpoolSample;
+use crate::util::format::{disk_mbps, format_count, format_datetime, units};
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, IsTerminal, Read, Write};
+use std::path::Path;
+
+/// Percent change in the bad direction reported as a regression
+const REGRESSION_PCT: f64 = 5.0;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// One compared figure, read from a `T`
+struct Metric<T> {
+    label: &'static str,
+    value: fn(&T) -> f64,
+    /// Whether an increase is an improvement (throughput) or not (latency)
+    higher_is_better: bool,
+    /// Changes smaller than this are noise on an idle drive, never flagged
+    noise_floor: f64,
+    format: fn(f64) -> String,
+}
+
+const IOPS: fn(f64) -> String = format_count;
+const MBPS: fn(f64) -> String = |v| format!("{:.1}", disk_mbps(v));
+const MS: fn(f64) -> String = |v| format!("{:.2}", v);
+const PCT: fn(f64) -> String = |v| format!("{:.0}%", v);
+const DEPTH: fn(f64) -> String = |v| format!("{:.1}", v);
+
+const DRIVE_METRICS: [Metric<DiskStatistics>; 7] = [
+    Metric { label: "R_IOPS", value: |s| s.read_iops, higher_is_better: true, noise_floor: 1.0, format: IOPS },
+    Metric { label: "W_IOPS", value: |s| s.write_iops, higher_is_better: true, noise_floor: 1.0, format: IOPS },
+    Metric { label: "R_MB", value: |s| s.read_bw_mbps, higher_is_better: true, noise_floor: 0.1, format: MBPS },
+    Metric { label: "W_MB", value: |s| s.write_bw_mbps, higher_is_better: true, noise_floor: 0.1, format: MBPS },
+    Metric { label: "R_MS", value: |s| s.read_latency_ms, higher_is_better: false, noise_floor: 0.05, format: MS },
+    Metric { label: "W_MS", value: |s| s.write_latency_ms, higher_is_better: false, noise_floor: 0.05, format: MS },
+    Metric { label: "BUSY", value: |s| s.busy_pct, higher_is_better: false, noise_floor: 1.0, format: PCT },
+];
+
+const AGGREGATE_METRICS: [Metric<SpoolSample>; 10] = [
+    Metric { label: "R_IOPS", value: |s| s.read_iops, higher_is_better: true, noise_floor: 1.0, format: IOPS },
+    Metric { label: "W_IOPS", value: |s| s.write_iops, higher_is_better: true, noise_floor: 1.0, format: IOPS },
+    Metric { label: "R_MB", value: |s| s.read_mbps, higher_is_better: true, noise_floor: 0.1, format: MBPS },
+    Metric { label: "W_MB", value: |s| s.write_mbps, higher_is_better: true, noise_floor: 0.1, format: MBPS },
+    Metric { label: "R_MS", value: |s| s.read_latency_ms, higher_is_better: false, noise_floor: 0.05, format: MS },
+    Metric { label: "W_MS", value: |s| s.write_latency_ms, higher_is_better: false, noise_floor: 0.05, format: MS },
+    Metric { label: "BUSY", value: |s| s.busy_pct, higher_is_better: false, noise_floor: 1.0, format: PCT },
+    Metric { label: "QD", value: |s| s.queue_depth, higher_is_better: false, noise_floor: 0.1, format: DEPTH },
+    Metric { label: "CPU", value: |s| s.cpu_pct, higher_is_better: false, noise_floor: 1.0, format: PCT },
+    Metric { label: "MEM", value: |s| s.memory_used_pct, higher_is_better: false, noise_floor: 1.0, format: PCT },
+];
+
+/// Direction of a change, for highlighting
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Same,
+    Better,
+    Worse,
+}
+
+impl<T> Metric<T> {
+    fn verdict(&self, before: f64, after: f64) -> Verdict {
+        let delta = after - before;
+        if delta.abs() < self.noise_floor {
+            return Verdict::Same;
+        }
+        // From zero any change counts; otherwise it has to clear the threshold
+        if before.abs() > f64::EPSILON && (delta / before * 100.0).abs() < REGRESSION_PCT {
+            return Verdict::Same;
+        }
+        if (delta > 0.0) == self.higher_is_better {
+            Verdict::Better
+        } else {
+            Verdict::Worse
+        }
+    }
+
+    /// "+1.2k (+15%)", with the percentage left out when there was nothing before
+    fn change(&self, before: f64, after: f64) -> String {
+        let delta = after - before;
+        let sign = if delta < 0.0 { "-" } else { "+" };
+        let amount = format!("{}{}", sign, (self.format)(delta.abs()));
+        if before.abs() > f64::EPSILON {
+            format!("{} ({:+.0}%)", amount, delta / before * 100.0)
+        } else if after.abs() > f64::EPSILON {
+            format!("{} (new)", amount)
+        } else {
+            amount
+        }
+    }
+}
+
+/// Load both files and print the comparison to stdout
+pub fn run(before: &Path, after: &Path) -> Result<()> {
+    let before = load_snapshot(before)?;
+    let after = load_snapshot(after)?;
+    let color = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    write_diff(&mut io::stdout().lock(), &before, &after, color)?;
+    Ok(())
+}
+
+/// The last snapshot in a `--json` capture (plain or gzip)
+pub fn load_snapshot(path: &Path) -> Result<Snapshot> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "gz") {
+        Box::new(GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+
+    let mut last = None;
+    for snapshot in serde_json::Deserializer::from_reader(BufReader::new(reader)).into_iter::<Snapshot>() {
+        last = Some(snapshot.with_context(|| format!("Failed to parse {}", path.display()))?);
+    }
+    last.with_context(|| format!("No snapshot in {}", path.display()))
+}
+
+/// Aggregate table, then one line per drive present in both snapshots
+pub fn write_diff(out: &mut impl Write, before: &Snapshot, after: &Snapshot, color: bool) -> io::Result<()> {
+    let highlight = |text: String, verdict: Verdict| -> String {
+        match verdict {
+            Verdict::Worse if color => format!("{}{}!{}", RED, text, RESET),
+            Verdict::Worse => format!("{}!", text),
+            Verdict::Better if color => format!("{}{}{}", GREEN, text, RESET),
+            _ => text,
+        }
+    };
+
+    for (label, snapshot) in [("before", before), ("after", after)] {
+        writeln!(
+            out,
+            "{:<7} {} {}",
+            label,
+            format_datetime(snapshot.timestamp_ms),
+            snapshot.hostname.as_deref().unwrap_or("")
+        )?;
+    }
+    writeln!(out, "(bandwidth in {}, totals cover multipath devices)", units().mb_label())?;
+    writeln!(out)?;
+
+    let aggregate_before = SpoolSample::from_snapshot(before);
+    let aggregate_after = SpoolSample::from_snapshot(after);
+    let mut regressions = 0;
+    writeln!(out, "{:<8} {:>10} {:>10}  CHANGE", "TOTAL", "BEFORE", "AFTER")?;
+    for metric in &AGGREGATE_METRICS {
+        let (b, a) = ((metric.value)(&aggregate_before), (metric.value)(&aggregate_after));
+        let verdict = metric.verdict(b, a);
+        regressions += usize::from(verdict == Verdict::Worse);
+        writeln!(
+            out,
+            "{:<8} {:>10} {:>10}  {}",
+            metric.label,
+            (metric.format)(b),
+            (metric.format)(a),
+            highlight(metric.change(b, a), verdict)
+        )?;
+    }
+    writeln!(out)?;
+
+    let before_drives = drive_stats(before);
+    let after_drives = drive_stats(after);
+    // Padded before highlighting, so escape codes don't upset the columns
+    const CELL: usize = 17;
+    write!(out, "{:<24}", "DEVICE")?;
+    for metric in &DRIVE_METRICS {
+        write!(out, " {:<CELL$}", metric.label)?;
+    }
+    writeln!(out)?;
+
+    let mut drive_regressions = 0;
+    for (name, b) in &before_drives {
+        let Some(a) = after_drives.get(name) else {
+            continue;
+        };
+        let mut line = format!("{:<24}", name);
+        let mut regressed = false;
+        for metric in &DRIVE_METRICS {
+            let (b, a) = ((metric.value)(b), (metric.value)(a));
+            let verdict = metric.verdict(b, a);
+            regressed |= verdict == Verdict::Worse;
+            line.push(' ');
+            line.push_str(&highlight(format!("{:<CELL$}", metric.change(b, a)), verdict));
+        }
+        drive_regressions += usize::from(regressed);
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    for (label, names) in [
+        ("only before", missing_from(&before_drives, &after_drives)),
+        ("only after", missing_from(&after_drives, &before_drives)),
+    ] {
+        if !names.is_empty() {
+            writeln!(out, "{}: {}", label, names.join(", "))?;
+        }
+    }
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{} aggregate regression(s), {} drive(s) regressed (beyond {:.0}%)",
+        regressions, drive_regressions, REGRESSION_PCT
+    )
+}
+
+/// Statistics of every multipath device and standalone disk, by name
+fn drive_stats(snapshot: &Snapshot) -> BTreeMap<&str, &DiskStatistics> {
+    snapshot
+        .multipath_devices
+        .iter()
+        .map(|d| (d.name.as_str(), &d.statistics))
+        .chain(snapshot.standalone_disks.iter().map(|d| (d.device_name.as_str(), &d.statistics)))
+        .collect()
+}
+
+/// Drives in `from` that `other` lacks (pulled, renamed or added in between)
+fn missing_from<'a>(from: &BTreeMap<&'a str, &DiskStatistics>, other: &BTreeMap<&str, &DiskStatistics>) -> Vec<&'a str> {
+    from.keys().filter(|name| !other.contains_key(*name)).copied().collect()
+}
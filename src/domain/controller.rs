@@ -0,0 +1,36 @@
+use crate::domain::device::{DiskStatistics, MultipathDevice};
+use crate::domain::vdev::aggregate;
+use std::collections::BTreeMap;
+
+/// Per-controller load summed over every multipath device's paths
+///
+/// Rates are totals across the paths routed through each controller and
+/// busy% is the mean over those paths, so an active/active pair that is
+/// balanced shows roughly equal numbers on both sides.
+pub fn controller_rollup(devices: &[MultipathDevice]) -> BTreeMap<u8, DiskStatistics> {
+    let mut by_controller: BTreeMap<u8, Vec<&DiskStatistics>> = BTreeMap::new();
+    for path in devices.iter().flat_map(|d| d.path_stats.iter()) {
+        by_controller.entry(path.controller).or_default().push(&path.statistics);
+    }
+
+    by_controller
+        .into_iter()
+        .map(|(controller, stats)| (controller, aggregate(stats.into_iter())))
+        .collect()
+}
+
+/// Display letter for a controller index (0 = A, 1 = B, ...)
+pub fn controller_label(controller: u8) -> char {
+    (b'A' + controller.min(25)) as char
+}
+
+/// Controller A's share (0-100) of the bandwidth through A and B
+///
+/// Reads and writes count alike. None when either controller carries no
+/// paths or nothing moved, since there is no balance to judge.
+pub fn controller_balance(rollup: &BTreeMap<u8, DiskStatistics>) -> Option<f64> {
+    let a = rollup.get(&0)?.total_bw_mbps();
+    let b = rollup.get(&1)?.total_bw_mbps();
+    let total = a + b;
+    (total > 0.0).then(|| a / total * 100.0)
+}
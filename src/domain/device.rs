@@ -1,7 +1,8 @@
 use crate::collectors::ZfsDriveInfo;
+use serde::{Deserialize, Serialize};
 use std::time::Instant;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PhysicalDisk {
     pub device_name: String,
     pub rank: Option<u32>,                // GEOM rank (1 = physical, higher = derived)
@@ -11,10 +12,38 @@ pub struct PhysicalDisk {
     pub enclosure: Option<String>,        // Enclosure identifier (e.g., "ses0")
     pub statistics: DiskStatistics,
     pub path_state: PathState,
+    pub zfs_info: Option<ZfsDriveInfo>,   // ZFS info for disks used directly (no gmultipath)
+    pub partitions: Vec<Partition>,       // GEOM PART providers carved from this disk
+}
+
+impl PhysicalDisk {
+    /// Driver class from the device name: "da" (SAS/SATA via CAM),
+    /// "nda" (NVMe via CAM), "multipath", or "other"
+    pub fn device_class(&self) -> &'static str {
+        let name = self.device_name.trim_start_matches("/dev/");
+        let driver = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        if name.starts_with("multipath/") {
+            "multipath"
+        } else if driver == "nda" || driver == "nvd" {
+            "nda"
+        } else if driver == "da" {
+            "da"
+        } else {
+            "other"
+        }
+    }
+}
+
+/// A partition provider stacked on a disk (from kern.geom.conftxt)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Partition {
+    pub name: String,                     // e.g., "da5p1"
+    pub part_type: Option<String>,        // gpart type, e.g., "freebsd-zfs"
+    pub size_bytes: u64,
 }
 
 /// Per-path I/O statistics for dual-controller tracking
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PathStats {
     pub device_name: String,              // e.g., "da0"
     pub controller: u8,                   // 0 = Controller A, 1 = Controller B
@@ -22,7 +51,7 @@ pub struct PathStats {
     pub statistics: DiskStatistics,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MultipathDevice {
     pub name: String,                     // "multipath/2MVULJ1A"
     pub ident: Option<String>,            // GEOM identifier of the underlying disk
@@ -33,23 +62,39 @@ pub struct MultipathDevice {
     pub path_stats: Vec<PathStats>,       // Per-path stats for controller activity LEDs
     pub zfs_info: Option<ZfsDriveInfo>,   // ZFS pool/vdev/role information
     pub slot: Option<usize>,              // Physical enclosure slot number
+    pub enclosure: Option<String>,        // SES enclosure of the active (or first) path
+    pub partitions: Vec<Partition>,       // Partitions on the multipath provider
+    #[serde(default)]
+    pub failed_paths: Vec<String>,        // Paths gmultipath lists as FAIL
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl MultipathDevice {
+    /// Paths present in GEOM that gmultipath hasn't failed
+    pub fn working_paths(&self) -> usize {
+        self.paths.iter().filter(|p| !self.failed_paths.contains(p)).count()
+    }
+
+    /// Running on fewer working paths than `expected` while still usable
+    ///
+    /// gmultipath can report OPTIMAL with a single path left (the other was
+    /// pulled before the geom noticed, or never attached), so this counts
+    /// paths instead of trusting the state. FAILED devices are excluded:
+    /// they have no working path and are flagged as failed already.
+    pub fn lacks_redundancy(&self, expected: usize) -> bool {
+        self.state != MultipathState::Failed && self.working_paths() < expected
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum MultipathState {
     Optimal,
     Degraded,
     Failed,
+    #[default]
     Unknown,
 }
 
-impl Default for MultipathState {
-    fn default() -> Self {
-        MultipathState::Unknown
-    }
-}
-
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct DiskStatistics {
     pub read_iops: f64,
     pub write_iops: f64,
@@ -59,6 +104,15 @@ pub struct DiskStatistics {
     pub write_latency_ms: f64,
     pub queue_depth: f64,
     pub busy_pct: f64,
+    // Cumulative CAM error counts since boot (devstat has none); a multipath
+    // device sums its paths, and all are zero where the kernel doesn't count
+    #[serde(default)]
+    pub read_errors: u64,
+    #[serde(default)]
+    pub write_errors: u64,
+    #[serde(default)]
+    pub other_errors: u64,
+    #[serde(skip)]
     pub timestamp: Option<Instant>,
 }
 
@@ -70,18 +124,41 @@ impl DiskStatistics {
     pub fn total_bw_mbps(&self) -> f64 {
         self.read_bw_mbps + self.write_bw_mbps
     }
+
+    pub fn total_errors(&self) -> u64 {
+        self.read_errors + self.write_errors + self.other_errors
+    }
+
+    /// Busy% rounds to 100 - the device had I/O outstanding the whole interval
+    pub fn is_saturated(&self) -> bool {
+        self.busy_pct >= 99.5
+    }
+
+    /// IOPS or busy% above `min_activity`; anything at or below it counts as idle
+    pub fn is_active(&self, min_activity: f64) -> bool {
+        self.total_iops() > min_activity || self.busy_pct > min_activity
+    }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum PathState {
     Active,
     Passive,
     Failed,
+    #[default]
     Unknown,
 }
 
-impl Default for PathState {
-    fn default() -> Self {
-        PathState::Unknown
-    }
+/// One-line partition layout for drill-down views,
+/// e.g. "da5p1 (freebsd-zfs), da5p2 (freebsd-swap)"
+pub fn partition_summary(partitions: &[Partition]) -> String {
+    partitions
+        .iter()
+        .map(|p| match p.part_type {
+            Some(ref ty) => format!("{} ({})", p.name, ty),
+            None => p.name.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
+
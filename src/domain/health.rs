@@ -0,0 +1,32 @@
+/// Busy% at which a drive counts as a hotspot (the drive rows' red level)
+pub const BUSY_ALERT_PCT: f64 = 80.0;
+
+/// Busy% at which a drive row or bay turns warn
+pub const BUSY_WARN_PCT: f64 = 50.0;
+
+/// ifi_link_state value for a link that is known to be down
+pub const LINK_STATE_DOWN: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Critical,
+    Warning,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Critical => "CRIT",
+            Severity::Warning => "WARN",
+        }
+    }
+}
+
+/// Severity of a vdev/leaf state from zpool status (None when healthy)
+pub fn zfs_state_severity(state: &str) -> Option<Severity> {
+    match state {
+        "ONLINE" | "AVAIL" | "INUSE" => None,
+        "DEGRADED" | "OFFLINE" => Some(Severity::Warning),
+        _ => Some(Severity::Critical),
+    }
+}
@@ -1,5 +1,15 @@
+pub mod controller;
 pub mod device;
+pub mod health;
+pub mod statistics;
 pub mod topology;
+pub mod vdev;
 
-pub use device::{DiskStatistics, MultipathDevice, MultipathState, PathState, PhysicalDisk};
+pub use controller::{controller_balance, controller_label, controller_rollup};
+pub use device::{
+    partition_summary, DiskStatistics, MultipathDevice, MultipathState, Partition, PathState, PhysicalDisk,
+};
+pub use health::{zfs_state_severity, Severity, BUSY_ALERT_PCT, BUSY_WARN_PCT, LINK_STATE_DOWN};
+pub use statistics::Percentiles;
 pub use topology::TopologyCorrelator;
+pub use vdev::{group_by_vdev, VdevGroup, VdevMember};
@@ -0,0 +1,134 @@
+use std::collections::VecDeque;
+
+/// Value of history slots from before the first sample
+///
+/// Histories are padded to the chart width so charts scroll from the start;
+/// padding with NaN rather than zero keeps that stretch blank on the charts
+/// and out of every mean and percentile, where zeros would pass for idle
+/// time. Anything reading a history has to skip non-finite values.
+pub const HISTORY_GAP: f64 = f64::NAN;
+
+/// Newest real sample of a history, or 0 before there is one
+pub fn latest(history: &VecDeque<f64>) -> f64 {
+    history.iter().rev().copied().find(|v| v.is_finite()).unwrap_or(0.0)
+}
+
+/// Mean of the real samples among `values`, or None if all are gaps
+pub fn mean(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Tail summary of a history series
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    /// p50/p95/p99 of `values`, or None if there are none
+    pub fn of(values: impl Iterator<Item = f64>) -> Option<Self> {
+        let mut sorted: Vec<f64> = values.filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        Some(Self {
+            p50: percentile_sorted(&sorted, 50.0),
+            p95: percentile_sorted(&sorted, 95.0),
+            p99: percentile_sorted(&sorted, 99.0),
+        })
+    }
+
+    /// Percentiles of latency history, skipping intervals with no I/O
+    ///
+    /// A zero latency sample means nothing completed in that interval, so
+    /// it says nothing about how long I/O took and would drag every
+    /// percentile toward zero; the leading `HISTORY_GAP` padding is skipped
+    /// like any other gap.
+    pub fn of_latency(history: &VecDeque<f64>) -> Option<Self> {
+        Self::of(history.iter().copied().filter(|&v| v > 0.0))
+    }
+}
+
+/// `p`th percentile (0-100) of ascending `sorted`, linearly interpolated between ranks
+fn percentile_sorted(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/// Upper bounds in ms of the latency histogram buckets, doubling from
+/// 1/8 ms; one more bucket takes everything above the last bound
+pub const LATENCY_BUCKET_BOUNDS_MS: [f64; 11] = [0.125, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0];
+
+/// Latency samples per bucket of `LATENCY_BUCKET_BOUNDS_MS` (plus the
+/// overflow bucket), skipping intervals with no I/O as `of_latency` does
+///
+/// Doubling buckets keep a cache-hit mode at 0.1ms and a miss mode at 8ms
+/// apart, where the mean would put a single line between the two.
+pub fn latency_histogram(history: &VecDeque<f64>) -> [usize; LATENCY_BUCKET_BOUNDS_MS.len() + 1] {
+    let mut counts = [0; LATENCY_BUCKET_BOUNDS_MS.len() + 1];
+    for &v in history.iter().filter(|v| v.is_finite() && **v > 0.0) {
+        let bucket = LATENCY_BUCKET_BOUNDS_MS
+            .iter()
+            .position(|&bound| v <= bound)
+            .unwrap_or(LATENCY_BUCKET_BOUNDS_MS.len());
+        counts[bucket] += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_of_nothing() {
+        assert_eq!(Percentiles::of(std::iter::empty()), None);
+        assert_eq!(Percentiles::of([f64::NAN, f64::NAN].into_iter()), None);
+        assert_eq!(Percentiles::of_latency(&VecDeque::from(vec![HISTORY_GAP, 0.0, 0.0])), None);
+    }
+
+    #[test]
+    fn percentiles_of_single_sample() {
+        let p = Percentiles::of([4.0].into_iter()).unwrap();
+        assert_eq!(p, Percentiles { p50: 4.0, p95: 4.0, p99: 4.0 });
+    }
+
+    #[test]
+    fn percentiles_interpolate_between_ranks() {
+        // Out of order, with gaps: 1..=5 after sorting
+        let p = Percentiles::of([5.0, HISTORY_GAP, 1.0, 4.0, 2.0, 3.0].into_iter()).unwrap();
+        assert_eq!(p.p50, 3.0);
+        assert!((p.p95 - 4.8).abs() < 1e-9);
+        assert!((p.p99 - 4.96).abs() < 1e-9);
+
+        let sorted = [10.0, 20.0];
+        assert_eq!(percentile_sorted(&sorted, 25.0), 12.5);
+    }
+
+    #[test]
+    fn percentile_clamps_to_range() {
+        let sorted = [1.0, 2.0, 3.0];
+        assert_eq!(percentile_sorted(&sorted, 0.0), 1.0);
+        assert_eq!(percentile_sorted(&sorted, -10.0), 1.0);
+        assert_eq!(percentile_sorted(&sorted, 100.0), 3.0);
+        assert_eq!(percentile_sorted(&sorted, 150.0), 3.0);
+    }
+
+    #[test]
+    fn latency_percentiles_skip_idle_intervals() {
+        let history = VecDeque::from(vec![HISTORY_GAP, 0.0, 2.0, 0.0, 2.0]);
+        let p = Percentiles::of_latency(&history).unwrap();
+        assert_eq!(p, Percentiles { p50: 2.0, p95: 2.0, p99: 2.0 });
+    }
+}
@@ -86,29 +86,52 @@ impl TopologyCorrelator {
             }
 
             // Use statistics from the multipath device itself if available in disk_map,
-            // otherwise use active path stats, or first available, or default
-            let stats = if let Some(mp_disk) = disk_map.remove(&mp_name) {
+            // otherwise use active path stats, or first available, or default.
+            // The provider is looked up by name, then as the parent GEOM recorded
+            // for the path disks, in case gmultipath and devstat spell it differently;
+            // either way it still reports I/O when the path disks are missing
+            let mp_disk = disk_map.remove(&mp_name).or_else(|| {
+                let parent = path_disks.iter().find_map(|d| d.multipath_parent.clone())?;
+                debug!("Multipath device {} found in GEOM as {}", mp_name, parent);
+                disk_map.remove(&parent)
+            });
+            let partitions = mp_disk.as_ref().map(|d| d.partitions.clone()).unwrap_or_default();
+            let mut stats = if let Some(mp_disk) = mp_disk {
                 // Prefer multipath device stats (aggregated by GEOM)
                 mp_disk.statistics
             } else if path_disks.is_empty() {
-                debug!("Multipath device {} has no associated physical disks in GEOM snapshot", mp_name);
+                debug!("Multipath device {} has neither its provider nor any path in the GEOM snapshot", mp_name);
                 DiskStatistics::default()
-            } else if let Some(ref active) = active_path {
+            } else {
                 path_disks
                     .iter()
-                    .find(|d| d.device_name == *active)
+                    .find(|d| Some(&d.device_name) == active_path.as_ref())
+                    .or_else(|| path_disks.first())
                     .map(|d| d.statistics.clone())
                     .unwrap_or_default()
-            } else {
-                path_disks.first().map(|d| d.statistics.clone()).unwrap_or_default()
             };
+            // CAM counts errors on the path disks, never on the multipath provider
+            stats.read_errors = path_disks.iter().map(|d| d.statistics.read_errors).sum();
+            stats.write_errors = path_disks.iter().map(|d| d.statistics.write_errors).sum();
+            stats.other_errors = path_disks.iter().map(|d| d.statistics.other_errors).sum();
 
             let paths: Vec<String> = path_disks.iter().map(|d| d.device_name.clone()).collect();
+            let failed_paths: Vec<String> = mp_info
+                .paths
+                .iter()
+                .filter(|p| p.is_failed && paths.contains(&p.device_name))
+                .map(|p| p.device_name.clone())
+                .collect();
 
-            // Use the serial from the multipath info (extracted from multipath name)
-            let ident = Some(mp_info.serial.clone());
+            // Prefer the GEOM ident of the underlying disk so a replacement disk
+            // relabeled with the same multipath name is still distinguishable;
+            // fall back to the serial extracted from the multipath name
+            let ident = path_disks
+                .iter()
+                .find_map(|d| d.ident.clone())
+                .or_else(|| Some(mp_info.serial.clone()));
 
-            // Also update the physical disks with this serial
+            // Also update the physical disks with this identity
             for disk in &mut path_disks {
                 disk.ident = ident.clone();
             }
@@ -136,6 +159,13 @@ impl TopologyCorrelator {
                 active_path
             );
 
+            // Enclosure as seen through the active path, else any path that has one
+            let enclosure = path_disks
+                .iter()
+                .filter(|d| Some(&d.device_name) == active_path.as_ref())
+                .chain(path_disks.iter())
+                .find_map(|d| d.enclosure.clone());
+
             // Look up ZFS info for this multipath device
             let zfs = zfs_info.get(&mp_name).cloned();
 
@@ -149,6 +179,9 @@ impl TopologyCorrelator {
                 path_stats: path_stats_list,
                 zfs_info: zfs,
                 slot,
+                enclosure,
+                partitions,
+                failed_paths,
             });
         }
 
@@ -162,11 +195,30 @@ impl TopologyCorrelator {
             }
         });
 
+        // A consumer of a multipath geom is never standalone, even when its
+        // name didn't line up with a path in the gmultipath listing
+        disk_map.retain(|name, disk| match disk.multipath_parent {
+            Some(ref parent) => {
+                debug!("{} is a consumer of {}, not standalone", name, parent);
+                false
+            }
+            None => true,
+        });
+
         // Remaining disks in disk_map are standalone (not part of multipath)
         // But we still need to deduplicate by WWN
-        let deduplicated_standalone = self.deduplicate_by_wwn(disk_map);
+        let deduplicated_standalone = self.deduplicate_by_wwn(disk_map, &zfs_info);
         standalone_disks.extend(deduplicated_standalone);
 
+        // Attach ZFS info to standalone disks used directly by a pool
+        // (e.g. a SLOG or L2ARC on a plain da/nda device)
+        for disk in &mut standalone_disks {
+            disk.zfs_info = zfs_info.get(&disk.device_name).cloned();
+            if let Some(ref zfs) = disk.zfs_info {
+                debug!("Standalone {} -> pool {} ({:?})", disk.device_name, zfs.pool, zfs.role);
+            }
+        }
+
         debug!(
             "Topology: {} multipath devices, {} standalone disks",
             multipath_devices.len(),
@@ -177,8 +229,13 @@ impl TopologyCorrelator {
     }
 
     /// Deduplicate standalone disks by identifier (WWN, serial, GEOM ident)
-    /// If multiple disks have the same identifier, they're the same physical disk through different paths
-    fn deduplicate_by_wwn(&self, disk_map: HashMap<String, PhysicalDisk>) -> Vec<PhysicalDisk> {
+    /// If multiple disks have the same identifier, they're the same physical disk through different paths.
+    /// The path ZFS references is kept so pool membership isn't lost; otherwise the lowest name wins.
+    fn deduplicate_by_wwn(
+        &self,
+        disk_map: HashMap<String, PhysicalDisk>,
+        zfs_info: &HashMap<String, ZfsDriveInfo>,
+    ) -> Vec<PhysicalDisk> {
         let mut ident_groups: HashMap<String, Vec<PhysicalDisk>> = HashMap::new();
         let mut no_ident_disks = Vec::new();
 
@@ -204,6 +261,11 @@ impl TopologyCorrelator {
                 );
                 // TODO: We could aggregate stats here if needed
             }
+            disks.sort_by(|a, b| {
+                let a_zfs = zfs_info.contains_key(&a.device_name);
+                let b_zfs = zfs_info.contains_key(&b.device_name);
+                b_zfs.cmp(&a_zfs).then_with(|| a.device_name.cmp(&b.device_name))
+            });
             result.push(disks.remove(0));
         }
 
@@ -0,0 +1,149 @@
+use crate::collectors::{ZfsDriveInfo, ZfsRole};
+use crate::domain::device::{DiskStatistics, MultipathDevice, PhysicalDisk};
+use std::collections::BTreeMap;
+
+/// A member drive of a vdev group
+#[derive(Clone, Debug)]
+pub struct VdevMember {
+    pub name: String,
+    pub ident: Option<String>,            // GEOM ident, for looking up an alias
+    pub slot: Option<usize>,
+    pub statistics: DiskStatistics,
+}
+
+/// Aggregated view of one vdev (raidz/mirror group, single-disk vdev,
+/// or the log/cache/spare set of a pool)
+#[derive(Clone, Debug)]
+pub struct VdevGroup {
+    pub pool: String,
+    pub vdev: String,                     // "raidz1-0", "mirror-5", disk name, or "logs"/"cache"/"spares"
+    pub role: ZfsRole,
+    pub members: Vec<VdevMember>,
+    pub statistics: DiskStatistics,       // Summed IOPS/bandwidth/queue, averaged busy/latency
+}
+
+/// Write IOPS below which a vdev is too quiet to judge for read-modify-write
+const RMW_MIN_WRITE_IOPS: f64 = 50.0;
+/// Reads per write at which a write-heavy raidz vdev looks like it is
+/// doing read-modify-write
+const RMW_READ_RATIO: f64 = 0.5;
+
+impl VdevGroup {
+    /// Reads per write on a raidz/draid vdev whose traffic looks like
+    /// read-modify-write, None otherwise
+    ///
+    /// Heuristic: writes that are not full-stripe make raidz read the rest
+    /// of the stripe first, so a vdev that moves more data in writes than
+    /// in reads yet issues many (small) reads per write is suspect. Pure
+    /// read workloads don't trip it since they aren't write-heavy.
+    pub fn rmw_ratio(&self) -> Option<f64> {
+        if self.role != ZfsRole::Data
+            || !(self.vdev.starts_with("raidz") || self.vdev.starts_with("draid"))
+        {
+            return None;
+        }
+        let stats = &self.statistics;
+        if stats.write_iops < RMW_MIN_WRITE_IOPS || stats.write_bw_mbps <= stats.read_bw_mbps {
+            return None;
+        }
+        let ratio = stats.read_iops / stats.write_iops;
+        (ratio >= RMW_READ_RATIO).then_some(ratio)
+    }
+}
+
+/// Group correlated devices by (pool, vdev)
+///
+/// Standalone disks are included when they belong to a pool. Groups are
+/// ordered by pool, then role (data, log, cache, spare), then vdev name.
+pub fn group_by_vdev(
+    multipath_devices: &[MultipathDevice],
+    standalone_disks: &[PhysicalDisk],
+) -> Vec<VdevGroup> {
+    let mut groups: BTreeMap<(String, u8, String), VdevGroup> = BTreeMap::new();
+
+    let members = multipath_devices
+        .iter()
+        .filter_map(|d| d.zfs_info.as_ref().map(|z| (z, &d.name, &d.ident, d.slot, &d.statistics)))
+        .chain(standalone_disks.iter().filter_map(|d| {
+            d.zfs_info.as_ref().map(|z| (z, &d.device_name, &d.ident, d.slot, &d.statistics))
+        }));
+
+    for (zfs, name, ident, slot, stats) in members {
+        let vdev = vdev_label(zfs, name);
+        let group = groups
+            .entry((zfs.pool.clone(), role_order(&zfs.role), vdev.clone()))
+            .or_insert_with(|| VdevGroup {
+                pool: zfs.pool.clone(),
+                vdev,
+                role: zfs.role.clone(),
+                members: Vec::new(),
+                statistics: DiskStatistics::default(),
+            });
+
+        group.members.push(VdevMember {
+            name: name.clone(),
+            ident: ident.clone(),
+            slot,
+            statistics: stats.clone(),
+        });
+    }
+
+    let mut result: Vec<VdevGroup> = groups.into_values().collect();
+    for group in &mut result {
+        group.members.sort_by(|a, b| a.slot.cmp(&b.slot).then_with(|| a.name.cmp(&b.name)));
+        group.statistics = aggregate(group.members.iter().map(|m| &m.statistics));
+    }
+    result
+}
+
+/// Sum rates, queue depth and error counts, average busy% and latency
+pub fn aggregate<'a>(stats: impl Iterator<Item = &'a DiskStatistics>) -> DiskStatistics {
+    let mut total = DiskStatistics::default();
+    let mut count = 0usize;
+
+    for s in stats {
+        total.read_iops += s.read_iops;
+        total.write_iops += s.write_iops;
+        total.read_bw_mbps += s.read_bw_mbps;
+        total.write_bw_mbps += s.write_bw_mbps;
+        total.read_latency_ms += s.read_latency_ms;
+        total.write_latency_ms += s.write_latency_ms;
+        total.queue_depth += s.queue_depth;
+        total.busy_pct += s.busy_pct;
+        total.read_errors += s.read_errors;
+        total.write_errors += s.write_errors;
+        total.other_errors += s.other_errors;
+        count += 1;
+    }
+
+    if count > 0 {
+        let n = count as f64;
+        total.read_latency_ms /= n;
+        total.write_latency_ms /= n;
+        total.busy_pct /= n;
+    }
+    total
+}
+
+/// Vdev name for grouping; devices without a vdev are either single-disk
+/// top-level vdevs (data) or part of the pool's log/cache/spare set
+fn vdev_label(zfs: &ZfsDriveInfo, device_name: &str) -> String {
+    if !zfs.vdev.is_empty() {
+        return zfs.vdev.clone();
+    }
+    match zfs.role {
+        ZfsRole::Data => device_name.to_string(),
+        ZfsRole::Slog => "logs".to_string(),
+        ZfsRole::Cache => "cache".to_string(),
+        ZfsRole::Spare => "spares".to_string(),
+    }
+}
+
+fn role_order(role: &ZfsRole) -> u8 {
+    match role {
+        ZfsRole::Data => 0,
+        ZfsRole::Slog => 1,
+        ZfsRole::Cache => 2,
+        ZfsRole::Spare => 3,
+    }
+}
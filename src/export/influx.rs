@@ -0,0 +1,204 @@
+//! InfluxDB line-protocol exporter over UDP
+//!
+//! Formats storage and system metrics as line protocol and pushes them to a
+//! Telegraf/InfluxDB UDP listener. Measurement names and tag keys are fixed so
+//! the resulting schema doesn't churn between cycles; missing tag values are
+//! written as "-" rather than omitted.
+
+use crate::collectors::{CpuStats, MemoryStats, NetworkStats, ZfsRole};
+use crate::domain::device::{DiskStatistics, MultipathDevice, PhysicalDisk};
+use anyhow::{Context, Result};
+use log::debug;
+use std::fmt::Write;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct InfluxUdpSink {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl InfluxUdpSink {
+    /// Create a sink sending to `addr` (host:port)
+    pub fn new(addr: &str) -> Result<Self> {
+        let target = addr
+            .to_socket_addrs()
+            .with_context(|| format!("Failed to resolve InfluxDB address {}", addr))?
+            .next()
+            .with_context(|| format!("No address found for {}", addr))?;
+
+        let bind_addr = if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+        let socket = UdpSocket::bind(bind_addr)
+            .context("Failed to bind UDP socket for InfluxDB output")?;
+
+        Ok(Self { socket, target })
+    }
+
+    /// Format all metrics for this cycle and send them as a single datagram
+    pub fn send(
+        &self,
+        multipath_devices: &[MultipathDevice],
+        standalone_disks: &[PhysicalDisk],
+        cpu_stats: &CpuStats,
+        memory_stats: &MemoryStats,
+        network_stats: &[NetworkStats],
+    ) -> Result<()> {
+        let timestamp_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+
+        let payload = format_line_protocol(
+            multipath_devices,
+            standalone_disks,
+            cpu_stats,
+            memory_stats,
+            network_stats,
+            timestamp_ns,
+        );
+
+        self.socket
+            .send_to(payload.as_bytes(), self.target)
+            .with_context(|| format!("Failed to send metrics to {}", self.target))?;
+
+        debug!("Sent {} bytes of line protocol to {}", payload.len(), self.target);
+        Ok(())
+    }
+}
+
+/// Build the line-protocol payload for one cycle
+pub fn format_line_protocol(
+    multipath_devices: &[MultipathDevice],
+    standalone_disks: &[PhysicalDisk],
+    cpu_stats: &CpuStats,
+    memory_stats: &MemoryStats,
+    network_stats: &[NetworkStats],
+    timestamp_ns: u128,
+) -> String {
+    let mut out = String::new();
+
+    for dev in multipath_devices {
+        let (pool, vdev, role) = match dev.zfs_info {
+            Some(ref z) => (z.pool.as_str(), z.vdev.as_str(), role_tag(&z.role)),
+            None => ("-", "-", "-"),
+        };
+        write_disk_line(&mut out, &dev.name, pool, vdev, role, &dev.statistics, timestamp_ns);
+    }
+
+    for disk in standalone_disks {
+        write_disk_line(&mut out, &disk.device_name, "-", "-", "-", &disk.statistics, timestamp_ns);
+    }
+
+    // Aggregate CPU plus one line per core
+    if !cpu_stats.cores.is_empty() {
+        let n = cpu_stats.cores.len() as f64;
+        let user = cpu_stats.cores.iter().map(|c| c.user_pct).sum::<f64>() / n;
+        let system = cpu_stats.cores.iter().map(|c| c.system_pct).sum::<f64>() / n;
+        let interrupt = cpu_stats.cores.iter().map(|c| c.interrupt_pct).sum::<f64>() / n;
+        let total = cpu_stats.cores.iter().map(|c| c.total_pct).sum::<f64>() / n;
+        let _ = writeln!(
+            out,
+            "sanview_cpu,core=all user={:.2},system={:.2},interrupt={:.2},total={:.2} {}",
+            user, system, interrupt, total, timestamp_ns
+        );
+        for core in &cpu_stats.cores {
+            let _ = writeln!(
+                out,
+                "sanview_cpu,core={} user={:.2},system={:.2},interrupt={:.2},total={:.2} {}",
+                core.core_id, core.user_pct, core.system_pct, core.interrupt_pct, core.total_pct, timestamp_ns
+            );
+        }
+    }
+
+    let _ = writeln!(
+        out,
+        "sanview_mem total={}i,active={}i,inactive={}i,laundry={}i,wired={}i,free={}i,committed={}i,\
+         reclaimable={}i,used_pct={:.2},swap_total={}i,swap_used={}i,arc_size={}i,arc_ratio={:.2} {}",
+        memory_stats.total_bytes,
+        memory_stats.active_bytes,
+        memory_stats.inactive_bytes,
+        memory_stats.laundry_bytes,
+        memory_stats.wired_bytes,
+        memory_stats.free_bytes,
+        memory_stats.committed_bytes,
+        memory_stats.reclaimable_bytes,
+        memory_stats.used_pct,
+        memory_stats.swap_total_bytes,
+        memory_stats.swap_used_bytes,
+        memory_stats.arc_total_bytes,
+        memory_stats.arc_ratio,
+        timestamp_ns
+    );
+
+    for iface in network_stats {
+        let _ = writeln!(
+            out,
+            "sanview_net,interface={} rx_bps={:.0},tx_bps={:.0},rx_pps={:.0},tx_pps={:.0},link_state={}i {}",
+            escape_tag(&iface.name),
+            iface.rx_bytes_per_sec,
+            iface.tx_bytes_per_sec,
+            iface.rx_packets_per_sec,
+            iface.tx_packets_per_sec,
+            iface.link_state,
+            timestamp_ns
+        );
+    }
+
+    out
+}
+
+fn write_disk_line(
+    out: &mut String,
+    device: &str,
+    pool: &str,
+    vdev: &str,
+    role: &str,
+    stats: &DiskStatistics,
+    timestamp_ns: u128,
+) {
+    let _ = writeln!(
+        out,
+        "sanview_disk,device={},pool={},vdev={},role={} read_iops={:.2},write_iops={:.2},\
+         read_mbps={:.3},write_mbps={:.3},read_latency_ms={:.3},write_latency_ms={:.3},\
+         queue_depth={:.0},busy={:.2} {}",
+        escape_tag(device),
+        escape_tag(tag_or_dash(pool)),
+        escape_tag(tag_or_dash(vdev)),
+        role,
+        stats.read_iops,
+        stats.write_iops,
+        stats.read_bw_mbps,
+        stats.write_bw_mbps,
+        stats.read_latency_ms,
+        stats.write_latency_ms,
+        stats.queue_depth,
+        stats.busy_pct,
+        timestamp_ns
+    );
+}
+
+fn role_tag(role: &ZfsRole) -> &'static str {
+    match role {
+        ZfsRole::Data => "data",
+        ZfsRole::Slog => "log",
+        ZfsRole::Cache => "cache",
+        ZfsRole::Spare => "spare",
+    }
+}
+
+/// Influx rejects empty tag values, so keep the tag and use a placeholder
+fn tag_or_dash(s: &str) -> &str {
+    if s.is_empty() { "-" } else { s }
+}
+
+/// Escape commas, spaces and equals signs in tag keys/values
+fn escape_tag(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == ',' || c == ' ' || c == '=' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
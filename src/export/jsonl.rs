@@ -0,0 +1,89 @@
+//! JSON lines output for `--json`
+//!
+//! One snapshot per line to stdout, or with `--output` to a file. A path
+//! ending in ".gz" (or `--compress`) is gzip-compressed; the stream is
+//! sync-flushed every few seconds so `zcat` can follow a live capture, and
+//! has to be finished on exit or the file lacks its gzip trailer.
+
+use crate::remote::Snapshot;
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::File;
+use std::io::{self, BufWriter, Stdout, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How often file output is pushed to disk (stdout is flushed every line)
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+enum Sink {
+    Stdout(Stdout),
+    File(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+pub struct JsonLinesWriter {
+    sink: Sink,
+    last_flush: Instant,
+}
+
+impl JsonLinesWriter {
+    pub fn stdout() -> Self {
+        Self {
+            sink: Sink::Stdout(io::stdout()),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Create (truncate) `path`, compressed if asked to or if it ends in ".gz"
+    pub fn create(path: &Path, compress: bool) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        let file = BufWriter::new(file);
+        let compress = compress || path.extension().is_some_and(|ext| ext == "gz");
+
+        Ok(Self {
+            sink: if compress {
+                Sink::Gzip(GzEncoder::new(file, Compression::default()))
+            } else {
+                Sink::File(file)
+            },
+            last_flush: Instant::now(),
+        })
+    }
+
+    /// Append one snapshot as a line
+    pub fn write(&mut self, snapshot: &Snapshot) -> io::Result<()> {
+        let out: &mut dyn Write = match self.sink {
+            Sink::Stdout(ref mut out) => out,
+            Sink::File(ref mut out) => out,
+            Sink::Gzip(ref mut out) => out,
+        };
+        serde_json::to_writer(&mut *out, snapshot)?;
+        writeln!(out)?;
+
+        if matches!(self.sink, Sink::Stdout(_)) || self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.last_flush = Instant::now();
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.sink {
+            Sink::Stdout(ref mut out) => out.flush(),
+            Sink::File(ref mut out) => out.flush(),
+            Sink::Gzip(ref mut out) => out.flush(),
+        }
+    }
+
+    /// Flush everything and, for gzip, write the trailer
+    pub fn finish(self) -> io::Result<()> {
+        match self.sink {
+            Sink::Stdout(mut out) => out.flush(),
+            Sink::File(mut out) => out.flush(),
+            Sink::Gzip(out) => out.finish()?.flush(),
+        }
+    }
+}
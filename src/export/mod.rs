@@ -0,0 +1,5 @@
+pub mod influx;
+pub mod jsonl;
+
+pub use influx::InfluxUdpSink;
+pub use jsonl::JsonLinesWriter;
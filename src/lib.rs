@@ -1,3 +1,21 @@
+pub mod anonymize;
+#[cfg(target_os = "freebsd")]
+pub mod check;
 pub mod collectors;
+pub mod config;
+#[cfg(target_os = "freebsd")]
+pub mod devices;
+pub mod diff;
 pub mod domain;
+pub mod export;
+#[cfg(target_os = "freebsd")]
+pub mod monitor;
+pub mod plain;
+pub mod remote;
+pub mod simulate;
+pub mod spool;
 pub mod ui;
+pub mod util;
+
+#[cfg(target_os = "freebsd")]
+pub use monitor::{Due, Monitor, MonitorOptions};
@@ -1,13 +1,37 @@
-use anyhow::{Context, Result};
+#[cfg(target_os = "freebsd")]
+use anyhow::Context;
+use anyhow::Result;
 use clap::Parser;
-use sanview::collectors::{
-    BhyveCollector, CpuCollector, GeomCollector, JailCollector, MemoryCollector,
-    MultipathCollector, NetworkCollector, SesCollector, ZfsCollector,
-};
-use sanview::domain::TopologyCorrelator;
-use sanview::ui::{run_tui, AppState};
+#[cfg(target_os = "freebsd")]
+use sanview::anonymize::Anonymizer;
+#[cfg(target_os = "freebsd")]
+use sanview::collectors::InterfaceFilter;
+use sanview::config::Config;
+#[cfg(target_os = "freebsd")]
+use sanview::export::{InfluxUdpSink, JsonLinesWriter};
+#[cfg(target_os = "freebsd")]
+use sanview::monitor::{Due, Monitor, MonitorOptions};
+#[cfg(target_os = "freebsd")]
+use sanview::plain::write_plain;
+#[cfg(target_os = "freebsd")]
+use sanview::remote::SnapshotServer;
+#[cfg(target_os = "freebsd")]
+use sanview::spool::SpoolWriter;
+use sanview::ui::{run_tui, AppState, ClockStyle, OverviewPanels, Theme, UiOptions};
+use sanview::util::format::Units;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+#[cfg(target_os = "freebsd")]
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+#[cfg(target_os = "freebsd")]
+use std::time::Instant;
+
+/// Set by SIGINT/SIGTERM in headless modes so the collection loop exits cleanly
+#[cfg(target_os = "freebsd")]
+static STOP: AtomicBool = AtomicBool::new(false);
 
 #[derive(Parser, Debug)]
 #[command(name = "sanview")]
@@ -17,156 +41,473 @@ struct Args {
     /// Refresh interval in milliseconds
     #[arg(short, long, default_value_t = 250, value_parser = clap::value_parser!(u64).range(50..=10000))]
     refresh: u64,
+
+    /// Disk stats interval in milliseconds (default: --refresh)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(50..=10000))]
+    refresh_storage: Option<u64>,
+
+    /// CPU, memory and network stats interval in milliseconds (default: --refresh)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(50..=10000))]
+    refresh_system: Option<u64>,
+
+    /// Per-drive IOPS, MB/s and busy figures show the mean over this window in
+    /// milliseconds (sparklines stay per-sample); 0 shows every sample as is
+    #[arg(long, value_name = "MS", default_value_t = 1000, value_parser = clap::value_parser!(u64).range(0..=60000))]
+    average: u64,
+
+    /// Samples of history retained per chart series (independent of terminal width)
+    #[arg(long, value_name = "SAMPLES", default_value_t = sanview::ui::state::DEFAULT_MAX_HISTORY)]
+    history: usize,
+
+    /// Send metrics as InfluxDB line protocol over UDP on each slow refresh
+    #[arg(long, value_name = "HOST:PORT")]
+    influx_udp: Option<String>,
+
+    /// Hide y-axis scale labels on the storage charts
+    #[arg(long)]
+    no_chart_labels: bool,
+
+    /// Only show network interfaces matching these globs (comma-separated, e.g. "ix*,lagg*")
+    #[arg(long, value_name = "GLOBS", value_delimiter = ',')]
+    net_include: Vec<String>,
+
+    /// Hide network interfaces matching these globs; replaces the default
+    /// list (lo*, pflog*, enc*, tap*, epair*, bridge*, gif*, stf*), "" hides none
+    #[arg(long, value_name = "GLOBS", value_delimiter = ',')]
+    net_exclude: Option<Vec<String>>,
+
+    /// Print a compatibility report (kernel, struct layouts, collectors) and exit
+    #[arg(long)]
+    check: bool,
+
+    /// Run one collection cycle, print every device and how it was correlated, and exit
+    #[arg(long)]
+    list_devices: bool,
+
+    /// Skip bhyve VM collection and hide the VM panel
+    #[arg(long)]
+    no_bhyve: bool,
+
+    /// Skip jail collection (jls) and hide the jail panel
+    #[arg(long)]
+    no_jails: bool,
+
+    /// Skip ZFS topology collection (zpool); drives show no pool or role
+    #[arg(long)]
+    no_zfs: bool,
+
+    /// Skip network interface collection and hide the network panel
+    #[arg(long)]
+    no_network: bool,
+
+    /// Skip SES enclosure probing; drives show no slot numbers
+    #[arg(long)]
+    no_ses: bool,
+
+    /// Let 'l' switch the SES locate LED of the selected bay (writes to /dev/ses*, needs root)
+    #[arg(long, conflicts_with = "connect")]
+    allow_control: bool,
+
+    /// Front panel title, e.g. the enclosure model (default: "Storage Array")
+    #[arg(long, value_name = "TEXT")]
+    title: Option<String>,
+
+    /// Start in the alerts view, listing only unhealthy devices, pools and links ('a' toggles)
+    #[arg(long)]
+    alerts: bool,
+
+    /// Draw with plain ASCII only (for consoles without LED, block or braille glyphs)
+    #[arg(long)]
+    ascii: bool,
+
+    /// Write log lines as JSON objects (timestamp, level, target, message)
+    #[arg(long)]
+    log_json: bool,
+
+    /// Replace serials, multipath labels, pool, dataset, VM and jail names, host names and jail
+    /// addresses with stable placeholders (TUI, --json, --serve, --plain, --list-devices)
+    #[arg(long, conflicts_with_all = ["allow_control", "connect"])]
+    anonymize: bool,
+
+    /// Print one JSON snapshot per line to stdout instead of running the TUI
+    #[arg(long, conflicts_with_all = ["serve", "connect"])]
+    json: bool,
+
+    /// With --json, write the snapshots to PATH instead of stdout (gzip if it ends in .gz)
+    #[arg(long, value_name = "PATH", requires = "json")]
+    output: Option<PathBuf>,
+
+    /// With --output, gzip the file whatever its name
+    #[arg(long, requires = "output")]
+    compress: bool,
+
+    /// Redraw a plain text device and system table in the terminal instead of the TUI
+    /// (no alternate screen or mouse capture; Ctrl-C to stop)
+    #[arg(long, conflicts_with_all = ["serve", "connect", "json"])]
+    plain: bool,
+
+    /// With --json, stop after this long (e.g. 30s, 5m); runs until interrupted otherwise
+    #[arg(long, value_name = "DURATION", requires = "json", value_parser = parse_duration)]
+    duration: Option<Duration>,
+
+    /// With --json, time between snapshots (e.g. 1s); rates cover the whole interval
+    #[arg(long, value_name = "DURATION", requires = "json", value_parser = parse_duration)]
+    interval: Option<Duration>,
+
+    /// Run headless and stream JSON snapshots to clients of this Unix socket
+    #[arg(long, value_name = "PATH", conflicts_with = "connect")]
+    serve: Option<PathBuf>,
+
+    /// Show snapshots from a --serve socket (path or host:port) instead of local collectors
+    #[arg(long, value_name = "SOCKET")]
+    connect: Option<String>,
+
+    /// Show synthetic data (animated I/O, two pools, VMs and jails) instead of local
+    /// collectors, for working on the UI without FreeBSD or a storage array
+    #[arg(long, conflicts_with_all = ["connect", "serve", "json", "plain", "spool", "allow_control"])]
+    simulate: bool,
+
+    /// Exit with an error when no collection cycle has succeeded for this long (e.g. 10s),
+    /// so a supervisor can restart sanview
+    #[arg(long, value_name = "DURATION", conflicts_with = "connect", value_parser = parse_duration)]
+    watchdog: Option<Duration>,
+
+    /// Keep the last hour of aggregate samples in DIR/sanview.spool, synced every few seconds
+    #[arg(long, value_name = "DIR", conflicts_with = "connect")]
+    spool: Option<PathBuf>,
+
+    /// Print the samples saved by --spool in DIR (e.g. after a crash) and exit
+    #[arg(long, value_name = "DIR")]
+    replay_spool: Option<PathBuf>,
+
+    /// Compare two --json captures (the last snapshot of each) and print the per-drive
+    /// and aggregate changes, flagging regressions, then exit
+    #[arg(long, num_args = 2, value_names = ["BEFORE", "AFTER"])]
+    diff: Option<Vec<PathBuf>>,
+
+    /// Configuration file (default: /usr/local/etc/sanview.toml if present)
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Size and rate units: si (MB = 10^6) or iec (MiB = 2^20); default from the config file, else si
+    #[arg(long, value_enum)]
+    units: Option<UnitsArg>,
+
+    /// Header time of the last update: relative ("1.2s ago"), absolute (HH:MM:SS) or both;
+    /// default from the config file, else relative
+    #[arg(long, value_enum)]
+    clock: Option<ClockArg>,
+
+    /// Fade the display to gray once no drive has been more than 1% busy for this long
+    /// (e.g. 5m); any activity restores the colors
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    idle_dim: Option<Duration>,
+
+    /// Never capture the mouse, leaving clicks and drags to the terminal for text selection
+    #[arg(long)]
+    no_mouse: bool,
+
+    /// Color theme; auto honors NO_COLOR and TERM=dumb, then picks light or dark from COLORFGBG
+    #[arg(long, value_enum, default_value_t = ThemeArg::Auto)]
+    theme: ThemeArg,
 }
 
-fn main() -> Result<()> {
-    env_logger::init();
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ThemeArg {
+    Auto,
+    Dark,
+    Light,
+    Mono,
+}
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum UnitsArg {
+    Si,
+    Iec,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ClockArg {
+    Relative,
+    Absolute,
+    Both,
+}
+
+fn main() -> Result<()> {
     let args = Args::parse();
+    init_logger(args.log_json);
+
+    #[cfg(target_os = "freebsd")]
+    if args.check {
+        let ok = sanview::check::run()?;
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if let Some(ref dir) = args.replay_spool {
+        let samples = sanview::spool::read_spool(dir)?;
+        sanview::spool::write_replay(&mut std::io::stdout().lock(), &samples)?;
+        return Ok(());
+    }
+
+    if let Some([ref before, ref after]) = args.diff.as_deref() {
+        return sanview::diff::run(before, after);
+    }
 
-    // Initialize collectors
-    let mut geom_collector = GeomCollector::new()
-        .context("Failed to initialize GEOM collector")?;
-    let mut multipath_collector = MultipathCollector::new();
-    let ses_collector = SesCollector::new();
-    let mut zfs_collector = ZfsCollector::new();
-    let topology_correlator = TopologyCorrelator::new();
-
-    // Initialize system stats collectors
-    let mut cpu_collector = CpuCollector::new();
-    let memory_collector = MemoryCollector::new();
-    let mut network_collector = NetworkCollector::new();
-    let bhyve_collector = BhyveCollector::new();
-    let jail_collector = JailCollector::new();
-
-    // Collect SES slot mappings once (static data)
-    let ses_info = match ses_collector.collect() {
-        Ok(info) => {
-            log::info!("Found {} disk slot mappings via SES", info.len());
-            info
+    let config = Config::load(args.config.as_deref())?;
+
+    #[cfg(target_os = "freebsd")]
+    if args.list_devices {
+        return sanview::devices::run(&config, !args.no_ses, !args.no_zfs, args.anonymize);
+    }
+
+    // Remote client or simulator: the TUI shows snapshots from a --serve
+    // instance or synthetic ones, no local collectors
+    if args.connect.is_some() || args.simulate {
+        let app_state = Arc::new(Mutex::new(AppState::with_max_history(args.history)));
+        {
+            let mut state = app_state.lock().unwrap();
+            state.alerts_only = args.alerts;
+            state.expected_paths = config.multipath.expected_paths;
+            state.hide_idle = config.idle_drives.hide;
+            state.remote_source = true;
         }
-        Err(e) => {
-            log::warn!("Failed to collect SES data: {}", e);
-            log::warn!("Continuing without slot mapping...");
-            std::collections::HashMap::new()
+        match args.connect {
+            Some(ref target) => {
+                sanview::remote::spawn_client(target.clone(), Arc::clone(&app_state));
+            }
+            None => {
+                let interval = Duration::from_millis(args.refresh_storage.unwrap_or(args.refresh));
+                sanview::simulate::spawn(interval, Arc::clone(&app_state));
+            }
         }
+        return run_tui(app_state, ui_options(&args, config), Arc::new(AtomicBool::new(false)));
+    }
+
+    run_local(args, config)
+}
+
+/// Collect from this host's GEOM, gmultipath, SES, ZFS and system
+/// counters, for the TUI or the headless outputs
+#[cfg(target_os = "freebsd")]
+fn run_local(args: Args, config: Config) -> Result<()> {
+    let interface_filter = InterfaceFilter {
+        include: args.net_include.iter().filter(|p| !p.is_empty()).cloned().collect(),
+        exclude: match args.net_exclude {
+            Some(ref exclude) => exclude.iter().filter(|p| !p.is_empty()).cloned().collect(),
+            None => InterfaceFilter::default().exclude,
+        },
+    };
+    let mut monitor = Monitor::new(
+        &config,
+        MonitorOptions {
+            ses: !args.no_ses,
+            zfs: !args.no_zfs,
+            network: !args.no_network,
+            interface_filter,
+            bhyve: !args.no_bhyve,
+            jails: !args.no_jails,
+        },
+    )?;
+
+    // Optional metrics export
+    let influx_sink = match args.influx_udp {
+        Some(ref addr) => Some(
+            InfluxUdpSink::new(addr).context("Failed to initialize InfluxDB UDP output")?,
+        ),
+        None => None,
     };
 
     // Create shared application state
-    let app_state = Arc::new(Mutex::new(AppState::new()));
+    let app_state = Arc::new(Mutex::new(AppState::with_max_history(args.history)));
+    let mut anonymizer = args.anonymize.then(Anonymizer::new);
+    {
+        let mut state = app_state.lock().unwrap();
+        state.hostname = if args.anonymize {
+            Some(sanview::anonymize::HOSTNAME.to_string())
+        } else {
+            sanview::monitor::read_hostname()
+        };
+        state.alerts_only = args.alerts;
+        state.expected_paths = config.multipath.expected_paths;
+        state.hide_idle = config.idle_drives.hide;
+    }
 
-    // Run TUI in a separate thread (TUI can be Send, but GEOM FFI cannot)
-    let tui_state = Arc::clone(&app_state);
-    let tui_handle = std::thread::spawn(move || {
-        run_tui(tui_state)
-    });
+    // Headless modes (--serve, --json) have no TUI and stop on SIGINT/SIGTERM
+    let mut snapshot_server = match args.serve {
+        Some(ref path) => Some(SnapshotServer::bind(path)?),
+        None => None,
+    };
+    let mut spool = match args.spool {
+        Some(ref dir) => Some(SpoolWriter::open(dir)?),
+        None => None,
+    };
+    let headless = snapshot_server.is_some() || args.json || args.plain;
+    if headless {
+        install_stop_handler();
+    }
+    // --plain formats sizes without the TUI, which otherwise picks the units
+    if args.plain {
+        sanview::util::format::select(selected_units(&args, &config));
+    }
 
-    // Run data collection in main thread (required because GEOM FFI is not Send)
-    let mut last_update = std::time::Instant::now();
-    let mut last_slow_update = std::time::Instant::now();
+    // --json samples once per --interval so each snapshot's rates span it;
+    // otherwise storage and system stats each follow their own interval
+    let (storage_ms, system_ms) = match args.interval {
+        Some(interval) => {
+            let ms = (interval.as_millis() as u64).max(50);
+            (ms, ms)
+        }
+        None => (
+            args.refresh_storage.unwrap_or(args.refresh),
+            args.refresh_system.unwrap_or(args.refresh),
+        ),
+    };
+    let deadline = args.duration.map(|d| Instant::now() + d);
+    let mut stdout = std::io::stdout();
+    let mut json_out = match (args.json, args.output.as_ref()) {
+        (false, _) => None,
+        (true, Some(path)) => Some(JsonLinesWriter::create(path, args.compress)?),
+        (true, None) => Some(JsonLinesWriter::stdout()),
+    };
+
+    // Run TUI in a separate thread (TUI can be Send, but GEOM FFI cannot);
+    // it sets `quit` as it exits
+    let quit = Arc::new(AtomicBool::new(false));
+    let tui_handle = if !headless {
+        let tui_state = Arc::clone(&app_state);
+        let ui_options = ui_options(&args, config);
+        let tui_quit = Arc::clone(&quit);
+        Some(std::thread::spawn(move || run_tui(tui_state, ui_options, tui_quit)))
+    } else {
+        None
+    };
+
+    // Run data collection in main thread (required because GEOM FFI is not
+    // Send); Monitor::new took the rate baseline, so the first cycle one
+    // interval from now already has rates to show
+    let mut last_storage_update = Instant::now();
+    let mut last_system_update = Instant::now();
+    let mut last_slow_update = Instant::now();
+    // Last cycle that pushed fresh data into the state, for --watchdog
+    let mut last_successful_update = Instant::now();
+    let mut stalled = false;
 
     loop {
-        // Check if TUI thread has finished (user quit)
-        if tui_handle.is_finished() {
+        if quit.load(Ordering::SeqCst)
+            || STOP.load(Ordering::SeqCst)
+            || deadline.is_some_and(|d| Instant::now() >= d)
+        {
+            break;
+        }
+        if args.watchdog.is_some_and(|timeout| last_successful_update.elapsed() >= timeout) {
+            log::error!(
+                "Watchdog: no data collected for {:.0?}, exiting",
+                last_successful_update.elapsed()
+            );
+            stalled = true;
             break;
         }
 
-        // Fast refresh for storage/CPU/memory stats
-        if last_update.elapsed() >= Duration::from_millis(args.refresh) {
-            last_update = std::time::Instant::now();
+        // 'l' in the UI: switch a bay's locate LED (between cycles, so it's prompt)
+        let locate = app_state.lock().unwrap().take_locate_request();
+        if let Some(request) = locate {
+            let result = if !args.allow_control {
+                Err(anyhow::anyhow!("start sanview with --allow-control to switch LEDs"))
+            } else {
+                monitor.set_locate(&request.paths, request.on)
+            };
+            app_state.lock().unwrap().finish_locate(request, result);
+        }
 
-            // Collect raw disk statistics
-            let physical_disks = match geom_collector.collect() {
-                Ok(disks) => disks,
-                Err(e) => {
-                    log::error!("Error collecting GEOM statistics: {}", e);
-                    continue;
+        // Storage stats (--refresh-storage) and CPU/memory/network stats
+        // (--refresh-system) on their own timers; a cycle runs when either is
+        // due. VMs, jails and enclosure status refresh less often (8x the
+        // storage interval, min 2s)
+        let slow_interval = (storage_ms * 8).max(2000);
+        let due = Due {
+            storage: last_storage_update.elapsed() >= Duration::from_millis(storage_ms),
+            system: last_system_update.elapsed() >= Duration::from_millis(system_ms),
+            slow: last_slow_update.elapsed() >= Duration::from_millis(slow_interval),
+        };
+        if due.storage || due.system {
+            if due.storage {
+                last_storage_update = Instant::now();
+                // 'r' in the UI: drop the topology caches and re-probe SES this cycle
+                if app_state.lock().unwrap().take_topology_refresh() {
+                    monitor.force_refresh();
                 }
-            };
+            }
+            if due.system {
+                last_system_update = Instant::now();
+            }
+            if due.slow {
+                last_slow_update = Instant::now();
+            }
 
-            // Collect multipath topology
-            let multipath_info = match multipath_collector.collect() {
-                Ok(info) => info,
+            let mut snapshot = match monitor.poll_due(due) {
+                Ok(snapshot) => snapshot,
                 Err(e) => {
-                    log::error!("Error collecting multipath topology: {}", e);
+                    log::error!("Error collecting storage topology: {:#}", e);
                     continue;
                 }
             };
 
-            // Collect ZFS topology
-            let zfs_info = match zfs_collector.collect() {
-                Ok(info) => info,
-                Err(e) => {
-                    log::warn!("Error collecting ZFS topology: {}", e);
-                    std::collections::HashMap::new()
+            // Push metrics to InfluxDB on the slow cadence (one datagram per cycle)
+            if due.slow {
+                if let Some(ref sink) = influx_sink {
+                    if let Err(e) = sink.send(
+                        &snapshot.multipath_devices,
+                        &snapshot.standalone_disks,
+                        &snapshot.cpu_stats,
+                        &snapshot.memory_stats,
+                        &snapshot.network_stats,
+                    ) {
+                        log::warn!("Error sending InfluxDB metrics: {}", e);
+                    }
                 }
-            };
-
-            // Correlate and deduplicate
-            let (multipath_devices, standalone_disks) =
-                topology_correlator.correlate(physical_disks, multipath_info, ses_info.clone(), zfs_info);
+            }
 
-            // Collect system stats
-            let cpu_stats = cpu_collector.collect().unwrap_or_else(|e| {
-                log::error!("Error collecting CPU stats: {}", e);
-                sanview::collectors::CpuStats { cores: Vec::new() }
-            });
+            // The UI quit while this cycle was collecting; nothing would show it
+            if quit.load(Ordering::SeqCst) {
+                break;
+            }
 
-            let memory_stats = memory_collector.collect().unwrap_or_else(|e| {
-                log::error!("Error collecting memory stats: {}", e);
-                sanview::collectors::MemoryStats {
-                    total_bytes: 0,
-                    active_bytes: 0,
-                    inactive_bytes: 0,
-                    laundry_bytes: 0,
-                    wired_bytes: 0,
-                    buf_bytes: 0,
-                    free_bytes: 0,
-                    used_pct: 0.0,
-                    swap_total_bytes: 0,
-                    swap_used_bytes: 0,
-                    swap_used_pct: 0.0,
-                    arc_total_bytes: 0,
-                    arc_mfu_bytes: 0,
-                    arc_mru_bytes: 0,
-                    arc_anon_bytes: 0,
-                    arc_header_bytes: 0,
-                    arc_other_bytes: 0,
-                    arc_compressed_bytes: 0,
-                    arc_uncompressed_bytes: 0,
-                    arc_ratio: 0.0,
+            if let Some(ref mut anonymizer) = anonymizer {
+                anonymizer.snapshot(&mut snapshot);
+            }
+            if let Some(ref mut server) = snapshot_server {
+                server.broadcast(&snapshot);
+            }
+            if let Some(ref mut spool) = spool {
+                if due.storage {
+                    spool.record(&snapshot);
                 }
-            });
-
-            let network_stats = network_collector.collect().unwrap_or_else(|e| {
-                log::warn!("Error collecting network stats: {}", e);
-                Vec::new()
-            });
-
-            // Collect VMs and jails less frequently (8x the refresh interval, min 2s)
-            let slow_interval = (args.refresh * 8).max(2000);
-            let (vms, jails) = if last_slow_update.elapsed() >= Duration::from_millis(slow_interval) {
-                last_slow_update = std::time::Instant::now();
-                let v = bhyve_collector.collect().unwrap_or_else(|e| {
-                    log::warn!("Error collecting bhyve VMs: {}", e);
-                    Vec::new()
-                });
-                let j = jail_collector.collect().unwrap_or_else(|e| {
-                    log::warn!("Error collecting jails: {}", e);
-                    Vec::new()
-                });
-                (v, j)
-            } else {
-                // Use previous values
-                let state = app_state.lock().unwrap();
-                (state.vms.clone(), state.jails.clone())
-            };
+            }
+            if let Some(ref mut out) = json_out {
+                // A closed pipe (e.g. `| head`) or a full disk ends the run
+                if let Err(e) = out.write(&snapshot) {
+                    log::debug!("Stopping JSON output: {}", e);
+                    break;
+                }
+            }
+            if args.plain {
+                if let Err(e) = write_plain(&mut stdout, &snapshot) {
+                    log::debug!("Stopping plain output: {}", e);
+                    break;
+                }
+            }
 
             // Update shared state
             {
                 let mut state = app_state.lock().unwrap();
-                state.update_topology(multipath_devices, standalone_disks);
-                state.update_system_stats(cpu_stats, memory_stats, network_stats, vms, jails);
+                snapshot.apply(&mut state);
+                if due.storage {
+                    state.finish_topology_refresh();
+                }
+            }
+            // Only GEOM passes count for --watchdog; system-only cycles can't stall it
+            if due.storage {
+                last_successful_update = Instant::now();
             }
         }
 
@@ -174,8 +515,123 @@ fn main() -> Result<()> {
         std::thread::sleep(Duration::from_millis(50));
     }
 
-    // Wait for TUI thread to finish
-    tui_handle.join().expect("TUI thread panicked")?;
+    // Finish --json output; a gzip file is unreadable without its trailer
+    if let Some(out) = json_out {
+        if let Err(e) = out.finish() {
+            log::warn!("Failed to finish JSON output: {}", e);
+        }
+    }
 
+    // Wait for TUI thread to finish (the watchdog asks it to, so the
+    // terminal is restored before exiting)
+    if stalled {
+        app_state.lock().unwrap().quit();
+    }
+    if let Some(handle) = tui_handle {
+        handle.join().expect("TUI thread panicked")?;
+    }
+
+    if stalled {
+        anyhow::bail!("watchdog expired: collection has been failing");
+    }
     Ok(())
 }
+
+#[cfg(not(target_os = "freebsd"))]
+fn run_local(_args: Args, _config: Config) -> Result<()> {
+    anyhow::bail!("Local collection needs FreeBSD; use --simulate for synthetic data or --connect to a --serve host")
+}
+
+/// UI settings derived from the command line and config file
+fn ui_options(args: &Args, config: Config) -> UiOptions {
+    UiOptions {
+        chart_axis_labels: !args.no_chart_labels,
+        refresh_ms: args.refresh_storage.unwrap_or(args.refresh),
+        system_refresh_ms: args.refresh_system.unwrap_or(args.refresh),
+        average_ms: args.average,
+        theme: match args.theme {
+            ThemeArg::Auto => Theme::detect(),
+            ThemeArg::Dark => Theme::dark(),
+            ThemeArg::Light => Theme::light(),
+            ThemeArg::Mono => Theme::monochrome(),
+        }
+        .with_chart_colors(config.colors),
+        panels: OverviewPanels {
+            network: !args.no_network,
+            vms: !args.no_bhyve,
+            jails: !args.no_jails,
+        },
+        title: args.title.clone().unwrap_or_else(|| UiOptions::default().title),
+        ascii: args.ascii,
+        units: selected_units(args, &config),
+        clock: match args.clock {
+            Some(ClockArg::Relative) => ClockStyle::Relative,
+            Some(ClockArg::Absolute) => ClockStyle::Absolute,
+            Some(ClockArg::Both) => ClockStyle::Both,
+            None => config.clock.unwrap_or_default(),
+        },
+        idle_dim: args.idle_dim,
+        mouse: !args.no_mouse,
+        config,
+    }
+}
+
+/// --units, else the config file's `units`, else SI
+fn selected_units(args: &Args, config: &Config) -> Units {
+    match args.units {
+        Some(UnitsArg::Si) => Units::Si,
+        Some(UnitsArg::Iec) => Units::Iec,
+        None => config.units.unwrap_or_default(),
+    }
+}
+
+/// Route SIGINT and SIGTERM to the STOP flag instead of killing the process,
+/// so headless runs finish their output and remove the --serve socket
+#[cfg(target_os = "freebsd")]
+fn install_stop_handler() {
+    extern "C" fn request_stop(_signal: libc::c_int) {
+        STOP.store(true, Ordering::SeqCst);
+    }
+
+    let handler = request_stop as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe
+    unsafe {
+        libc::signal(libc::SIGINT, handler);
+        libc::signal(libc::SIGTERM, handler);
+    }
+}
+
+/// Parse "250ms", "30s", "5m", "1h" or a bare number of seconds
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", s))?;
+    let secs = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return Err(format!("unknown unit '{}' in '{}' (use ms, s, m or h)", unit, s)),
+    };
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("invalid duration '{}': {}", s, e))
+}
+
+/// env_logger with either its default text format or one JSON object per line
+fn init_logger(json: bool) {
+    let mut builder = env_logger::Builder::from_default_env();
+    if json {
+        builder.format(|buf, record| {
+            let line = serde_json::json!({
+                "timestamp": buf.timestamp_millis().to_string(),
+                "level": record.level().as_str(),
+                "target": record.target(),
+                "message": record.args().to_string(),
+            });
+            writeln!(buf, "{}", line)
+        });
+    }
+    builder.init();
+}
@@ -0,0 +1,461 @@
+//! Collection loop behind the TUI and headless modes, and embedding API
+//!
+//! `Monitor` owns every collector: GEOM, gmultipath, SES and ZFS correlated
+//! into the drive topology, plus CPU, memory, network, VMs, jails and
+//! enclosure status. `poll()` collects all of it and returns the result as
+//! the same [`Snapshot`] that `--json` and `--serve` emit, for daemons
+//! that want the data without the TUI; the sanview binary drives
+//! `poll_due()` on its own storage, system and slow cadences.
+
+use crate::collectors::{
+    BhyveCollector, CpuCollector, CpuStats, GeomCollector, InterfaceFilter, InterruptCollector, JailCollector,
+    JailInfo, MemoryCollector, MemoryStats, MultipathCollector, NetworkCollector, NetworkStats, SesAccessDenied,
+    SesCollector, SesSlotInfo, VmInfo, ZfsCollector, ZilCollector, ZilStats,
+};
+use crate::config::Config;
+use crate::domain::device::{MultipathDevice, PhysicalDisk};
+use crate::domain::TopologyCorrelator;
+use crate::remote::Snapshot;
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Which collectors a [`Monitor`] runs (all of them by default)
+#[derive(Clone, Debug)]
+pub struct MonitorOptions {
+    /// Map drives to enclosure slots and read enclosure status via SES
+    pub ses: bool,
+    /// Read pool membership from `zpool status` and ZIL activity
+    pub zfs: bool,
+    /// Collect network interface rates, filtered by `interface_filter`
+    pub network: bool,
+    pub interface_filter: InterfaceFilter,
+    /// List bhyve VMs
+    pub bhyve: bool,
+    /// List jails
+    pub jails: bool,
+}
+
+impl Default for MonitorOptions {
+    fn default() -> Self {
+        Self {
+            ses: true,
+            zfs: true,
+            network: true,
+            interface_filter: InterfaceFilter::default(),
+            bhyve: true,
+            jails: true,
+        }
+    }
+}
+
+/// Which parts of a [`Monitor::poll_due`] cycle to collect
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Due {
+    /// GEOM statistics and the correlated topology, ZIL activity
+    pub storage: bool,
+    /// CPU, interrupts, memory and network
+    pub system: bool,
+    /// VMs, jails, enclosure status, pool and dataset usage, and whether
+    /// an enclosure appeared or went away
+    pub slow: bool,
+}
+
+impl Due {
+    /// Everything, as `poll()` collects
+    pub const ALL: Due = Due { storage: true, system: true, slow: true };
+}
+
+/// Owns every collector and produces correlated snapshots
+///
+/// Rates (IOPS, bandwidth, CPU and network) cover the time since that
+/// part was last collected, or since `new()` for the first time, so call
+/// it at a steady interval of your choosing. `poll()` runs all enabled
+/// collectors, including the VM, jail and enclosure scans that the TUI
+/// only runs every few seconds; `poll_due()` runs a subset and repeats
+/// the latest values of the rest.
+///
+/// A `Monitor` is neither `Send` nor `Sync`: the GEOM statistics collector
+/// holds libgeom handles that must stay on the thread that created them.
+/// Create and poll it on one thread and send the snapshots (which are
+/// plain data and `Send`) to the rest of the program.
+pub struct Monitor {
+    geom: GeomCollector,
+    multipath: MultipathCollector,
+    zfs: Option<ZfsCollector>,
+    zil: Option<ZilCollector>,
+    ses: Option<SesCollector>,
+    cpu: CpuCollector,
+    interrupts: InterruptCollector,
+    memory: MemoryCollector,
+    network: Option<NetworkCollector>,
+    bhyve: Option<BhyveCollector>,
+    jails: Option<JailCollector>,
+    correlator: TopologyCorrelator,
+    ses_info: HashMap<String, SesSlotInfo>,
+    known_disks: Option<BTreeSet<String>>,
+    known_enclosures: Vec<String>,
+    // Shown in the header while SES can't be read for lack of privileges
+    notice: Option<String>,
+    hostname: Option<String>,
+    // Latest results of each half, repeated in snapshots until it's next due
+    storage: Option<(Vec<MultipathDevice>, Vec<PhysicalDisk>)>,
+    zil_stats: Option<ZilStats>,
+    system: Option<(CpuStats, MemoryStats, Vec<NetworkStats>)>,
+    // VM and jail lists from the startup scan, for the first cycle
+    startup_lists: Option<(Vec<VmInfo>, Vec<JailInfo>)>,
+}
+
+impl Monitor {
+    /// Initialize the collectors and take the baseline for the first rates
+    ///
+    /// The SES scan, the bhyve process scan and the collectors that run
+    /// external commands (gmultipath, zpool, ifconfig, jls) run side by
+    /// side, so the first cycle finds the topology caches filled and lists
+    /// VMs and jails without waiting for a slow cycle.
+    ///
+    /// `config` supplies the enclosure slot calibration. Fails only when
+    /// GEOM statistics are unavailable; an optional collector that can't
+    /// start is logged and reports nothing.
+    pub fn new(config: &Config, options: MonitorOptions) -> Result<Self> {
+        let mut geom = GeomCollector::new().context("Failed to initialize GEOM collector")?;
+        let mut multipath = MultipathCollector::new();
+        let mut zfs = options.zfs.then(ZfsCollector::new);
+        let ses = options.ses.then(|| SesCollector::with_calibration(config.enclosures.clone()));
+        let mut network = options.network.then(|| NetworkCollector::with_filter(options.interface_filter));
+        let bhyve = options.bhyve.then(BhyveCollector::new);
+        let jails = options.jails.then(JailCollector::new);
+
+        // GEOM stays on this thread (its FFI handles aren't Send)
+        let (ses_result, vms, jail_list) = std::thread::scope(|scope| {
+            let ses = ses.as_ref().map(|c| scope.spawn(move || c.collect()));
+            let multipath = scope.spawn(|| multipath.collect());
+            let zfs = zfs.as_mut().map(|c| scope.spawn(move || c.collect()));
+            let network = network.as_mut().map(|c| scope.spawn(move || c.collect()));
+            let vms = bhyve.as_ref().map(|c| scope.spawn(move || c.collect()));
+            let jails = jails.as_ref().map(|c| scope.spawn(move || c.collect()));
+
+            // Only the caches matter here: the first cycle reports any
+            // topology or network failure from its own attempt
+            let _ = multipath.join();
+            let _ = zfs.map(|h| h.join());
+            let _ = network.map(|h| h.join());
+
+            let ses = ses.map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)));
+            (ses, join_list(vms, "bhyve VMs"), join_list(jails, "jails"))
+        });
+
+        let mut notice = None;
+        let ses_info = match ses_result {
+            None => HashMap::new(),
+            Some(Ok(info)) => {
+                log::info!("Found {} disk slot mappings via SES", info.len());
+                info
+            }
+            Some(Err(e)) => {
+                log::warn!("Failed to collect SES data: {}", e);
+                log::warn!("Continuing without slot mapping...");
+                notice = ses_access_notice(&e);
+                HashMap::new()
+            }
+        };
+        let known_enclosures = ses
+            .as_ref()
+            .map(|c| c.enclosure_devices().unwrap_or_default())
+            .unwrap_or_default();
+
+        // The delta-based collectors report nothing until their second call
+        geom.collect().context("Failed to collect GEOM statistics")?;
+        let mut cpu = CpuCollector::new();
+        let mut interrupts = InterruptCollector::new();
+        let _ = cpu.collect();
+        let _ = interrupts.collect();
+
+        Ok(Self {
+            geom,
+            multipath,
+            zfs,
+            zil: options.zfs.then(ZilCollector::new),
+            ses,
+            cpu,
+            interrupts,
+            memory: MemoryCollector::new(),
+            network,
+            bhyve,
+            jails,
+            correlator: TopologyCorrelator::new(),
+            ses_info,
+            known_disks: None,
+            known_enclosures,
+            notice,
+            hostname: read_hostname(),
+            storage: None,
+            zil_stats: None,
+            system: None,
+            startup_lists: Some((vms, jail_list)),
+        })
+    }
+
+    /// Collect and correlate everything once
+    ///
+    /// Fails when GEOM or gmultipath can't be read, since there is no
+    /// topology without them; the other collectors fall back to empty
+    /// values with a logged warning, as in the TUI.
+    pub fn poll(&mut self) -> Result<Snapshot> {
+        self.poll_due(Due::ALL)
+    }
+
+    /// Collect the parts that are `due` and repeat the latest values of
+    /// the others, marked `storage_repeated`/`system_repeated` so the UI
+    /// doesn't chart them twice
+    ///
+    /// A half that has never been collected is empty and marked repeated.
+    /// VMs, jails, enclosure status and pool usage are only present on
+    /// slow cycles (and the VM and jail lists from the startup scan on
+    /// the first cycle). Fails like `poll()` when storage is due.
+    pub fn poll_due(&mut self, due: Due) -> Result<Snapshot> {
+        if due.storage {
+            self.collect_storage(due.slow)?;
+        }
+        if due.system {
+            self.collect_system();
+        }
+
+        // Before the first pass of a half its values are empty
+        let (multipath_devices, standalone_disks) = self.storage.clone().unwrap_or_default();
+        let (cpu_stats, memory_stats, network_stats) = self.system.clone().unwrap_or_else(|| {
+            (CpuStats { cores: Vec::new(), interrupts: None }, MemoryStats::default(), Vec::new())
+        });
+
+        let (vms, jails) = if due.slow {
+            self.startup_lists = None;
+            (Some(self.collect_vms()), Some(self.collect_jails()))
+        } else {
+            self.startup_lists
+                .take()
+                .map_or((None, None), |(vms, jails)| (Some(vms), Some(jails)))
+        };
+
+        // Enclosure PSU/fan/temperature status, pool capacity for the
+        // fill-rate trend and dataset space follow the VMs
+        let enclosure_health = self.ses.as_ref().filter(|_| due.slow).map(|c| {
+            c.collect_health().unwrap_or_else(|e| {
+                log::warn!("Error collecting enclosure health: {}", e);
+                Vec::new()
+            })
+        });
+        let pool_usage = self.zfs.as_ref().filter(|_| due.slow).map(|c| {
+            c.collect_usage().unwrap_or_else(|e| {
+                log::warn!("Error collecting pool usage: {}", e);
+                Vec::new()
+            })
+        });
+        let datasets = self.zfs.as_ref().filter(|_| due.slow).map(|c| {
+            c.collect_datasets().unwrap_or_else(|e| {
+                log::warn!("Error collecting dataset usage: {}", e);
+                Vec::new()
+            })
+        });
+
+        Ok(Snapshot {
+            multipath_devices,
+            standalone_disks,
+            cpu_stats,
+            memory_stats,
+            network_stats,
+            vms,
+            jails,
+            zil_stats: self.zil_stats.clone(),
+            enclosure_health,
+            pool_usage,
+            datasets,
+            notice: self.notice.clone(),
+            storage_repeated: !due.storage || self.storage.is_none(),
+            system_repeated: !due.system || self.system.is_none(),
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            hostname: self.hostname.clone(),
+        })
+    }
+
+    /// Re-scan SES slots now and re-read GEOM, gmultipath and ZFS topology
+    /// on the next poll instead of using their caches ('r' in the TUI)
+    pub fn force_refresh(&mut self) {
+        self.geom.force_refresh();
+        self.multipath.force_refresh();
+        if let Some(ref mut zfs) = self.zfs {
+            zfs.force_refresh();
+        }
+        if let Some(ref ses) = self.ses {
+            match ses.collect() {
+                Ok(info) => {
+                    self.ses_info = info;
+                    self.notice = None;
+                }
+                Err(e) => {
+                    log::warn!("Failed to refresh SES data: {}", e);
+                    self.notice = ses_access_notice(&e);
+                }
+            }
+            // Just re-scanned; a disk set change on the next poll needn't scan again
+            self.known_disks = None;
+        }
+    }
+
+    /// Switch the SES locate LED of the bay holding any of `paths`; returns
+    /// the enclosure and element that was changed
+    pub fn set_locate(&self, paths: &[String], on: bool) -> Result<String> {
+        match self.ses {
+            Some(ref ses) => ses.set_locate(paths, on),
+            None => anyhow::bail!("SES is disabled by --no-ses"),
+        }
+    }
+
+    /// GEOM statistics correlated with gmultipath, SES and ZFS, plus ZIL activity
+    fn collect_storage(&mut self, slow: bool) -> Result<()> {
+        let physical_disks = self.geom.collect().context("Failed to collect GEOM statistics")?;
+
+        // Re-map slots when the disk set changes (hot insert into an empty
+        // bay) or, on the slow cadence, when an enclosure appears or goes;
+        // the SES ioctls only run when one of those changed
+        if let Some(ref ses) = self.ses {
+            let disks: BTreeSet<String> = physical_disks.iter().map(|d| d.device_name.clone()).collect();
+            let disks_changed = self.known_disks.as_ref().is_some_and(|known| *known != disks);
+            self.known_disks = Some(disks);
+
+            let mut enclosures_changed = false;
+            if slow {
+                match ses.enclosure_devices() {
+                    Ok(devices) => {
+                        enclosures_changed = devices != self.known_enclosures;
+                        self.known_enclosures = devices;
+                    }
+                    Err(e) => log::debug!("Failed to list SES devices: {}", e),
+                }
+            }
+
+            if disks_changed || enclosures_changed {
+                match ses.collect() {
+                    Ok(info) => {
+                        log::info!("Re-scanned SES: {} disk slot mappings", info.len());
+                        self.ses_info.extend(info);
+                    }
+                    Err(e) => log::warn!("Failed to refresh SES data: {}", e),
+                }
+            }
+        }
+
+        let multipath_info = self
+            .multipath
+            .collect()
+            .context("Failed to collect multipath topology")?;
+        let zfs_info = match self.zfs.as_mut().map(|c| c.collect()) {
+            Some(Ok(info)) => info,
+            Some(Err(e)) => {
+                log::warn!("Error collecting ZFS topology: {}", e);
+                HashMap::new()
+            }
+            None => HashMap::new(),
+        };
+        self.storage = Some(self.correlator.correlate(
+            physical_disks,
+            multipath_info,
+            self.ses_info.clone(),
+            zfs_info,
+        ));
+
+        // ZIL activity for the SLOG line; absent until zfs.ko is loaded
+        self.zil_stats = self.zil.as_mut().and_then(|c| match c.collect() {
+            Ok(stats) => Some(stats),
+            Err(e) => {
+                log::debug!("Error collecting ZIL stats: {}", e);
+                None
+            }
+        });
+        Ok(())
+    }
+
+    /// CPU (with interrupt rates), memory and network
+    fn collect_system(&mut self) {
+        let mut cpu_stats = self.cpu.collect().unwrap_or_else(|e| {
+            log::error!("Error collecting CPU stats: {}", e);
+            CpuStats { cores: Vec::new(), interrupts: None }
+        });
+        cpu_stats.interrupts = match self.interrupts.collect() {
+            Ok(stats) => Some(stats),
+            Err(e) => {
+                log::debug!("Error collecting interrupt counters: {}", e);
+                None
+            }
+        };
+        let memory_stats = self.memory.collect().unwrap_or_else(|e| {
+            log::error!("Error collecting memory stats: {}", e);
+            MemoryStats::default()
+        });
+        let network_stats = match self.network.as_mut() {
+            Some(c) => c.collect().unwrap_or_else(|e| {
+                log::warn!("Error collecting network stats: {}", e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        self.system = Some((cpu_stats, memory_stats, network_stats));
+    }
+
+    fn collect_vms(&self) -> Vec<VmInfo> {
+        match self.bhyve {
+            Some(ref c) => c.collect().unwrap_or_else(|e| {
+                log::warn!("Error collecting bhyve VMs: {}", e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        }
+    }
+
+    fn collect_jails(&self) -> Vec<JailInfo> {
+        match self.jails {
+            Some(ref c) => c.collect().unwrap_or_else(|e| {
+                log::warn!("Error collecting jails: {}", e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Result of a startup VM or jail listing (empty if disabled or failed)
+fn join_list<T>(handle: Option<std::thread::ScopedJoinHandle<'_, Result<Vec<T>>>>, what: &str) -> Vec<T> {
+    let Some(handle) = handle else {
+        return Vec::new();
+    };
+    handle
+        .join()
+        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+        .unwrap_or_else(|e| {
+            log::warn!("Error collecting {}: {}", what, e);
+            Vec::new()
+        })
+}
+
+/// Header notice for an SES failure that is a permissions problem rather
+/// than a missing or broken enclosure
+fn ses_access_notice(error: &anyhow::Error) -> Option<String> {
+    error.downcast_ref::<SesAccessDenied>().map(|_| {
+        "Running without SES access - slot mapping unavailable (run as root)".to_string()
+    })
+}
+
+/// kern.hostname, for labelling snapshots
+pub fn read_hostname() -> Option<String> {
+    use sysctl::Sysctl;
+    match sysctl::Ctl::new("kern.hostname").and_then(|ctl| ctl.value_string()) {
+        Ok(name) if !name.is_empty() => Some(name),
+        Ok(_) => None,
+        Err(e) => {
+            log::debug!("Failed to read kern.hostname: {}", e);
+            None
+        }
+    }
+}
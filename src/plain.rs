@@ -0,0 +1,121 @@
+//! Text-only watch mode
+//!
+//! `--plain` redraws a compact device and system table in the normal
+//! terminal with ANSI cursor-home and clear-to-end, instead of the ratatui
+//! alternate screen: no raw mode, no mouse capture, and the output can be
+//! recorded with script(1) or viewed where the full TUI misbehaves.
+
+use crate::remote::Snapshot;
+use crate::util::format::{format_bytes, format_iops, format_mbps, format_rate, units};
+use std::io::{self, Write};
+
+/// Move to the top-left corner without clearing (avoids flicker)
+const CURSOR_HOME: &str = "\x1b[H";
+/// Clear from the cursor to the end of the line
+const CLEAR_LINE: &str = "\x1b[K";
+/// Clear from the cursor to the end of the screen
+const CLEAR_BELOW: &str = "\x1b[J";
+
+/// Redraw the whole table for one snapshot
+pub fn write_plain(out: &mut impl Write, snapshot: &Snapshot) -> io::Result<()> {
+    let mut lines = Vec::new();
+
+    let cores = &snapshot.cpu_stats.cores;
+    let cpu_pct = if cores.is_empty() {
+        0.0
+    } else {
+        cores.iter().map(|c| c.total_pct).sum::<f64>() / cores.len() as f64
+    };
+    let memory = &snapshot.memory_stats;
+    lines.push(format!(
+        "sanview - {}  {} multipath, {} standalone",
+        snapshot.hostname.as_deref().unwrap_or("localhost"),
+        snapshot.multipath_devices.len(),
+        snapshot.standalone_disks.len()
+    ));
+    lines.push(format!(
+        "CPU {:>3.0}% ({} cores)  MEM {:>3.0}% of {}  ARC {}  SWAP {}/{}",
+        cpu_pct,
+        cores.len(),
+        memory.used_pct,
+        format_bytes(memory.total_bytes as f64),
+        format_bytes(memory.arc_total_bytes as f64),
+        format_bytes(memory.swap_used_bytes as f64),
+        format_bytes(memory.swap_total_bytes as f64),
+    ));
+    lines.push(String::new());
+
+    lines.push(format!(
+        "{:<24} {:>4} {:<10} {:>5} {:>5} {:>5} {:>5} {:>6} {:>6} {:>4} {:>4}",
+        "DEVICE",
+        "SLOT",
+        "POOL",
+        "R/s",
+        "W/s",
+        format!("R_{}", units().mb_label().trim_end_matches("/s")),
+        format!("W_{}", units().mb_label().trim_end_matches("/s")),
+        "R_MS",
+        "W_MS",
+        "QD",
+        "BUSY"
+    ));
+
+    // Multipath devices first, then standalone disks, each in slot order
+    let mut drives: Vec<_> = snapshot
+        .multipath_devices
+        .iter()
+        .map(|d| (&d.name, d.slot, d.zfs_info.as_ref(), &d.statistics))
+        .collect();
+    let mut standalone: Vec<_> = snapshot
+        .standalone_disks
+        .iter()
+        .map(|d| (&d.device_name, d.slot, d.zfs_info.as_ref(), &d.statistics))
+        .collect();
+    for list in [&mut drives, &mut standalone] {
+        list.sort_by(|a, b| {
+            a.1.is_none()
+                .cmp(&b.1.is_none())
+                .then(a.1.cmp(&b.1))
+                .then_with(|| a.0.cmp(b.0))
+        });
+    }
+    for (name, slot, zfs, stats) in drives.into_iter().chain(standalone) {
+        lines.push(format!(
+            "{:<24} {:>4} {:<10} {} {} {} {} {:>6.2} {:>6.2} {:>4.1} {:>3.0}%",
+            name,
+            slot.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()),
+            zfs.map(|z| z.pool.as_str()).unwrap_or("-"),
+            format_iops(stats.read_iops),
+            format_iops(stats.write_iops),
+            format_mbps(stats.read_bw_mbps),
+            format_mbps(stats.write_bw_mbps),
+            stats.read_latency_ms,
+            stats.write_latency_ms,
+            stats.queue_depth,
+            stats.busy_pct.min(100.0),
+        ));
+    }
+
+    if !snapshot.network_stats.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("{:<24} {:>10} {:>10}", "INTERFACE", "RX", "TX"));
+        for iface in snapshot.network_stats.iter().filter(|i| !i.is_member) {
+            lines.push(format!(
+                "{:<24} {:>10} {:>10}",
+                iface.name,
+                format_rate(iface.rx_bytes_per_sec),
+                format_rate(iface.tx_bytes_per_sec)
+            ));
+        }
+    }
+
+    let mut frame = String::from(CURSOR_HOME);
+    for line in lines {
+        frame.push_str(&line);
+        frame.push_str(CLEAR_LINE);
+        frame.push('\n');
+    }
+    frame.push_str(CLEAR_BELOW);
+    out.write_all(frame.as_bytes())?;
+    out.flush()
+}
@@ -0,0 +1,239 @@
+//! Remote snapshot streaming
+//!
+//! `--serve` runs the collectors headless and writes one JSON snapshot per
+//! refresh cycle, newline-delimited, to every client connected to a Unix
+//! socket. `--connect` feeds those snapshots into the TUI in place of the
+//! local collectors, so a storage node can be watched from a workstation
+//! without running the TUI over SSH.
+
+use crate::collectors::{
+    CpuStats, DatasetUsage, EnclosureHealth, JailInfo, MemoryStats, NetworkStats, PoolUsage, VmInfo, ZilStats,
+};
+use crate::domain::device::{MultipathDevice, PhysicalDisk};
+use crate::ui::AppState;
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// A stalled client is dropped rather than holding up the collector loop
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+/// Delay between reconnect attempts in client mode
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Everything the UI needs from one collection cycle
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub multipath_devices: Vec<MultipathDevice>,
+    pub standalone_disks: Vec<PhysicalDisk>,
+    pub cpu_stats: CpuStats,
+    pub memory_stats: MemoryStats,
+    pub network_stats: Vec<NetworkStats>,
+    // Only set on cycles that re-collected them; None keeps the previous list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vms: Option<Vec<VmInfo>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jails: Option<Vec<JailInfo>>,
+    #[serde(default)]
+    pub zil_stats: Option<ZilStats>,
+    // Like vms/jails, only present on cycles that re-read SES status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enclosure_health: Option<Vec<EnclosureHealth>>,
+    // Pool sizes and allocation, also from the slow cadence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_usage: Option<Vec<PoolUsage>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datasets: Option<Vec<DatasetUsage>>,
+    // A condition the operator should fix, shown in the header (e.g. no SES access)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notice: Option<String>,
+    // Set when that half repeats an earlier cycle's values because only the
+    // other half was due (--refresh-storage and --refresh-system differ);
+    // the UI shows them but doesn't add them to its histories again
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub storage_repeated: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub system_repeated: bool,
+    #[serde(default)]
+    pub timestamp_ms: u64,  // Unix time the snapshot was collected
+
+    #[serde(default)]
+    pub hostname: Option<String>,
+}
+
+impl Snapshot {
+    /// Push this snapshot into the shared UI state
+    pub fn apply(self, state: &mut AppState) {
+        if self.hostname.is_some() {
+            state.hostname = self.hostname;
+        }
+        state.zil_stats = self.zil_stats;
+        state.notice = self.notice;
+        if let Some(health) = self.enclosure_health {
+            state.enclosure_health = health;
+        }
+        if let Some(usage) = self.pool_usage {
+            state.update_pool_usage(usage);
+        }
+        if let Some(datasets) = self.datasets {
+            state.datasets = datasets;
+        }
+        if !self.storage_repeated {
+            state.update_topology(self.multipath_devices, self.standalone_disks);
+        }
+        if !self.system_repeated {
+            state.update_system_stats(
+                self.cpu_stats,
+                self.memory_stats,
+                self.network_stats,
+                self.vms,
+                self.jails,
+            );
+        } else {
+            // The VM and jail lists follow the slow cadence, not the system half
+            if let Some(vms) = self.vms {
+                state.vms = vms;
+            }
+            if let Some(jails) = self.jails {
+                state.jails = jails;
+            }
+        }
+    }
+}
+
+/// Unix socket broadcaster used by `--serve`
+pub struct SnapshotServer {
+    listener: UnixListener,
+    path: PathBuf,
+    clients: Vec<UnixStream>,
+}
+
+impl SnapshotServer {
+    /// Listen on `path`, replacing a stale socket left by a previous run
+    pub fn bind(path: &Path) -> Result<Self> {
+        if path.exists() {
+            match UnixStream::connect(path) {
+                Ok(_) => anyhow::bail!("{} is in use by another sanview", path.display()),
+                Err(_) => std::fs::remove_file(path)
+                    .with_context(|| format!("Failed to remove stale socket {}", path.display()))?,
+            }
+        }
+
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("Failed to listen on {}", path.display()))?;
+        listener
+            .set_nonblocking(true)
+            .context("Failed to make socket non-blocking")?;
+        info!("Serving snapshots on {}", path.display());
+
+        Ok(Self {
+            listener,
+            path: path.to_path_buf(),
+            clients: Vec::new(),
+        })
+    }
+
+    /// Accept pending clients and send them this cycle's snapshot
+    pub fn broadcast(&mut self, snapshot: &Snapshot) {
+        self.accept_pending();
+        if self.clients.is_empty() {
+            return;
+        }
+
+        let mut line = match serde_json::to_vec(snapshot) {
+            Ok(line) => line,
+            Err(e) => {
+                warn!("Failed to encode snapshot: {}", e);
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        self.clients.retain_mut(|client| match client.write_all(&line) {
+            Ok(()) => true,
+            Err(e) => {
+                debug!("Dropping snapshot client: {}", e);
+                false
+            }
+        });
+    }
+
+    fn accept_pending(&mut self) {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    // Accepted sockets may inherit non-blocking mode; writes use a timeout instead
+                    if let Err(e) = stream
+                        .set_nonblocking(false)
+                        .and_then(|_| stream.set_write_timeout(Some(CLIENT_WRITE_TIMEOUT)))
+                    {
+                        warn!("Failed to configure snapshot client: {}", e);
+                        continue;
+                    }
+                    debug!("Snapshot client connected");
+                    self.clients.push(stream);
+                }
+                Err(ref e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    warn!("Failed to accept snapshot client: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SnapshotServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Read snapshots from `target` into `state` on a background thread
+///
+/// `target` is a Unix socket path, or host:port for a socket forwarded over
+/// TCP (e.g. with socat or `ssh -L`). Lost connections are retried.
+pub fn spawn_client(target: String, state: Arc<Mutex<AppState>>) -> JoinHandle<()> {
+    std::thread::spawn(move || loop {
+        match connect(&target) {
+            Ok(stream) => {
+                info!("Connected to {}", target);
+                if let Err(e) = read_snapshots(stream, &state) {
+                    warn!("Connection to {} lost: {}", target, e);
+                }
+            }
+            Err(e) => warn!("{:#}", e),
+        }
+        std::thread::sleep(RECONNECT_DELAY);
+    })
+}
+
+fn connect(target: &str) -> Result<Box<dyn Read + Send>> {
+    if target.contains('/') || Path::new(target).exists() {
+        let stream = UnixStream::connect(target)
+            .with_context(|| format!("Failed to connect to {}", target))?;
+        Ok(Box::new(stream))
+    } else {
+        let stream = TcpStream::connect(target)
+            .with_context(|| format!("Failed to connect to {}", target))?;
+        Ok(Box::new(stream))
+    }
+}
+
+fn read_snapshots(stream: Box<dyn Read + Send>, state: &Mutex<AppState>) -> Result<()> {
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let snapshot: Snapshot = serde_json::from_str(&line).context("Malformed snapshot")?;
+        snapshot.apply(&mut state.lock().unwrap());
+    }
+    anyhow::bail!("server closed the connection")
+}
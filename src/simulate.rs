@@ -0,0 +1,540 @@
+//! Synthetic data for `--simulate`
+//!
+//! Stands in for the collectors where there is no FreeBSD storage host to
+//! watch, so the UI can be worked on anywhere: a 24-bay dual-controller
+//! shelf of multipath drives in two pools with a hot spare, an NVMe log
+//! device, and CPU, memory, network, VM, jail and enclosure figures. The
+//! workload follows a slow sine wave and random drives run hot for a
+//! while, so the charts, LEDs and alerts all have something to show.
+//! Nothing is read from the machine running it.
+
+use crate::collectors::{
+    CoreStats, CpuStats, DatasetUsage, ElementKind, ElementStatus, EnclosureElement, EnclosureHealth,
+    InterruptSource, InterruptStats, JailInfo, MemoryStats, NetworkStats, PoolUsage, VmInfo, ZfsDriveInfo,
+    ZfsRole, ZilStats,
+};
+use crate::domain::device::{DiskStatistics, MultipathDevice, MultipathState, PathState, PathStats, PhysicalDisk};
+use crate::remote::Snapshot;
+use crate::ui::AppState;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Host name shown in the header
+pub const HOSTNAME: &str = "simulated";
+
+const ENCLOSURE: &str = "ses0";
+
+/// Populated bays; the last of the 24 is left empty
+const DRIVES: usize = 23;
+
+const CORES: usize = 16;
+
+const GIB: u64 = 1 << 30;
+const TIB: u64 = 1 << 40;
+
+/// Seconds for the workload to swing from quiet to busy and back
+const WAVE_SECS: f64 = 90.0;
+
+/// IOPS at which a hard drive reads 100% busy
+const DRIVE_CAPACITY_IOPS: f64 = 900.0;
+
+/// Chance per snapshot that another drive starts running hot, and for how
+/// many snapshots it stays that way
+const HOT_CHANCE: f64 = 0.03;
+const HOT_SNAPSHOTS: std::ops::Range<u64> = 20..80;
+
+/// Snapshots between refreshes of the lists the real loop re-reads on its
+/// slow cadence (VMs, jails, enclosure status, pool and dataset usage)
+const SLOW_EVERY: u64 = 20;
+
+/// Feed simulated snapshots into `state` on a background thread, one per
+/// `interval`, the way `remote::spawn_client` feeds received ones
+pub fn spawn(interval: Duration, state: Arc<Mutex<AppState>>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut simulator = Simulator::new();
+        loop {
+            simulator.snapshot().apply(&mut state.lock().unwrap());
+            std::thread::sleep(interval);
+        }
+    })
+}
+
+/// Where a simulated drive sits in the pools
+struct Layout {
+    pool: &'static str,
+    vdev: String,
+    role: ZfsRole,
+    /// Relative share of its pool's workload (0 for an idle spare)
+    weight: f64,
+}
+
+/// Generates one snapshot per call, animated by wall-clock time
+pub struct Simulator {
+    started: Instant,
+    count: u64,
+    rng: u64,
+    /// Drive index -> snapshots it keeps running hot
+    hot: HashMap<usize, u64>,
+    /// Cumulative read errors per drive, which only ever grow
+    errors: Vec<u64>,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulator {
+    pub fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self {
+            started: Instant::now(),
+            count: 0,
+            rng: seed | 1,
+            hot: HashMap::new(),
+            errors: vec![0; DRIVES],
+        }
+    }
+
+    /// The next snapshot
+    pub fn snapshot(&mut self) -> Snapshot {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        // 0.1 (quiet) to 0.9 (busy)
+        let load = 0.5 - 0.4 * (elapsed / WAVE_SECS * TAU).cos();
+        let slow = self.count.is_multiple_of(SLOW_EVERY);
+        self.count += 1;
+
+        self.hot.retain(|_, left| {
+            *left -= 1;
+            *left > 0
+        });
+        if self.chance(HOT_CHANCE) {
+            let drive = self.below(DRIVES as u64) as usize;
+            let snapshots = HOT_SNAPSHOTS.start + self.below(HOT_SNAPSHOTS.end - HOT_SNAPSHOTS.start);
+            self.hot.insert(drive, snapshots);
+        }
+
+        let multipath_devices = (0..DRIVES).map(|i| self.multipath_device(i, elapsed, load)).collect();
+        let standalone_disks = vec![self.log_device(load)];
+
+        Snapshot {
+            multipath_devices,
+            standalone_disks,
+            cpu_stats: self.cpu_stats(load),
+            memory_stats: self.memory_stats(elapsed),
+            network_stats: self.network_stats(load),
+            vms: slow.then(|| self.vms(elapsed)),
+            jails: slow.then(jails),
+            zil_stats: Some(ZilStats {
+                commits_per_sec: 1200.0 * load * self.jitter(0.1),
+                slog_writes_per_sec: 1100.0 * load * self.jitter(0.1),
+                slog_mbps: 60.0 * load * self.jitter(0.1),
+                normal_writes_per_sec: 40.0 * load * self.jitter(0.3),
+            }),
+            enclosure_health: slow.then(|| self.enclosure_health(load)),
+            pool_usage: slow.then(|| pool_usage(elapsed)),
+            datasets: slow.then(|| datasets(elapsed)),
+            notice: None,
+            storage_repeated: false,
+            system_repeated: false,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            hostname: Some(HOSTNAME.to_string()),
+        }
+    }
+
+    /// Drive `i` in slot `i`, with paths da`i` on controller A and
+    /// da`i + DRIVES` on controller B, alternating which one is active
+    fn multipath_device(&mut self, i: usize, elapsed: f64, load: f64) -> MultipathDevice {
+        let layout = layout(i);
+        // Each drive lags the wave a little, so they don't move in lockstep
+        let phase = (elapsed / 7.0 + i as f64).sin() * 0.15;
+        let hot = self.hot.contains_key(&i);
+
+        let mut statistics = if layout.weight == 0.0 {
+            DiskStatistics::default()
+        } else {
+            let demand = layout.weight * (load + phase).clamp(0.02, 1.0);
+            let read_iops = 500.0 * demand * self.jitter(0.2);
+            let write_iops = 250.0 * demand * self.jitter(0.2);
+            let total = read_iops + write_iops;
+            let busy = if hot {
+                85.0 + 15.0 * self.unit()
+            } else {
+                (total / DRIVE_CAPACITY_IOPS * 100.0).min(95.0)
+            };
+            DiskStatistics {
+                read_iops,
+                write_iops,
+                // 128K records
+                read_bw_mbps: read_iops * 0.125,
+                write_bw_mbps: write_iops * 0.125,
+                read_latency_ms: (4.0 + busy / 8.0) * self.jitter(0.3),
+                write_latency_ms: (1.5 + busy / 12.0) * self.jitter(0.3),
+                queue_depth: busy / 100.0 * if hot { 24.0 } else { 4.0 } * self.jitter(0.2),
+                busy_pct: busy,
+                ..Default::default()
+            }
+        };
+        // Now and then a read error on a data drive, to exercise the counters
+        if layout.weight > 0.0 && self.chance(0.0005) {
+            self.errors[i] += 1;
+        }
+        statistics.read_errors = self.errors[i];
+        statistics.timestamp = Some(Instant::now());
+
+        let active_controller = (i % 2) as u8;
+        let path_stats: Vec<PathStats> = [i, i + DRIVES]
+            .into_iter()
+            .enumerate()
+            .map(|(controller, da)| {
+                let is_active = controller as u8 == active_controller;
+                PathStats {
+                    device_name: format!("da{}", da),
+                    controller: controller as u8,
+                    is_active,
+                    statistics: if is_active { statistics.clone() } else { DiskStatistics::default() },
+                }
+            })
+            .collect();
+        let zfs_state = if layout.role == ZfsRole::Spare { "AVAIL" } else { "ONLINE" };
+        let paths = path_stats.iter().map(|p| p.device_name.clone()).collect();
+        let active_path = path_stats.iter().find(|p| p.is_active).map(|p| p.device_name.clone());
+
+        MultipathDevice {
+            name: format!("multipath/SIM{:04}", i),
+            ident: Some(format!("SIM{:04}", i)),
+            state: MultipathState::Optimal,
+            paths,
+            active_path,
+            statistics,
+            path_stats,
+            zfs_info: Some(ZfsDriveInfo {
+                pool: layout.pool.to_string(),
+                vdev: layout.vdev,
+                state: zfs_state.to_string(),
+                role: layout.role,
+            }),
+            slot: Some(i),
+            enclosure: Some(ENCLOSURE.to_string()),
+            partitions: Vec::new(),
+            failed_paths: Vec::new(),
+        }
+    }
+
+    /// The NVMe SLOG of "tank", outside the enclosure
+    fn log_device(&mut self, load: f64) -> PhysicalDisk {
+        let write_iops = 1100.0 * load * self.jitter(0.1);
+        PhysicalDisk {
+            device_name: "nda0".to_string(),
+            rank: Some(1),
+            ident: Some("SIMNVME0".to_string()),
+            multipath_parent: None,
+            slot: None,
+            enclosure: None,
+            statistics: DiskStatistics {
+                write_iops,
+                write_bw_mbps: write_iops * 0.05,
+                write_latency_ms: 0.05 * self.jitter(0.3),
+                queue_depth: load * self.jitter(0.2),
+                busy_pct: 20.0 * load * self.jitter(0.2),
+                timestamp: Some(Instant::now()),
+                ..Default::default()
+            },
+            path_state: PathState::Active,
+            zfs_info: Some(ZfsDriveInfo {
+                pool: "tank".to_string(),
+                vdev: String::new(),
+                role: ZfsRole::Slog,
+                state: "ONLINE".to_string(),
+            }),
+            partitions: Vec::new(),
+        }
+    }
+
+    fn cpu_stats(&mut self, load: f64) -> CpuStats {
+        let cores = (0..CORES)
+            .map(|core_id| {
+                let user_pct = 35.0 * load * self.jitter(0.4);
+                let system_pct = 12.0 * load * self.jitter(0.4);
+                // Interrupts land on the first few cores, where the HBAs and NICs are bound
+                let interrupt_pct = if core_id < 4 { 10.0 * load * self.jitter(0.3) } else { 0.5 };
+                let total_pct = (user_pct + system_pct + interrupt_pct).min(100.0);
+                CoreStats {
+                    core_id,
+                    user_pct,
+                    system_pct,
+                    interrupt_pct,
+                    idle_pct: 100.0 - total_pct,
+                    total_pct,
+                }
+            })
+            .collect();
+
+        let mut top_sources: Vec<InterruptSource> = [
+            ("irq264: mpr0", 9000.0),
+            ("irq265: mpr1", 8500.0),
+            ("irq270: ix0:rxq0", 6000.0),
+            ("irq271: ix0:rxq1", 5500.0),
+            ("cpu0:timer", 2000.0),
+        ]
+        .into_iter()
+        .map(|(name, peak)| InterruptSource {
+            name: name.to_string(),
+            per_sec: peak * load * self.jitter(0.2),
+        })
+        .collect();
+        top_sources.sort_by(|a, b| b.per_sec.total_cmp(&a.per_sec));
+
+        CpuStats {
+            cores,
+            interrupts: Some(InterruptStats {
+                total_per_sec: top_sources.iter().map(|s| s.per_sec).sum::<f64>() * 1.2,
+                top_sources,
+            }),
+        }
+    }
+
+    fn memory_stats(&mut self, elapsed: f64) -> MemoryStats {
+        let total_bytes = 128 * GIB;
+        let managed_bytes = 126 * GIB;
+        // The ARC breathes between 60 and 70 GiB
+        let arc_total_bytes = 60 * GIB + (((elapsed / 40.0).sin() * 0.5 + 0.5) * 10.0 * GIB as f64) as u64;
+        let arc_reclaimable_bytes = arc_total_bytes - 8 * GIB;
+        let active_bytes = 14 * GIB;
+        let inactive_bytes = 18 * GIB;
+        let laundry_bytes = GIB;
+        let wired_bytes = arc_total_bytes + 6 * GIB;
+        let free_bytes = managed_bytes - active_bytes - inactive_bytes - laundry_bytes - wired_bytes;
+        let reclaimable_bytes = inactive_bytes + arc_reclaimable_bytes;
+        let committed_bytes = managed_bytes - free_bytes - reclaimable_bytes;
+        let arc_compressed_bytes = arc_total_bytes * 2 / 3;
+        let arc_uncompressed_bytes = arc_total_bytes * 3 / 2;
+
+        MemoryStats {
+            total_bytes,
+            managed_bytes,
+            active_bytes,
+            inactive_bytes,
+            laundry_bytes,
+            wired_bytes,
+            buf_bytes: GIB / 2,
+            free_bytes,
+            committed_bytes,
+            reclaimable_bytes,
+            arc_reclaimable_bytes,
+            used_pct: committed_bytes as f64 / total_bytes as f64 * 100.0,
+            swap_total_bytes: 8 * GIB,
+            swap_used_bytes: GIB / 4,
+            swap_used_pct: 100.0 / 32.0,
+            arc_total_bytes,
+            arc_mfu_bytes: arc_total_bytes / 2,
+            arc_mru_bytes: arc_total_bytes * 2 / 5,
+            arc_anon_bytes: GIB / 8,
+            arc_header_bytes: GIB / 2,
+            arc_compressed_bytes,
+            arc_uncompressed_bytes,
+            arc_ratio: arc_uncompressed_bytes as f64 / arc_compressed_bytes as f64,
+            arc_hit_pct: Some((96.0 * self.jitter(0.02)).min(100.0)),
+            arc_demand_data_hit_pct: Some((92.0 * self.jitter(0.04)).min(100.0)),
+            l2arc_size_bytes: 400 * GIB,
+            l2arc_hit_pct: Some(30.0 * self.jitter(0.3)),
+            ..Default::default()
+        }
+    }
+
+    /// A two-port lagg carrying the storage traffic, and a management port
+    fn network_stats(&mut self, load: f64) -> Vec<NetworkStats> {
+        let ports: Vec<(f64, f64)> = (0..2)
+            .map(|_| (600e6 * load * self.jitter(0.2), 250e6 * load * self.jitter(0.2)))
+            .collect();
+        let interface = |name: &str, rx: f64, tx: f64, baudrate: u64| NetworkStats {
+            name: name.to_string(),
+            rx_bytes_per_sec: rx,
+            tx_bytes_per_sec: tx,
+            rx_packets_per_sec: rx / 1500.0,
+            tx_packets_per_sec: tx / 1500.0,
+            rx_bytes_per_sec_raw: rx,
+            tx_bytes_per_sec_raw: tx,
+            link_state: 2,
+            baudrate,
+            ..Default::default()
+        };
+
+        let mut lagg = interface("lagg0", ports[0].0 + ports[1].0, ports[0].1 + ports[1].1, 20_000_000_000);
+        lagg.is_aggregate = true;
+        let mut stats = vec![lagg];
+        for (n, &(rx, tx)) in ports.iter().enumerate() {
+            let mut port = interface(&format!("ix{}", n), rx, tx, 10_000_000_000);
+            port.is_member = true;
+            stats.push(port);
+        }
+        stats.push(interface("igb0", 20e3 * self.jitter(0.5), 8e3 * self.jitter(0.5), 1_000_000_000));
+        stats
+    }
+
+    fn vms(&mut self, elapsed: f64) -> Vec<VmInfo> {
+        [("build01", 8, 70.0), ("db01", 16, 140.0), ("web01", 4, 25.0)]
+            .into_iter()
+            .enumerate()
+            .map(|(n, (name, memory_gib, cpu_pct))| VmInfo {
+                name: name.to_string(),
+                pid: 4100 + n as u32,
+                cpu_pct: cpu_pct * self.jitter(0.5),
+                memory_bytes: memory_gib * GIB,
+                virtual_bytes: (memory_gib + 1) * GIB,
+                runtime_secs: 86_400.0 * (n + 3) as f64 + elapsed,
+            })
+            .collect()
+    }
+
+    /// Two PSUs, four fans and two temperature sensors, with the second fan
+    /// flagged while the shelf is working hardest
+    fn enclosure_health(&mut self, load: f64) -> Vec<EnclosureHealth> {
+        let mut elements = Vec::new();
+        for index in 0..2 {
+            elements.push(EnclosureElement {
+                kind: ElementKind::PowerSupply,
+                index,
+                status: ElementStatus::Ok,
+                reading: None,
+            });
+        }
+        for index in 0..4 {
+            let status = if index == 1 && load > 0.85 { ElementStatus::NonCritical } else { ElementStatus::Ok };
+            elements.push(EnclosureElement {
+                kind: ElementKind::Fan,
+                index: 2 + index,
+                status,
+                reading: Some((4000.0 + 4000.0 * load * self.jitter(0.05)).round()),
+            });
+        }
+        for index in 0..2 {
+            elements.push(EnclosureElement {
+                kind: ElementKind::Temperature,
+                index: 6 + index,
+                status: ElementStatus::Ok,
+                reading: Some((28.0 + 10.0 * load * self.jitter(0.05)).round()),
+            });
+        }
+        vec![EnclosureHealth { enclosure: ENCLOSURE.to_string(), elements }]
+    }
+
+    /// xorshift64; quality doesn't matter here and it saves a dependency
+    fn next(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+
+    /// Uniform in [0, 1)
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        self.unit() < p
+    }
+
+    /// A factor within `spread` of 1
+    fn jitter(&mut self, spread: f64) -> f64 {
+        1.0 + spread * (self.unit() * 2.0 - 1.0)
+    }
+}
+
+/// Slots 0-15: "tank", two 8-wide raidz2; 16-21: "fast", three mirrors;
+/// 22: tank's hot spare
+fn layout(i: usize) -> Layout {
+    match i {
+        0..=15 => Layout {
+            pool: "tank",
+            vdev: format!("raidz2-{}", i / 8),
+            role: ZfsRole::Data,
+            weight: 1.0,
+        },
+        16..=21 => Layout {
+            pool: "fast",
+            vdev: format!("mirror-{}", (i - 16) / 2),
+            role: ZfsRole::Data,
+            weight: 1.4,
+        },
+        _ => Layout {
+            pool: "tank",
+            vdev: String::new(),
+            role: ZfsRole::Spare,
+            weight: 0.0,
+        },
+    }
+}
+
+fn jails() -> Vec<JailInfo> {
+    ["dns", "proxy", "monitoring"]
+        .into_iter()
+        .enumerate()
+        .map(|(n, name)| JailInfo {
+            jid: n as u32 + 1,
+            name: name.to_string(),
+            hostname: format!("{}.example.com", name),
+            ip_addresses: vec![format!("192.0.2.{}", n + 10), format!("2001:db8::{:x}", n + 10)],
+            path: format!("/jails/{}", name),
+        })
+        .collect()
+}
+
+/// "tank" slowly filling up; "fast" steady
+fn pool_usage(elapsed: f64) -> Vec<PoolUsage> {
+    let tank_size = 160 * TIB;
+    let tank_alloc = 96 * TIB + (elapsed * 256.0 * GIB as f64 / 3600.0) as u64;
+    let fast_size = 21 * TIB;
+    let fast_alloc = 9 * TIB;
+    vec![
+        PoolUsage {
+            pool: "tank".to_string(),
+            size_bytes: tank_size,
+            alloc_bytes: tank_alloc,
+            free_bytes: tank_size.saturating_sub(tank_alloc),
+            fragmentation_pct: Some(23.0),
+        },
+        PoolUsage {
+            pool: "fast".to_string(),
+            size_bytes: fast_size,
+            alloc_bytes: fast_alloc,
+            free_bytes: fast_size - fast_alloc,
+            fragmentation_pct: Some(8.0),
+        },
+    ]
+}
+
+fn datasets(elapsed: f64) -> Vec<DatasetUsage> {
+    let growth = (elapsed * 128.0 * GIB as f64 / 3600.0) as u64;
+    let dataset = |name: &str, used_bytes: u64, avail_bytes: u64, refer_bytes: u64, ratio: f64| DatasetUsage {
+        name: name.to_string(),
+        used_bytes,
+        avail_bytes,
+        refer_bytes,
+        compress_ratio: Some(ratio),
+    };
+    vec![
+        dataset("tank", 70 * TIB + growth, (40 * TIB).saturating_sub(growth), 200 * 1024, 1.31),
+        dataset("tank/backup", 48 * TIB + growth, (40 * TIB).saturating_sub(growth), 45 * TIB + growth, 1.12),
+        dataset("tank/media", 19 * TIB, (40 * TIB).saturating_sub(growth), 19 * TIB, 1.01),
+        dataset("tank/home", 3 * TIB, (40 * TIB).saturating_sub(growth), 2 * TIB, 1.86),
+        dataset("fast", 6 * TIB, 4 * TIB, 200 * 1024, 1.74),
+        dataset("fast/db", 4 * TIB, 4 * TIB, 4 * TIB, 2.10),
+        dataset("fast/vm", 2 * TIB, 4 * TIB, 2 * TIB, 1.55),
+    ]
+}
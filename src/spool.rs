@@ -0,0 +1,309 @@
+//! Crash-survivable ring buffer of aggregate samples
+//!
+//! `--spool <DIR>` keeps the last hour of aggregate samples (storage IOPS,
+//! bandwidth, latency and busy%, CPU and memory), one per second, in a
+//! fixed-size file `DIR/sanview.spool` that is synced to disk every few
+//! seconds. After a panic or hard reset, `--replay-spool <DIR>` prints what
+//! the box was doing leading up to it.
+//!
+//! File layout, little-endian: a 32-byte header (magic, version, record
+//! size, capacity, total records ever written) followed by `capacity`
+//! fixed-size records; record `n` lives in slot `n % capacity`, so the file
+//! never grows and the newest records overwrite the oldest.
+
+use crate::domain::device::DiskStatistics;
+use crate::remote::Snapshot;
+use crate::util::format::{disk_mbps, format_datetime};
+use anyhow::{Context, Result};
+use log::warn;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Spool file name inside the --spool directory
+pub const SPOOL_FILE: &str = "sanview.spool";
+
+const MAGIC: &[u8; 8] = b"SVSPOOL\0";
+const VERSION: u32 = 1;
+const HEADER_SIZE: u64 = 32;
+const RECORD_SIZE: usize = 48;
+/// One hour at one sample per second
+const CAPACITY: u64 = 3600;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Aggregate metrics for one moment, as stored in the spool
+///
+/// Storage figures cover multipath devices only, matching the aggregate
+/// charts (each disk counted once).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpoolSample {
+    pub timestamp_ms: u64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub read_mbps: f64,
+    pub write_mbps: f64,
+    pub read_latency_ms: f64,
+    pub write_latency_ms: f64,
+    pub busy_pct: f64,        // Average over devices
+    pub queue_depth: f64,     // Sum over devices
+    pub cpu_pct: f64,         // Average over cores
+    pub memory_used_pct: f64,
+}
+
+impl SpoolSample {
+    pub fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let devices = &snapshot.multipath_devices;
+        let sum = |f: fn(&DiskStatistics) -> f64| {
+            devices.iter().map(|d| f(&d.statistics)).sum::<f64>()
+        };
+        // Latency averages only devices that did I/O in that direction
+        let avg_active = |iops: fn(&DiskStatistics) -> f64,
+                          latency: fn(&DiskStatistics) -> f64| {
+            let active: Vec<f64> = devices
+                .iter()
+                .filter(|d| iops(&d.statistics) > 0.1)
+                .map(|d| latency(&d.statistics))
+                .collect();
+            if active.is_empty() {
+                0.0
+            } else {
+                active.iter().sum::<f64>() / active.len() as f64
+            }
+        };
+        let cores = &snapshot.cpu_stats.cores;
+
+        Self {
+            timestamp_ms: snapshot.timestamp_ms,
+            read_iops: sum(|s| s.read_iops),
+            write_iops: sum(|s| s.write_iops),
+            read_mbps: sum(|s| s.read_bw_mbps),
+            write_mbps: sum(|s| s.write_bw_mbps),
+            read_latency_ms: avg_active(|s| s.read_iops, |s| s.read_latency_ms),
+            write_latency_ms: avg_active(|s| s.write_iops, |s| s.write_latency_ms),
+            busy_pct: if devices.is_empty() {
+                0.0
+            } else {
+                sum(|s| s.busy_pct) / devices.len() as f64
+            },
+            queue_depth: sum(|s| s.queue_depth),
+            cpu_pct: if cores.is_empty() {
+                0.0
+            } else {
+                cores.iter().map(|c| c.total_pct).sum::<f64>() / cores.len() as f64
+            },
+            memory_used_pct: snapshot.memory_stats.used_pct,
+        }
+    }
+
+    fn values(&self) -> [f64; 10] {
+        [
+            self.read_iops,
+            self.write_iops,
+            self.read_mbps,
+            self.write_mbps,
+            self.read_latency_ms,
+            self.write_latency_ms,
+            self.busy_pct,
+            self.queue_depth,
+            self.cpu_pct,
+            self.memory_used_pct,
+        ]
+    }
+
+    fn encode(&self) -> [u8; RECORD_SIZE] {
+        let mut record = [0u8; RECORD_SIZE];
+        record[..8].copy_from_slice(&self.timestamp_ms.to_le_bytes());
+        for (i, value) in self.values().iter().enumerate() {
+            let at = 8 + i * 4;
+            record[at..at + 4].copy_from_slice(&(*value as f32).to_le_bytes());
+        }
+        record
+    }
+
+    fn decode(record: &[u8]) -> Self {
+        let value = |i: usize| {
+            let at = 8 + i * 4;
+            f32::from_le_bytes(record[at..at + 4].try_into().unwrap()) as f64
+        };
+        Self {
+            timestamp_ms: u64::from_le_bytes(record[..8].try_into().unwrap()),
+            read_iops: value(0),
+            write_iops: value(1),
+            read_mbps: value(2),
+            write_mbps: value(3),
+            read_latency_ms: value(4),
+            write_latency_ms: value(5),
+            busy_pct: value(6),
+            queue_depth: value(7),
+            cpu_pct: value(8),
+            memory_used_pct: value(9),
+        }
+    }
+}
+
+/// Appends samples to the spool file, wrapping at capacity
+pub struct SpoolWriter {
+    file: File,
+    written: u64,
+    pending: Vec<SpoolSample>,
+    last_sample: Option<Instant>,
+    last_flush: Instant,
+}
+
+impl SpoolWriter {
+    /// Open (or create) the spool in `dir`, continuing an existing ring
+    ///
+    /// A file with a different layout is reinitialized rather than misread.
+    pub fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create spool directory {}", dir.display()))?;
+        let path = dir.join(SPOOL_FILE);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open spool {}", path.display()))?;
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        let written = match file.read_exact(&mut header) {
+            Ok(()) => parse_header(&header).filter(|&(capacity, _)| capacity == CAPACITY),
+            Err(_) => None,
+        };
+        let written = match written {
+            Some((_, written)) => written,
+            None => {
+                file.set_len(HEADER_SIZE + CAPACITY * RECORD_SIZE as u64)
+                    .with_context(|| format!("Failed to size spool {}", path.display()))?;
+                write_header(&mut file, 0)?;
+                0
+            }
+        };
+
+        Ok(Self {
+            file,
+            written,
+            pending: Vec::new(),
+            last_sample: None,
+            last_flush: Instant::now(),
+        })
+    }
+
+    /// Queue this cycle's aggregates (at most one sample per second) and
+    /// sync to disk every few seconds
+    pub fn record(&mut self, snapshot: &Snapshot) {
+        if self.last_sample.is_some_and(|t| t.elapsed() < SAMPLE_INTERVAL) {
+            return;
+        }
+        self.last_sample = Some(Instant::now());
+        self.pending.push(SpoolSample::from_snapshot(snapshot));
+
+        if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+            if let Err(e) = self.flush() {
+                warn!("Failed to write spool: {:#}", e);
+            }
+        }
+    }
+
+    /// Write queued samples and the header, then fsync
+    pub fn flush(&mut self) -> Result<()> {
+        self.last_flush = Instant::now();
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        for sample in self.pending.drain(..) {
+            let slot = self.written % CAPACITY;
+            self.file
+                .seek(SeekFrom::Start(HEADER_SIZE + slot * RECORD_SIZE as u64))?;
+            self.file.write_all(&sample.encode())?;
+            self.written += 1;
+        }
+        write_header(&mut self.file, self.written)?;
+        self.file.sync_data().context("Failed to sync spool")
+    }
+}
+
+impl Drop for SpoolWriter {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            warn!("Failed to write spool: {:#}", e);
+        }
+    }
+}
+
+/// Samples stored in the spool in `dir`, oldest first
+pub fn read_spool(dir: &Path) -> Result<Vec<SpoolSample>> {
+    let path = dir.join(SPOOL_FILE);
+    let data = std::fs::read(&path)
+        .with_context(|| format!("Failed to read spool {}", path.display()))?;
+
+    let (capacity, written) = data
+        .get(..HEADER_SIZE as usize)
+        .and_then(parse_header)
+        .with_context(|| format!("{} is not a sanview spool", path.display()))?;
+
+    let count = written.min(capacity);
+    let samples = (written - count..written)
+        .filter_map(|n| {
+            let at = (HEADER_SIZE + (n % capacity) * RECORD_SIZE as u64) as usize;
+            data.get(at..at + RECORD_SIZE).map(SpoolSample::decode)
+        })
+        .filter(|s| s.timestamp_ms > 0)
+        .collect();
+
+    Ok(samples)
+}
+
+/// Print samples as a table, one row per second, for `--replay-spool`
+pub fn write_replay(out: &mut impl Write, samples: &[SpoolSample]) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{:<19} {:>8} {:>8} {:>9} {:>9} {:>7} {:>7} {:>5} {:>6} {:>5} {:>5}",
+        "TIME", "R_IOPS", "W_IOPS", "R_MB/S", "W_MB/S", "R_MS", "W_MS", "BUSY", "QD", "CPU", "MEM"
+    )?;
+    for s in samples {
+        writeln!(
+            out,
+            "{:<19} {:>8.0} {:>8.0} {:>9.1} {:>9.1} {:>7.2} {:>7.2} {:>4.0}% {:>6.1} {:>4.0}% {:>4.0}%",
+            format_datetime(s.timestamp_ms),
+            s.read_iops,
+            s.write_iops,
+            disk_mbps(s.read_mbps),
+            disk_mbps(s.write_mbps),
+            s.read_latency_ms,
+            s.write_latency_ms,
+            s.busy_pct,
+            s.queue_depth,
+            s.cpu_pct,
+            s.memory_used_pct,
+        )?;
+    }
+    Ok(())
+}
+
+/// (capacity, records written) from a valid header
+fn parse_header(header: &[u8]) -> Option<(u64, u64)> {
+    let u32_at = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+    if &header[..8] != MAGIC || u32_at(8) != VERSION || u32_at(12) as usize != RECORD_SIZE {
+        return None;
+    }
+    let capacity = u32_at(16) as u64;
+    let written = u64::from_le_bytes(header[24..32].try_into().unwrap());
+    (capacity > 0).then_some((capacity, written))
+}
+
+fn write_header(file: &mut File, written: u64) -> Result<()> {
+    let mut header = [0u8; HEADER_SIZE as usize];
+    header[..8].copy_from_slice(MAGIC);
+    header[8..12].copy_from_slice(&VERSION.to_le_bytes());
+    header[12..16].copy_from_slice(&(RECORD_SIZE as u32).to_le_bytes());
+    header[16..20].copy_from_slice(&(CAPACITY as u32).to_le_bytes());
+    header[24..32].copy_from_slice(&written.to_le_bytes());
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&header).context("Failed to write spool header")
+}
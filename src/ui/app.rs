@@ -1,6 +1,13 @@
-use crate::collectors::{CpuStats, MemoryStats};
-use crate::ui::components::{render_front_panel, render_system_overview};
-use crate::ui::state::AppState;
+use crate::domain::controller::{controller_balance, controller_rollup};
+use crate::ui::components::{
+    find_alerts, render_alerts, render_datasets, render_front_panel, render_system_overview,
+};
+use crate::ui::glyphs::{self, glyphs};
+use crate::ui::options::{ClockStyle, UiOptions};
+use crate::ui::theme::Theme;
+use crate::ui::zoom::Zoom;
+use crate::ui::state::{AppState, Density, HealthScore, Maximized};
+use crate::util::format::{self, format_bytes, format_clock};
 use anyhow::Result;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers},
@@ -9,44 +16,88 @@ use crossterm::{
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Terminal,
 };
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// How long the latest event stays in the footer
+const RECENT_EVENT_SECS: u64 = 60;
+
+/// Whether the UI acts on mouse events; until it does (click to select a
+/// bay), capturing them would only stop the terminal's own text selection,
+/// so mouse capture stays off even without --no-mouse
+const HANDLES_MOUSE: bool = false;
+
+/// Cells in the header's controller balance bar
+const BALANCE_BAR_WIDTH: usize = 10;
+
+/// Share of the bandwidth on the busier controller at which the balance
+/// bar turns warn
+const BALANCE_WARN_PCT: f64 = 70.0;
+
+/// Smallest terminal the full layout fits in (the 25-bay strip alone is 77 columns)
+const MIN_WIDTH: u16 = 100;
+const MIN_HEIGHT: u16 = 30;
+
+/// Sets the quit flag however the UI exits (quit key, watchdog, error),
+/// once the terminal has been restored
+struct QuitOnExit(Arc<AtomicBool>);
+
+impl Drop for QuitOnExit {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Run the UI until the user quits; `quit` is set on the way out, for the
+/// collection loop to stop on instead of polling the thread
+pub fn run_tui(state: Arc<Mutex<AppState>>, options: UiOptions, quit: Arc<AtomicBool>) -> Result<()> {
+    let _quit = QuitOnExit(quit);
+    glyphs::select(options.ascii);
+    format::select(options.units);
 
-pub fn run_tui(state: Arc<Mutex<AppState>>) -> Result<()> {
     // Setup terminal
+    let mouse = options.mouse && HANDLES_MOUSE;
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Run the UI loop
-    let result = run_app(&mut terminal, state);
+    let result = run_app(&mut terminal, state, &options);
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    if mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     result
 }
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: Arc<Mutex<AppState>>) -> Result<()> {
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    state: Arc<Mutex<AppState>>,
+    options: &UiOptions,
+) -> Result<()> {
     // Track last full screen clear to handle kernel console output clobbering
     let mut last_clear = Instant::now();
     const CLEAR_INTERVAL: Duration = Duration::from_secs(10);
     let mut force_clear = false;
+    let dimmed = options.theme.dimmed();
 
     loop {
         // Periodic full screen clear to remove any kernel console garbage
@@ -56,103 +107,124 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: Arc<Mut
             force_clear = false;
         }
 
-        // Update terminal width in state for dynamic history sizing
+        // Render straight from the shared state under its lock: a per-frame
+        // clone would copy every retained history (--history samples per
+        // series and drive). Only the drive numbers are swapped for their
+        // average over the --average window, and back after the frame
         let terminal_size = terminal.size()?;
-        {
-            let mut state_guard = state.lock().unwrap();
-            state_guard.set_terminal_width(terminal_size.width);
-        }
+        let mut state_guard = state.lock().unwrap();
+        state_guard.set_terminal_width(terminal_size.width);
+        let live_stats =
+            state_guard.average_drive_stats((options.average_ms / options.refresh_ms.max(1)) as usize);
+        let current_state: &AppState = &state_guard;
 
-        // Clone state for rendering
-        let current_state = {
-            let state_guard = state.lock().unwrap();
-            state_guard.clone()
+        let zoom = Zoom::new(current_state.zoom_factor, options.refresh_ms);
+        let system_zoom = Zoom::new(current_state.zoom_factor, options.system_refresh_ms);
+
+        let density = current_state.density;
+
+        // Any drive activity brings the colors straight back
+        let theme = match options.idle_dim {
+            Some(after) if current_state.is_idle(after) => &dimmed,
+            _ => &options.theme,
         };
 
-        // Render
-        terminal.draw(|frame| {
+        let drawn = terminal.draw(|frame| {
+            let size = frame.size();
+            if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+                render_too_small(frame, size, theme);
+                return;
+            }
+
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(3),      // Header
-                    Constraint::Percentage(30), // System stats (top)
-                    Constraint::Min(12),        // Drive array (bottom)
+                    Constraint::Length(match density {
+                        Density::Comfortable => 3,
+                        Density::Dense => 1,
+                    }),                         // Header
+                    // System stats (top) and drive array (bottom), or one of them alone
+                    match current_state.maximized {
+                        Maximized::Neither => Constraint::Percentage(30),
+                        Maximized::Storage => Constraint::Length(0),
+                        Maximized::System => Constraint::Fill(1),
+                    },
+                    match current_state.maximized {
+                        Maximized::System => Constraint::Length(0),
+                        _ => Constraint::Min(12),
+                    },
                     Constraint::Length(1),      // Footer (single line, no border)
                 ])
-                .split(frame.size());
+                .split(size);
 
             // Header
-            render_header(frame, chunks[0], &current_state);
+            render_header(frame, chunks[0], current_state, density, options.clock, theme);
+
+            // The key list, then the problems-only view, take over both panel sections
+            if current_state.help_view {
+                let body = Rect {
+                    height: chunks[1].height + chunks[2].height,
+                    ..chunks[1]
+                };
+                render_help(frame, body, theme);
+                render_footer(frame, chunks[3], current_state, theme);
+                return;
+            }
+            if current_state.alerts_only {
+                let body = Rect {
+                    height: chunks[1].height + chunks[2].height,
+                    ..chunks[1]
+                };
+                let alerts = find_alerts(
+                    &current_state.multipath_devices,
+                    &current_state.standalone_disks,
+                    &current_state.network_stats,
+                    &current_state.enclosure_health,
+                    current_state.expected_paths,
+                );
+                render_alerts(frame, body, &alerts, density, theme);
+                render_footer(frame, chunks[3], current_state, theme);
+                return;
+            }
+
+            // Likewise the dataset space list
+            if current_state.datasets_view {
+                let body = Rect {
+                    height: chunks[1].height + chunks[2].height,
+                    ..chunks[1]
+                };
+                render_datasets(frame, body, &current_state.datasets, density, theme);
+                render_footer(frame, chunks[3], current_state, theme);
+                return;
+            }
 
             // System stats section (CPU, Memory, VMs, Jails)
-            let empty_cpu = CpuStats { cores: Vec::new() };
-            let empty_mem = MemoryStats {
-                total_bytes: 0,
-                active_bytes: 0,
-                inactive_bytes: 0,
-                laundry_bytes: 0,
-                wired_bytes: 0,
-                buf_bytes: 0,
-                free_bytes: 0,
-                used_pct: 0.0,
-                swap_total_bytes: 0,
-                swap_used_bytes: 0,
-                swap_used_pct: 0.0,
-                arc_total_bytes: 0,
-                arc_mfu_bytes: 0,
-                arc_mru_bytes: 0,
-                arc_anon_bytes: 0,
-                arc_header_bytes: 0,
-                arc_other_bytes: 0,
-                arc_compressed_bytes: 0,
-                arc_uncompressed_bytes: 0,
-                arc_ratio: 0.0,
-            };
-
-            render_system_overview(
-                frame,
-                chunks[1],
-                current_state.cpu_stats.as_ref().unwrap_or(&empty_cpu),
-                current_state.memory_stats.as_ref().unwrap_or(&empty_mem),
-                &current_state.network_stats,
-                &current_state.vms,
-                &current_state.jails,
-                &current_state.cpu_history,
-                &current_state.cpu_aggregate_history,
-                &current_state.memory_history,
-                &current_state.arc_size_history,
-                &current_state.arc_ratio_history,
-                &current_state.network_history,
-            );
+            render_system_overview(frame, chunks[1], current_state, system_zoom, options, theme);
 
             // Drive array at bottom with history sparklines
-            render_front_panel(
-                frame,
-                chunks[2],
-                &current_state.multipath_devices,
-                &current_state.storage_read_iops_history,
-                &current_state.storage_write_iops_history,
-                &current_state.storage_read_bw_history,
-                &current_state.storage_write_bw_history,
-                &current_state.storage_read_latency_history,
-                &current_state.storage_write_latency_history,
-                &current_state.storage_queue_depth_history,
-                &current_state.storage_busy_history,
-                &current_state.drive_busy_history,
-            );
+            render_front_panel(frame, chunks[2], current_state, zoom, options, theme);
 
             // Footer
-            render_footer(frame, chunks[3], &current_state);
-        })?;
+            render_footer(frame, chunks[3], current_state, theme);
+        });
+        state_guard.restore_drive_stats(live_stats);
+        drop(state_guard);
+        drawn?;
 
         // Handle input with timeout to allow for periodic updates
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                match handle_key_event(key, &state) {
+            match event::read()? {
+                Event::Key(key) => match handle_key_event(key, &state) {
                     KeyAction::Quit => break,
                     KeyAction::Redraw => force_clear = true,
                     KeyAction::None => {}
+                },
+                // Redraw from a clean screen right away instead of on the next tick
+                Event::Resize(_, _) => {
+                    terminal.autoresize()?;
+                    force_clear = true;
                 }
+                _ => {}
             }
         }
 
@@ -168,49 +240,225 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, state: Arc<Mut
     Ok(())
 }
 
-fn render_header(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &AppState) {
-    let elapsed = state.last_update.elapsed();
-    let header_text = Line::from(vec![
+fn render_too_small(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, theme: &Theme) {
+    let message = Paragraph::new(vec![
+        Line::from(Span::styled("Terminal too small", Style::default().fg(theme.warn))),
+        Line::from(Span::styled(
+            format!("{}x{}, need at least {}x{}", area.width, area.height, MIN_WIDTH, MIN_HEIGHT),
+            Style::default().fg(theme.muted),
+        )),
+    ])
+    .alignment(Alignment::Center);
+
+    // Vertically centered when there is room for it
+    let y = area.y + area.height.saturating_sub(2) / 2;
+    let message_area = ratatui::layout::Rect {
+        y,
+        height: area.height.min(2),
+        ..area
+    };
+    frame.render_widget(message, message_area);
+}
+
+fn render_header(
+    frame: &mut ratatui::Frame,
+    area: ratatui::layout::Rect,
+    state: &AppState,
+    density: Density,
+    clock: ClockStyle,
+    theme: &Theme,
+) {
+    // Array health badge, readable from across the room
+    let health = state.health_score();
+    let health_color = match health {
+        HealthScore::Healthy => theme.good,
+        HealthScore::Degraded => theme.warn,
+        HealthScore::Failed => theme.critical,
+    };
+    let mut header_text = Line::from(vec![
         Span::styled(
             "SANVIEW",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::raw(" - FreeBSD Storage Array Monitor  "),
+        Span::raw(" "),
         Span::styled(
-            format!("Updated: {:.1}s ago", elapsed.as_secs_f64()),
-            Style::default().fg(Color::DarkGray),
+            format!(" {} {} ", glyphs().led_on, health.label()),
+            Style::default()
+                .fg(health_color)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ),
+        Span::raw(match state.hostname {
+            Some(ref host) => format!(" - {}  ", host),
+            None => " - FreeBSD Storage Array Monitor  ".to_string(),
+        }),
+        Span::styled(
+            format!("Updated: {}", updated_label(state, clock)),
+            Style::default().fg(theme.muted),
         ),
     ]);
+    if let Some(share) = controller_balance(&controller_rollup(&state.multipath_devices)) {
+        header_text.spans.extend(balance_bar(share, theme));
+    }
+    if let Some(ref notice) = state.notice {
+        header_text.spans.push(Span::styled(
+            format!("  {}", notice),
+            Style::default().fg(theme.warn).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if state.topology_refresh_pending() {
+        header_text.spans.push(Span::styled(
+            format!("  refreshing topology{}", glyphs().ellipsis),
+            Style::default().fg(theme.warn),
+        ));
+    }
 
-    let header = Paragraph::new(header_text)
-        .block(
+    // Dense mode shows the header as a bare line
+    let header = match density {
+        Density::Comfortable => Paragraph::new(header_text).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
-        );
+                .border_set(glyphs().border)
+                .border_style(Style::default().fg(theme.accent)),
+        ),
+        Density::Dense => Paragraph::new(header_text),
+    };
 
     frame.render_widget(header, area);
 }
 
-fn render_footer(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &AppState) {
-    let footer_text = Line::from(vec![
-        Span::styled("[Q]", Style::default().fg(Color::Cyan)),
-        Span::styled("uit ", Style::default().fg(Color::DarkGray)),
-        Span::styled("[R]", Style::default().fg(Color::Cyan)),
-        Span::styled("edraw  ", Style::default().fg(Color::DarkGray)),
+/// "A ██████░░░░ B 60/40": how the bandwidth splits between the
+/// controllers, so a path policy that sends everything one way stands out
+fn balance_bar(share: f64, theme: &Theme) -> Vec<Span<'static>> {
+    let filled = ((share / 100.0 * BALANCE_BAR_WIDTH as f64).round() as usize).min(BALANCE_BAR_WIDTH);
+    let color = if share.max(100.0 - share) >= BALANCE_WARN_PCT { theme.warn } else { theme.good };
+    vec![
+        Span::styled("  A ", Style::default().fg(theme.muted)),
+        Span::styled(glyphs().bar_full.repeat(filled), Style::default().fg(color)),
+        Span::styled(glyphs().bar_free.repeat(BALANCE_BAR_WIDTH - filled), Style::default().fg(color)),
+        Span::styled(
+            format!(" B {:.0}/{:.0}", share.round(), 100.0 - share.round()),
+            Style::default().fg(theme.muted),
+        ),
+    ]
+}
+
+/// Time of the last update as the header shows it
+fn updated_label(state: &AppState, clock: ClockStyle) -> String {
+    let ago = format!("{:.1}s ago", state.last_update.elapsed().as_secs_f64());
+    let wall = || {
+        let ms = state
+            .last_update_wall
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        format_clock(ms)
+    };
+    match clock {
+        ClockStyle::Relative => ago,
+        ClockStyle::Absolute => wall(),
+        ClockStyle::Both => format!("{} ({})", wall(), ago),
+    }
+}
+
+/// Keys and what they do, for the help view
+const KEY_HELP: &[(&str, &str)] = &[
+    ("q / Esc", "quit"),
+    ("?", "show or hide this list"),
+    ("r", "re-read multipath, ZFS and SES topology now"),
+    ("g", "drive list: flat, grouped by vdev, collapsed"),
+    ("s", "cycle the drive sort order"),
+    ("f", "hide or show idle drives"),
+    ("i", "drive sparkline: busy%, read or write IOPS"),
+    ("c", "bay coloring: activity or pool"),
+    ("Left / Right", "move the bay cursor"),
+    ("l", "switch the locate LED of the selected bay (--allow-control)"),
+    ("+ / -", "zoom the charts out and in"),
+    ("b", "per-core CPU breakdown"),
+    ("d", "dense layout"),
+    ("n", "network rate: smoothed or per interval"),
+    ("p", "network packets/sec instead of bytes/sec"),
+    ("h", "latency histogram in place of the latency chart"),
+    ("a", "problems-only view"),
+    ("z", "ZFS dataset space list"),
+    ("o / O", "maximize the storage or the system section"),
+    ("Ctrl-L", "redraw the screen"),
+];
+
+fn render_help(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, theme: &Theme) {
+    let key_width = KEY_HELP.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = KEY_HELP
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(format!(" {:<width$}  ", key, width = key_width), Style::default().fg(theme.accent)),
+                Span::styled(*action, Style::default().fg(theme.muted)),
+            ])
+        })
+        .collect();
+    let help = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Keys ([?] closes) "),
+    );
+    frame.render_widget(help, area);
+}
+
+fn render_footer(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &AppState, theme: &Theme) {
+    let mut spans = Vec::new();
+
+    // The latest hot-swap event comes first, for a minute after it happens,
+    // so a narrow terminal clips the key hints and totals instead
+    if let Some(event) = state.events.back() {
+        let age = event.at.elapsed().as_secs();
+        if age < RECENT_EVENT_SECS {
+            spans.push(Span::styled(
+                format!("{} ({}s ago) {} ", event.message, age, glyphs().separator),
+                Style::default().fg(theme.warn),
+            ));
+        }
+    }
+
+    spans.extend([
+        Span::styled("[Q]", Style::default().fg(theme.accent)),
+        Span::styled("uit ", Style::default().fg(theme.muted)),
+        Span::styled("[?]", Style::default().fg(theme.accent)),
+        Span::styled(" keys ", Style::default().fg(theme.muted)),
+        Span::styled("[+/-]", Style::default().fg(theme.accent)),
+        Span::styled(" zoom  ", Style::default().fg(theme.muted)),
         Span::styled(
             format!(
-                "│ {} multipath, {} standalone",
+                "{} {} multipath, {} standalone",
+                glyphs().separator,
                 state.multipath_devices.len(),
                 state.standalone_disks.len()
             ),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ]);
 
-    let footer = Paragraph::new(footer_text);
+    // Bytes moved since startup, and the heaviest writer (runaway writers stand out)
+    if state.session_read_bytes + state.session_write_bytes > 0.0 {
+        spans.push(Span::styled(
+            format!(
+                " {} session R:{} W:{}",
+                glyphs().separator,
+                format_bytes(state.session_read_bytes),
+                format_bytes(state.session_write_bytes)
+            ),
+            Style::default().fg(theme.muted),
+        ));
+        if let Some(top) = state.top_writer() {
+            spans.push(Span::styled(
+                format!(" top W {} {}", top.name, format_bytes(top.write_bytes)),
+                Style::default().fg(theme.muted),
+            ));
+        }
+    }
+
+    let footer = Paragraph::new(Line::from(spans));
     frame.render_widget(footer, area);
 }
 
@@ -227,9 +475,95 @@ fn handle_key_event(key: KeyEvent, state: &Arc<Mutex<AppState>>) -> KeyAction {
             state_guard.quit();
             KeyAction::Quit
         }
-        // Ctrl-L or 'r' to force screen redraw (clears kernel console garbage)
+        // Ctrl-L to force screen redraw (clears kernel console garbage)
         KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyAction::Redraw,
-        KeyCode::Char('r') | KeyCode::Char('R') => KeyAction::Redraw,
+        // 'r' to re-read multipath/ZFS/SES topology now instead of waiting out the cache
+        KeyCode::Char('?') => {
+            state.lock().unwrap().toggle_help_view();
+            KeyAction::None
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            state.lock().unwrap().request_topology_refresh();
+            KeyAction::None
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => {
+            state.lock().unwrap().cycle_drive_view();
+            KeyAction::None
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            state.lock().unwrap().zoom_in();
+            KeyAction::None
+        }
+        KeyCode::Char('-') | KeyCode::Char('_') => {
+            state.lock().unwrap().zoom_out();
+            KeyAction::None
+        }
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            state.lock().unwrap().toggle_cpu_breakdown();
+            KeyAction::None
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            state.lock().unwrap().cycle_drive_sort();
+            KeyAction::None
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            state.lock().unwrap().toggle_density();
+            KeyAction::None
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            state.lock().unwrap().toggle_net_rate();
+            KeyAction::None
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            state.lock().unwrap().toggle_net_pps();
+            KeyAction::None
+        }
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            state.lock().unwrap().toggle_alerts_only();
+            KeyAction::None
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            state.lock().unwrap().cycle_drive_sparkline();
+            KeyAction::None
+        }
+        // 'o' gives the storage section the whole screen, 'O' the system overview
+        KeyCode::Char('o') => {
+            state.lock().unwrap().toggle_maximized(Maximized::Storage);
+            KeyAction::None
+        }
+        KeyCode::Char('O') => {
+            state.lock().unwrap().toggle_maximized(Maximized::System);
+            KeyAction::None
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            state.lock().unwrap().toggle_latency_histogram();
+            KeyAction::None
+        }
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            state.lock().unwrap().toggle_hide_idle();
+            KeyAction::None
+        }
+        KeyCode::Char('z') | KeyCode::Char('Z') => {
+            state.lock().unwrap().toggle_datasets_view();
+            KeyAction::None
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            state.lock().unwrap().toggle_bay_coloring();
+            KeyAction::None
+        }
+        KeyCode::Left => {
+            state.lock().unwrap().move_bay_cursor(-1);
+            KeyAction::None
+        }
+        KeyCode::Right => {
+            state.lock().unwrap().move_bay_cursor(1);
+            KeyAction::None
+        }
+        // 'l' to switch the SES locate LED of the selected bay (needs --allow-control)
+        KeyCode::Char('l') | KeyCode::Char('L') => {
+            state.lock().unwrap().request_locate_toggle();
+            KeyAction::None
+        }
         _ => KeyAction::None,
     }
 }
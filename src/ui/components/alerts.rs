@@ -0,0 +1,159 @@
+use crate::collectors::{ElementStatus, EnclosureHealth, NetworkStats};
+use crate::domain::device::{MultipathDevice, MultipathState, PhysicalDisk};
+use crate::domain::health::{zfs_state_severity, Severity, BUSY_ALERT_PCT, LINK_STATE_DOWN};
+use crate::ui::components::panel_block;
+use crate::ui::glyphs::glyphs;
+use crate::ui::state::Density;
+use crate::ui::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, Paragraph},
+    Frame,
+};
+use std::collections::BTreeMap;
+
+/// One anomaly found in the collected topology
+#[derive(Clone, Debug)]
+pub struct Alert {
+    pub severity: Severity,
+    pub subject: String,
+    pub message: String,
+}
+
+/// Everything that isn't healthy, most severe first
+///
+/// Pool health is inferred from member states, since a pool with every
+/// member ONLINE is itself ONLINE. A multipath device gmultipath still
+/// calls OPTIMAL is flagged when it has fewer than `expected_paths`.
+pub fn find_alerts(
+    multipath_devices: &[MultipathDevice],
+    standalone_disks: &[PhysicalDisk],
+    network_stats: &[NetworkStats],
+    enclosure_health: &[EnclosureHealth],
+    expected_paths: usize,
+) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    // pool -> (members not ONLINE, any of them unusable)
+    let mut pools: BTreeMap<&str, (usize, bool)> = BTreeMap::new();
+
+    for mp in multipath_devices {
+        let problem = match mp.state {
+            MultipathState::Failed => Some((Severity::Critical, "FAILED")),
+            MultipathState::Degraded => Some((Severity::Warning, "DEGRADED")),
+            MultipathState::Optimal | MultipathState::Unknown => None,
+        };
+        if let Some((severity, state)) = problem {
+            alerts.push(Alert {
+                severity,
+                subject: mp.name.clone(),
+                message: format!("multipath {} ({} paths)", state, mp.paths.len()),
+            });
+        } else if mp.lacks_redundancy(expected_paths) {
+            alerts.push(Alert {
+                severity: Severity::Warning,
+                subject: mp.name.clone(),
+                message: format!("no path redundancy ({} of {} paths)", mp.working_paths(), expected_paths),
+            });
+        }
+    }
+
+    let drives = multipath_devices
+        .iter()
+        .map(|mp| (&mp.name, mp.zfs_info.as_ref(), &mp.statistics))
+        .chain(
+            standalone_disks
+                .iter()
+                .map(|disk| (&disk.device_name, disk.zfs_info.as_ref(), &disk.statistics)),
+        );
+    for (name, zfs, stats) in drives {
+        if let Some(zfs) = zfs {
+            if let Some(severity) = zfs_state_severity(&zfs.state) {
+                alerts.push(Alert {
+                    severity,
+                    subject: name.clone(),
+                    message: format!("{} in pool {}", zfs.state, zfs.pool),
+                });
+                let pool = pools.entry(zfs.pool.as_str()).or_default();
+                pool.0 += 1;
+                pool.1 |= severity == Severity::Critical;
+            }
+        }
+        if stats.busy_pct >= BUSY_ALERT_PCT {
+            alerts.push(Alert {
+                severity: Severity::Warning,
+                subject: name.clone(),
+                message: format!("{:.0}% busy", stats.busy_pct.min(100.0)),
+            });
+        }
+    }
+
+    for (pool, (members, unusable)) in pools {
+        alerts.push(Alert {
+            severity: if unusable { Severity::Critical } else { Severity::Warning },
+            subject: format!("pool {}", pool),
+            message: format!("{} member(s) not ONLINE", members),
+        });
+    }
+
+    for iface in network_stats {
+        if iface.link_state == LINK_STATE_DOWN {
+            alerts.push(Alert {
+                severity: Severity::Warning,
+                subject: iface.name.clone(),
+                message: "link down".to_string(),
+            });
+        }
+    }
+
+    for health in enclosure_health {
+        for element in health.elements.iter().filter(|e| e.status.is_fault()) {
+            alerts.push(Alert {
+                severity: match element.status {
+                    ElementStatus::NonCritical => Severity::Warning,
+                    _ => Severity::Critical,
+                },
+                subject: format!("{} element {}", health.enclosure, element.index),
+                message: format!("{:?} {:?}", element.kind, element.status),
+            });
+        }
+    }
+
+    alerts.sort_by_key(|a| a.severity);
+    alerts
+}
+
+/// Problems-only view: one line per alert, or an all-clear message
+pub fn render_alerts(frame: &mut Frame, area: Rect, alerts: &[Alert], density: Density, theme: &Theme) {
+    let title = format!(" Alerts ({}) ", alerts.len());
+    let block = panel_block(title, density, theme);
+
+    if alerts.is_empty() {
+        let paragraph = Paragraph::new(format!("{} No problems detected", glyphs().led_on))
+            .style(Style::default().fg(theme.good))
+            .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = alerts
+        .iter()
+        .map(|alert| {
+            let color = match alert.severity {
+                Severity::Critical => theme.critical,
+                Severity::Warning => theme.warn,
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{} {} ", glyphs().led_on, alert.severity.label()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:<24} ", alert.subject), Style::default().fg(theme.text)),
+                Span::styled(alert.message.clone(), Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).block(block), area);
+}
@@ -0,0 +1,112 @@
+use crate::collectors::DatasetUsage;
+use crate::ui::components::panel_block;
+use crate::ui::glyphs::glyphs;
+use crate::ui::state::Density;
+use crate::ui::theme::Theme;
+use crate::util::format::format_bytes;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Cells in the share-of-pool bar
+const SHARE_BAR_WIDTH: usize = 10;
+
+/// Share of its pool at which a dataset's bar turns warn / critical
+const SHARE_WARN_PCT: f64 = 25.0;
+const SHARE_CRITICAL_PCT: f64 = 50.0;
+
+/// Dataset space view ('z'): filesystems and volumes by used space, largest
+/// first, with their share of the pool and compression ratio
+///
+/// USED includes snapshots and child datasets, so a parent always sorts
+/// above its children; REFER is what the dataset holds itself. The share
+/// is of the pool's usable space (its root dataset's used + available),
+/// which unlike the raw `zpool list` size is net of parity.
+pub fn render_datasets(
+    frame: &mut Frame,
+    area: Rect,
+    datasets: &[DatasetUsage],
+    density: Density,
+    theme: &Theme,
+) {
+    let title = format!(" ZFS datasets ({}) by used space ", datasets.len());
+    let block = panel_block(title, density, theme);
+
+    if datasets.is_empty() {
+        let paragraph = Paragraph::new("No datasets (ZFS not in use, or zfs list failed)")
+            .style(Style::default().fg(theme.muted))
+            .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let mut sorted: Vec<&DatasetUsage> = datasets.iter().collect();
+    sorted.sort_by(|a, b| b.used_bytes.cmp(&a.used_bytes).then_with(|| a.name.cmp(&b.name)));
+
+    let name_width = sorted.iter().map(|d| d.name.chars().count()).max().unwrap_or(0).clamp(4, 48);
+    let header = ListItem::new(Line::styled(
+        format!(
+            "{:<name_width$} {:>8} {:>8} {:>8} {:>6}  {}",
+            "NAME", "USED", "AVAIL", "REFER", "RATIO", "OF POOL"
+        ),
+        Style::default().fg(theme.muted).add_modifier(Modifier::BOLD),
+    ));
+
+    let rows = sorted.into_iter().map(|dataset| {
+        let pool_size = datasets
+            .iter()
+            .find(|d| d.name == dataset.pool())
+            .map(|root| root.used_bytes + root.avail_bytes)
+            .unwrap_or(0);
+        let share = if pool_size > 0 {
+            dataset.used_bytes as f64 / pool_size as f64 * 100.0
+        } else {
+            0.0
+        };
+        let share_color = if share >= SHARE_CRITICAL_PCT {
+            theme.critical
+        } else if share >= SHARE_WARN_PCT {
+            theme.warn
+        } else {
+            theme.good
+        };
+        let filled = ((share / 100.0 * SHARE_BAR_WIDTH as f64).round() as usize).min(SHARE_BAR_WIDTH);
+
+        let mut name = dataset.name.clone();
+        if name.chars().count() > name_width {
+            let keep = name_width.saturating_sub(glyphs().ellipsis.chars().count());
+            name = name.chars().take(keep).collect::<String>() + glyphs().ellipsis;
+        }
+        let ratio = dataset
+            .compress_ratio
+            .map(|r| format!("{:.2}x", r))
+            .unwrap_or_else(|| "-".to_string());
+
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("{:<name_width$} ", name), Style::default().fg(theme.text)),
+            Span::styled(
+                format!(
+                    "{:>8} {:>8} {:>8} {:>6}  ",
+                    format_bytes(dataset.used_bytes as f64),
+                    format_bytes(dataset.avail_bytes as f64),
+                    format_bytes(dataset.refer_bytes as f64),
+                    ratio
+                ),
+                Style::default().fg(theme.text),
+            ),
+            Span::styled(glyphs().bar_full.repeat(filled), Style::default().fg(share_color)),
+            Span::styled(
+                glyphs().bar_free.repeat(SHARE_BAR_WIDTH - filled),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(format!(" {:>4.0}%", share), Style::default().fg(share_color)),
+        ]))
+    });
+
+    let items: Vec<ListItem> = std::iter::once(header).chain(rows).collect();
+    frame.render_widget(List::new(items).block(block), area);
+}
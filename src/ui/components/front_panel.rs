@@ -1,63 +1,94 @@
-use crate::collectors::ZfsRole;
-use crate::domain::device::MultipathDevice;
+use crate::collectors::{ElementKind, ElementStatus, EnclosureHealth, ZfsDriveInfo, ZfsRole, ZilStats};
+use crate::config::{Config, QueueDepthThresholds};
+use crate::domain::controller::{controller_label, controller_rollup};
+use crate::domain::device::{partition_summary, DiskStatistics, MultipathDevice, PhysicalDisk};
+use crate::domain::health::{BUSY_ALERT_PCT, BUSY_WARN_PCT};
+use crate::domain::statistics::{latency_histogram, latest, Percentiles, LATENCY_BUCKET_BOUNDS_MS};
+use crate::domain::vdev::{aggregate, group_by_vdev};
+use crate::ui::components::{chart_points, chart_x_bounds, chart_y_max, panel_block};
+use crate::ui::glyphs::glyphs;
+use crate::ui::options::UiOptions;
+use crate::ui::state::{AppState, BayColoring, Density, DriveSort, DriveSparkline, DriveView, PoolTrend};
+use crate::ui::theme::{ChartSeries, Theme};
+use crate::ui::zoom::Zoom;
+use crate::util::format::{
+    disk_mbps, format_bytes, format_count, format_iops, format_mbps, format_rate, format_span, units,
+};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    symbols::Marker,
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, Paragraph, Sparkline},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Sparkline},
     Frame,
 };
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::time::Duration;
 
 /// Render a front panel view with vertical 2.5" drives and activity LEDs
 pub fn render_front_panel(
     frame: &mut Frame,
     area: Rect,
-    devices: &[MultipathDevice],
-    read_iops_history: &VecDeque<f64>,
-    write_iops_history: &VecDeque<f64>,
-    read_bw_history: &VecDeque<f64>,
-    write_bw_history: &VecDeque<f64>,
-    read_latency_history: &VecDeque<f64>,
-    write_latency_history: &VecDeque<f64>,
-    queue_depth_history: &VecDeque<f64>,
-    busy_history: &VecDeque<f64>,
-    drive_busy_history: &HashMap<String, VecDeque<f64>>,
+    state: &AppState,
+    zoom: Zoom,
+    options: &UiOptions,
+    theme: &Theme,
 ) {
-    let block = Block::default()
-        .title(" Storage Array - EMC2 25-Bay (Vertical 2.5\" SAS) ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let density = state.density;
+    let devices = &state.multipath_devices;
+    let flashing = state.flashing_drives();
+
+    let block = panel_block(format!(" {} ", options.title), density, theme);
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Split horizontally: left (drives + sparklines) and right (per-drive stats full height)
+    // Split horizontally: left (drives + sparklines) and right (per-drive stats full height);
+    // dense mode widens the stats side so the drive list can pack into columns
+    let stats_pct = match density {
+        Density::Comfortable => 35,
+        Density::Dense => 50,
+    };
     let horiz_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(65),  // Left: drives visual + cumulative sparklines
-            Constraint::Percentage(35),  // Right: per-drive stats (narrower)
+            Constraint::Percentage(100 - stats_pct),  // Left: drives visual + cumulative sparklines
+            Constraint::Percentage(stats_pct),        // Right: per-drive stats
         ])
         .split(inner);
 
-    // Split left section vertically: drives (top) and cumulative sparklines (bottom)
+    // Drive bay: 2 outer border + 4 content + 2 drive border = 8 lines;
+    // dense mode drops the outer border and gives the rows to the charts
+    let bay_border: u16 = match density {
+        Density::Comfortable => 2,
+        Density::Dense => 0,
+    };
+    let bay_height = 6 + bay_border;
+
+    // Split left section vertically: drives (top), SLOG sync-write line when
+    // a pool has a log device, and cumulative sparklines (bottom)
+    let slog = slog_line(devices, &state.standalone_disks, state.zil_stats.as_ref(), theme);
+    let enclosure = enclosure_line(&state.enclosure_health, theme);
+    let selected = selected_drive_lines(state, &options.config, theme);
     let left_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(9),   // Drives visual (8) + legend (1)
-            Constraint::Fill(1),     // Cumulative sparklines (fills all remaining space)
+            Constraint::Length(bay_height + 1),                   // Drives visual + legend (1)
+            Constraint::Length(if slog.is_some() { 1 } else { 0 }), // SLOG line
+            Constraint::Length(if enclosure.is_some() { 1 } else { 0 }), // Enclosure health line
+            Constraint::Length(selected.len() as u16),            // Selected bay's drive
+            Constraint::Fill(1),                                  // Cumulative sparklines (fills all remaining space)
         ])
         .split(horiz_chunks[0]);
 
     // Layout drives area with legend
-    // Drive bay: 2 outer border + 4 content + 2 drive border = 8 lines
     let drive_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(8),   // Drive bay with outer border
-            Constraint::Length(1),   // Legend
+            Constraint::Length(bay_height),  // Drive bay with outer border
+            Constraint::Length(1),           // Legend
         ])
         .split(left_chunks[0]);
 
@@ -65,7 +96,7 @@ pub fn render_front_panel(
 
     // Create drive bay with border: 25 drives
     // Each slot is 3 chars wide, total = 75 chars + 2 for outer border = 77 chars
-    let total_bay_width: u16 = 25 * 3 + 2; // 25 slots * 3 chars + 2 border chars
+    let total_bay_width: u16 = 25 * 3 + bay_border;
 
     // Center the drive bay in the available area
     let left_padding = if drive_area.width > total_bay_width {
@@ -85,13 +116,16 @@ pub fn render_front_panel(
 
     // Draw outer border around the drive bay
     let bay_block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .borders(if bay_border > 0 { Borders::ALL } else { Borders::NONE })
+        .border_set(glyphs().border)
+        .border_style(Style::default().fg(theme.muted));
     let bay_inner = bay_block.inner(centered_chunks[1]);
     frame.render_widget(bay_block, centered_chunks[1]);
 
-    // Create 25 columns for drives
-    let constraints: Vec<Constraint> = (0..25)
+    // Create 25 columns for drives; a bay narrower than 75 columns shows the
+    // slots that fit rather than squeezing every drive
+    let visible_slots = (bay_inner.width / 3).min(25);
+    let constraints: Vec<Constraint> = (0..visible_slots)
         .map(|_| Constraint::Length(3))
         .collect();
 
@@ -101,54 +135,299 @@ pub fn render_front_panel(
         .split(bay_inner);
 
     for (slot, col_area) in cols.iter().enumerate() {
-        render_vertical_drive(frame, *col_area, slot, devices);
+        render_vertical_drive(frame, *col_area, slot, state, &flashing, theme);
     }
 
-    // Render legend
-    let legend = Paragraph::new(Line::from(vec![
-        Span::styled("●", Style::default().fg(Color::Green)),
+    // Render legend, followed by the per-controller rollup when paths span
+    // more than one controller (uneven active/active balancing shows up here)
+    let mut legend_spans = vec![
+        Span::styled(glyphs().led_on, Style::default().fg(theme.good)),
         Span::raw(" Rd "),
-        Span::styled("●", Style::default().fg(Color::Yellow)),
+        Span::styled(glyphs().led_on, Style::default().fg(theme.warn)),
         Span::raw(" Wr "),
-        Span::styled("●", Style::default().fg(Color::Magenta)),
+        Span::styled(glyphs().led_on, Style::default().fg(theme.highlight)),
         Span::raw(" R+W "),
-        Span::styled("○", Style::default().fg(Color::DarkGray)),
+        Span::styled(glyphs().led_off, Style::default().fg(theme.muted)),
         Span::raw(" Idle"),
-    ]));
+    ];
+    // Pool coloring: name each pool in its bay color
+    if state.bay_coloring == BayColoring::Pool {
+        let pools: BTreeSet<&str> = devices
+            .iter()
+            .filter_map(|d| d.zfs_info.as_ref().map(|z| z.pool.as_str()))
+            .collect();
+        for pool in pools {
+            legend_spans.push(Span::styled(
+                format!("  {} {}", glyphs().bar_full, pool),
+                Style::default().fg(theme.pool_color(pool)),
+            ));
+        }
+    }
+    if state.synthetic_slots {
+        legend_spans.push(Span::styled(
+            format!("  {} logical slots (no SES)", glyphs().separator),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    let controllers = controller_rollup(devices);
+    if controllers.len() > 1 {
+        for (controller, stats) in &controllers {
+            legend_spans.push(Span::styled(
+                format!("  {} Ctrl {} ", glyphs().separator, controller_label(*controller)),
+                Style::default().fg(theme.text),
+            ));
+            // e.g. "r:729 95MB/s w:355 47MB/s"
+            legend_spans.push(Span::styled(
+                format!(
+                    "r:{} {}{} ",
+                    format_count(stats.read_iops),
+                    format_count(disk_mbps(stats.read_bw_mbps)),
+                    units().mb_label()
+                ),
+                Style::default().fg(theme.good),
+            ));
+            legend_spans.push(Span::styled(
+                format!(
+                    "w:{} {}{} ",
+                    format_count(stats.write_iops),
+                    format_count(disk_mbps(stats.write_bw_mbps)),
+                    units().mb_label()
+                ),
+                Style::default().fg(theme.warn),
+            ));
+            legend_spans.push(Span::styled(
+                format!("{:.0}%", stats.busy_pct),
+                Style::default().fg(busy_color(stats.busy_pct, theme)),
+            ));
+        }
+    }
+    let legend = Paragraph::new(Line::from(legend_spans));
 
     frame.render_widget(legend, drive_chunks[1]);
 
+    if let Some(slog) = slog {
+        frame.render_widget(Paragraph::new(slog), left_chunks[1]);
+    }
+    if let Some(enclosure) = enclosure {
+        frame.render_widget(Paragraph::new(enclosure), left_chunks[2]);
+    }
+    if !selected.is_empty() {
+        frame.render_widget(Paragraph::new(selected), left_chunks[3]);
+    }
+
     // Render cumulative sparklines below drives
-    render_storage_charts(
-        frame,
-        left_chunks[1],
-        read_iops_history,
-        write_iops_history,
-        read_bw_history,
-        write_bw_history,
-        read_latency_history,
-        write_latency_history,
-        queue_depth_history,
-        busy_history,
-    );
+    render_storage_charts(frame, left_chunks[4], state, options.chart_axis_labels, zoom, theme);
 
     // Render per-drive stats panel on right side (full height)
-    render_drive_stats(frame, horiz_chunks[1], devices, drive_busy_history);
+    render_drive_stats(frame, horiz_chunks[1], state, &flashing, &options.config, theme);
+}
+
+/// Sync-write activity of the SLOG devices, plus where ZIL writes went
+///
+/// A log device only ever sees ZIL writes, so its write IOPS and latency are
+/// the sync-write path applications wait on. None if no pool has a SLOG.
+fn slog_line(
+    devices: &[MultipathDevice],
+    standalone_disks: &[PhysicalDisk],
+    zil_stats: Option<&ZilStats>,
+    theme: &Theme,
+) -> Option<Line<'static>> {
+    let is_slog = |zfs: &Option<ZfsDriveInfo>| zfs.as_ref().is_some_and(|z| z.role == ZfsRole::Slog);
+    let slogs: Vec<(&str, &DiskStatistics)> = devices
+        .iter()
+        .filter(|d| is_slog(&d.zfs_info))
+        .map(|d| (d.name.as_str(), &d.statistics))
+        .chain(
+            standalone_disks
+                .iter()
+                .filter(|d| is_slog(&d.zfs_info))
+                .map(|d| (d.device_name.as_str(), &d.statistics)),
+        )
+        .collect();
+    if slogs.is_empty() {
+        return None;
+    }
+
+    let mut spans = vec![Span::styled("SLOG ", Style::default().fg(theme.accent))];
+    for (name, stats) in slogs {
+        let latency_color = if stats.write_iops < 0.1 {
+            theme.muted
+        } else if stats.write_latency_ms < 1.0 {
+            theme.good
+        } else if stats.write_latency_ms < 5.0 {
+            theme.warn
+        } else {
+            theme.critical
+        };
+        spans.push(Span::styled(format!("{} ", name), Style::default().fg(theme.text)));
+        spans.push(Span::styled(
+            format!("w:{} ", format_count(stats.write_iops)),
+            Style::default().fg(theme.warn),
+        ));
+        spans.push(Span::styled(
+            format!("{:.2}ms  ", stats.write_latency_ms),
+            Style::default().fg(latency_color),
+        ));
+    }
+
+    if let Some(zil) = zil_stats {
+        spans.push(Span::styled(
+            format!("{} ZIL commits:{}/s", glyphs().separator, format_count(zil.commits_per_sec)),
+            Style::default().fg(theme.muted),
+        ));
+        if let Some(pct) = zil.slog_pct() {
+            // Log writes landing on the main vdevs bypass the SLOG entirely
+            let color = if pct < 90.0 { theme.warn } else { theme.muted };
+            spans.push(Span::styled(
+                format!(" on slog:{:.0}% {}", pct, format_rate(zil.slog_mbps * 1_000_000.0)),
+                Style::default().fg(color),
+            ));
+        }
+    }
+
+    Some(Line::from(spans))
+}
+
+/// Tail latency and busy% of the drive in the bay under the cursor, over
+/// its retained history, e.g. "SEL 05 multipath/A1 R p50/95/99:0.4/1.2/3.1ms
+/// W ... busy p50/95/99:12/40/77%", and a second line with the bytes it
+/// has moved since startup and its partitions; empty without a selected
+/// drive. An aliased drive is named "tank-d3 (multipath/A1)", so the real
+/// name stays in view.
+fn selected_drive_lines(state: &AppState, config: &Config, theme: &Theme) -> Vec<Line<'static>> {
+    let Some(drive) = state.selected_drive() else {
+        return Vec::new();
+    };
+    let mut spans = vec![
+        Span::styled("SEL ", Style::default().fg(theme.accent)),
+        Span::styled(format!("{:02} ", drive.slot.unwrap_or(0)), Style::default().fg(theme.text)),
+    ];
+    match config.alias(drive.ident.as_deref(), &drive.name) {
+        Some(alias) => {
+            spans.push(Span::styled(format!("{} ", alias), Style::default().fg(theme.text)));
+            spans.push(Span::styled(format!("({}) ", drive.name), Style::default().fg(theme.muted)));
+        }
+        None => spans.push(Span::styled(format!("{} ", drive.name), Style::default().fg(theme.text))),
+    }
+
+    for (name, histories) in [
+        ("R", &state.drive_read_latency_history),
+        ("W", &state.drive_write_latency_history),
+    ] {
+        if let Some(p) = histories.get(&drive.name).and_then(Percentiles::of_latency) {
+            spans.push(Span::styled(
+                format!(" {} p50/95/99:{:.1}/{:.1}/{:.1}ms", name, p.p50, p.p95, p.p99),
+                Style::default().fg(theme.muted),
+            ));
+        }
+    }
+    if let Some(p) = state
+        .drive_busy_history
+        .get(&drive.name)
+        .and_then(|history| Percentiles::of(history.iter().copied()))
+    {
+        spans.push(Span::styled(
+            format!(" busy p50/95/99:{:.0}/{:.0}/{:.0}%", p.p50, p.p95, p.p99),
+            Style::default().fg(busy_color(p.p95, theme)),
+        ));
+    }
+
+    let mut lines = vec![Line::from(spans)];
+    let mut details = Vec::new();
+    if let Some(totals) = state.drive_totals_for(drive) {
+        details.push(format!(
+            "since start R:{} W:{}",
+            format_bytes(totals.read_bytes),
+            format_bytes(totals.write_bytes)
+        ));
+    }
+    if !drive.partitions.is_empty() {
+        details.push(format!("{} {} {}", drive.name, glyphs().arrow, partition_summary(&drive.partitions)));
+    }
+    if !details.is_empty() {
+        lines.push(Line::styled(
+            format!("    {}", details.join(&format!(" {} ", glyphs().separator))),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    lines
+}
+
+/// PSU, fan and temperature summary per enclosure, e.g.
+/// "ENC ses0 PSU 2/2 fan 4/4 5.1k rpm 31C"; None without SES health data
+fn enclosure_line(enclosures: &[EnclosureHealth], theme: &Theme) -> Option<Line<'static>> {
+    let enclosures: Vec<&EnclosureHealth> = enclosures.iter().filter(|e| !e.elements.is_empty()).collect();
+    if enclosures.is_empty() {
+        return None;
+    }
+
+    let status_color = |status: ElementStatus| match status {
+        ElementStatus::Critical | ElementStatus::Unrecoverable => theme.critical,
+        ElementStatus::NonCritical => theme.warn,
+        _ => theme.good,
+    };
+
+    let mut spans = vec![Span::styled("ENC ", Style::default().fg(theme.accent))];
+    for health in enclosures {
+        spans.push(Span::styled(format!("{} ", health.enclosure), Style::default().fg(theme.text)));
+
+        // "ok/installed" per kind, colored by the worst element of that kind
+        for (kind, label) in [(ElementKind::PowerSupply, "PSU"), (ElementKind::Fan, "fan")] {
+            let installed: Vec<_> = health
+                .of_kind(kind)
+                .filter(|e| e.status != ElementStatus::NotInstalled)
+                .collect();
+            if installed.is_empty() {
+                continue;
+            }
+            let ok = installed.iter().filter(|e| !e.status.is_fault()).count();
+            let worst = installed.iter().map(|e| e.status).max().unwrap_or(ElementStatus::Ok);
+            spans.push(Span::styled(
+                format!("{} {}/{} ", label, ok, installed.len()),
+                Style::default().fg(status_color(worst)),
+            ));
+        }
+
+        let rpm = health
+            .of_kind(ElementKind::Fan)
+            .filter_map(|e| e.reading)
+            .filter(|r| *r > 0.0)
+            .fold(None, |min: Option<f64>, r| Some(min.map_or(r, |m| m.min(r))));
+        if let Some(rpm) = rpm {
+            spans.push(Span::styled(format!("{} rpm ", format_count(rpm)), Style::default().fg(theme.muted)));
+        }
+
+        let hottest = health
+            .of_kind(ElementKind::Temperature)
+            .filter(|e| e.reading.is_some())
+            .max_by(|a, b| a.reading.unwrap_or(0.0).total_cmp(&b.reading.unwrap_or(0.0)));
+        if let Some(sensor) = hottest {
+            spans.push(Span::styled(
+                format!("{:.0}C ", sensor.reading.unwrap_or(0.0)),
+                Style::default().fg(status_color(sensor.status)),
+            ));
+        }
+        spans.push(Span::styled(format!("{} ", glyphs().separator), Style::default().fg(theme.muted)));
+    }
+    spans.pop();
+
+    Some(Line::from(spans))
 }
 
 fn render_storage_charts(
     frame: &mut Frame,
     area: Rect,
-    read_iops_history: &VecDeque<f64>,
-    write_iops_history: &VecDeque<f64>,
-    read_bw_history: &VecDeque<f64>,
-    write_bw_history: &VecDeque<f64>,
-    read_latency_history: &VecDeque<f64>,
-    write_latency_history: &VecDeque<f64>,
-    queue_depth_history: &VecDeque<f64>,
-    _busy_history: &VecDeque<f64>,
+    state: &AppState,
+    axis_labels: bool,
+    zoom: Zoom,
+    theme: &Theme,
 ) {
-    // Split into 4 equal rows for different metrics
+    let read_iops_history = &state.storage_read_iops_history;
+    let write_iops_history = &state.storage_write_iops_history;
+    let read_latency_history = &state.storage_read_latency_history;
+    let write_latency_history = &state.storage_write_latency_history;
+
+    // Split into 5 equal rows for different metrics
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -156,15 +435,16 @@ fn render_storage_charts(
             Constraint::Fill(1),
             Constraint::Fill(1),
             Constraint::Fill(1),
+            Constraint::Fill(1),
         ])
         .split(area);
 
-    // Helper to render a chart with label on separate line above
+    // Helper to render a chart with label on separate line above; each
+    // series is drawn as its own line, scaled together
     let render_chart = |frame: &mut Frame,
                         chunk: Rect,
-                        history: &VecDeque<f64>,
-                        label: String,
-                        color: Color| {
+                        series: &[(&VecDeque<f64>, Color)],
+                        label: String| {
         if chunk.height < 2 {
             return;
         }
@@ -178,56 +458,71 @@ fn render_storage_charts(
             ])
             .split(chunk);
 
-        // Render label
+        // Use chart width to determine how many points to display
+        // Each braille character is 2 dots wide, so we can fit width * 2 points
+        let chart_width = sub_chunks[1].width as usize;
+        let max_points = chart_width * 2;
+
+        // Render label with the time window the chart covers
+        let label = format!("{} [{}]", label, zoom.window_label(max_points));
         let label_widget = Paragraph::new(label)
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(theme.text));
         frame.render_widget(label_widget, sub_chunks[0]);
 
         // Render chart if we have space
-        if sub_chunks[1].height < 1 || history.is_empty() {
+        if sub_chunks[1].height < 1 || series.iter().all(|(history, _)| history.is_empty()) {
             return;
         }
 
-        // Use chart width to determine how many points to display
-        // Each braille character is 2 dots wide, so we can fit width * 2 points
-        let chart_width = sub_chunks[1].width as usize;
-        let max_points = chart_width * 2;
-
-        // Take the most recent points (history is pre-filled so always has enough)
-        let start = history.len().saturating_sub(max_points);
-        let data: Vec<(f64, f64)> = history
+        // Take the most recent points, averaged down when zoomed out; a
+        // short history fills in from the right of a fixed-width window
+        let data: Vec<Vec<(f64, f64)>> = series
             .iter()
-            .skip(start)
-            .enumerate()
-            .map(|(i, &v)| (i as f64, v))
+            .map(|(history, _)| chart_points(zoom.tail(history, max_points), max_points))
             .collect();
 
-        // Find max Y value for scaling
-        let max_y = history.iter().cloned().fold(1.0_f64, f64::max) * 1.1;
+        // Y scale from the visible window only
+        let max_y = chart_y_max(data.iter().flatten().map(|&(_, v)| v), 1.0);
+
+        let datasets: Vec<Dataset> = data
+            .iter()
+            .zip(series)
+            .map(|(points, &(_, color))| {
+                // A line needs two points; the very first sample is drawn as a dot
+                let graph_type = if points.len() > 1 { GraphType::Line } else { GraphType::Scatter };
+                Dataset::default()
+                    .marker(glyphs().chart_marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(color))
+                    .data(points)
+            })
+            .collect();
 
-        let dataset = Dataset::default()
-            .marker(Marker::Braille)
-            .graph_type(ratatui::widgets::GraphType::Line)
-            .style(Style::default().fg(color))
-            .data(&data);
+        // Y scale labels (0..max) unless the minimal look was requested
+        let mut y_axis = Axis::default()
+            .bounds([0.0, max_y]);
+        if axis_labels {
+            y_axis = y_axis
+                .labels(vec![
+                    Span::styled("0", Style::default().fg(theme.muted)),
+                    Span::styled(format_count(max_y), Style::default().fg(theme.muted)),
+                ])
+                .style(Style::default().fg(theme.muted));
+        }
 
-        // X bounds match actual data length
-        let x_max = (data.len().saturating_sub(1)) as f64;
-        let chart = Chart::new(vec![dataset])
+        let chart = Chart::new(datasets)
             .x_axis(
                 Axis::default()
-                    .bounds([0.0, x_max.max(1.0)])
-            )
-            .y_axis(
-                Axis::default()
-                    .bounds([0.0, max_y.max(1.0)])
+                    .bounds(chart_x_bounds(max_points))
             )
+            .y_axis(y_axis)
             .hidden_legend_constraints((Constraint::Ratio(0, 1), Constraint::Ratio(0, 1)));
 
         frame.render_widget(chart, sub_chunks[1]);
     };
 
-    // Helper to combine two histories into total
+    // Helper to combine two histories into total (same length, written
+    // together, so a gap in one is a gap in both)
     let combine_histories = |h1: &VecDeque<f64>, h2: &VecDeque<f64>| -> VecDeque<f64> {
         let len = h1.len().max(h2.len());
         let mut combined = VecDeque::with_capacity(len);
@@ -239,71 +534,314 @@ fn render_storage_charts(
         combined
     };
 
-    // IOPS (combined read + write)
-    let total_iops = combine_histories(read_iops_history, write_iops_history);
-    let cur_read_iops = read_iops_history.back().unwrap_or(&0.0);
-    let cur_write_iops = write_iops_history.back().unwrap_or(&0.0);
+    // IOPS as separate read and write lines, so a workload flipping from
+    // read-heavy to write-heavy shows as the lines crossing
+    let cur_read_iops = latest(read_iops_history);
+    let cur_write_iops = latest(write_iops_history);
     let iops_label = format!("IOPS: R:{:.0} W:{:.0} T:{:.0}", cur_read_iops, cur_write_iops, cur_read_iops + cur_write_iops);
-    render_chart(frame, chunks[0], &total_iops, iops_label, Color::Cyan);
+    render_chart(
+        frame,
+        chunks[0],
+        &[
+            (read_iops_history, theme.chart(ChartSeries::ReadIops)),
+            (write_iops_history, theme.chart(ChartSeries::WriteIops)),
+        ],
+        iops_label,
+    );
 
     // Throughput (combined read + write)
-    let total_bw = combine_histories(read_bw_history, write_bw_history);
-    let cur_read_bw = read_bw_history.back().unwrap_or(&0.0);
-    let cur_write_bw = write_bw_history.back().unwrap_or(&0.0);
-    let bw_label = format!("MB/s: R:{:.1} W:{:.1} T:{:.1}", cur_read_bw, cur_write_bw, cur_read_bw + cur_write_bw);
-    render_chart(frame, chunks[1], &total_bw, bw_label, Color::Green);
-
-    // Latency (show max of read/write for worst-case view)
-    let max_latency: VecDeque<f64> = read_latency_history.iter()
-        .zip(write_latency_history.iter())
-        .map(|(r, w)| r.max(*w))
+    let total_bw: VecDeque<f64> = combine_histories(&state.storage_read_bw_history, &state.storage_write_bw_history)
+        .into_iter()
+        .map(disk_mbps)
         .collect();
-    let cur_read_lat = read_latency_history.back().unwrap_or(&0.0);
-    let cur_write_lat = write_latency_history.back().unwrap_or(&0.0);
-    let lat_label = format!("Latency(ms): R:{:.1} W:{:.1}", cur_read_lat, cur_write_lat);
-    render_chart(frame, chunks[2], &max_latency, lat_label, Color::Yellow);
+    let cur_read_bw = disk_mbps(latest(&state.storage_read_bw_history));
+    let cur_write_bw = disk_mbps(latest(&state.storage_write_bw_history));
+    let bw_label = format!(
+        "{}: R:{:.1} W:{:.1} T:{:.1}",
+        units().mb_label(),
+        cur_read_bw,
+        cur_write_bw,
+        cur_read_bw + cur_write_bw
+    );
+    render_chart(frame, chunks[1], &[(&total_bw, theme.chart(ChartSeries::Throughput))], bw_label);
+
+    // Latency (show max of read/write for worst-case view), or its
+    // distribution when toggled with 'h'
+    if state.latency_histogram {
+        render_latency_histogram(frame, chunks[2], read_latency_history, write_latency_history, zoom, theme);
+    } else {
+        let max_latency: VecDeque<f64> = read_latency_history.iter()
+            .zip(write_latency_history.iter())
+            .map(|(r, w)| r.max(*w))
+            .collect();
+        let cur_read_lat = latest(read_latency_history);
+        let cur_write_lat = latest(write_latency_history);
+        let mut lat_label = format!("Latency(ms): R:{:.1} W:{:.1}", cur_read_lat, cur_write_lat);
+
+        // Tail latency over the whole retained history, not just the visible window
+        for (name, history) in [("R", read_latency_history), ("W", write_latency_history)] {
+            if let Some(p) = Percentiles::of_latency(history) {
+                lat_label.push_str(&format!("  {} p50/95/99:{:.1}/{:.1}/{:.1}", name, p.p50, p.p95, p.p99));
+            }
+        }
+        render_chart(frame, chunks[2], &[(&max_latency, theme.chart(ChartSeries::Latency))], lat_label);
+    }
 
     // Queue depth
-    let cur_qd = queue_depth_history.back().unwrap_or(&0.0);
+    let cur_qd = latest(&state.storage_queue_depth_history);
     let qd_label = format!("Queue Depth: {:.0}", cur_qd);
-    render_chart(frame, chunks[3], queue_depth_history, qd_label, Color::Magenta);
+    render_chart(frame, chunks[3], &[(&state.storage_queue_depth_history, theme.chart(ChartSeries::QueueDepth))], qd_label);
+
+    // Busy: the busiest device against the mean, since one saturated drive
+    // in a wide array barely moves the mean
+    let mut busy_label = format!(
+        "Busy%: max:{:.0} mean:{:.0} >={:.0}%:{:.0}",
+        latest(&state.storage_max_busy_history),
+        latest(&state.storage_busy_history),
+        BUSY_ALERT_PCT,
+        latest(&state.storage_hot_drives_history)
+    );
+    // Tail of the busiest drive over the whole retained history
+    if let Some(p) = Percentiles::of(state.storage_max_busy_history.iter().copied()) {
+        busy_label.push_str(&format!("  max p50/95/99:{:.0}/{:.0}/{:.0}", p.p50, p.p95, p.p99));
+    }
+    render_chart(
+        frame,
+        chunks[4],
+        &[
+            (&state.storage_max_busy_history, theme.chart(ChartSeries::BusyMax)),
+            (&state.storage_busy_history, theme.chart(ChartSeries::BusyMean)),
+        ],
+        busy_label,
+    );
+}
+
+/// Latency distribution over the whole retained history ('h'): a bar strip
+/// each for reads and writes, one bar per doubling bucket, with the bucket
+/// bounds underneath
+///
+/// Each strip is scaled to its own fullest bucket, since it's the shape
+/// (one mode or two, and where) that matters rather than the sample counts.
+fn render_latency_histogram(
+    frame: &mut Frame,
+    area: Rect,
+    read_latency_history: &VecDeque<f64>,
+    write_latency_history: &VecDeque<f64>,
+    zoom: Zoom,
+    theme: &Theme,
+) {
+    if area.height == 0 {
+        return;
+    }
+    let window = Zoom::new(1, zoom.sample_ms).window_label(read_latency_history.len());
+    let mut lines = vec![Line::styled(
+        format!("Latency(ms) histogram R/W [{}]", window),
+        Style::default().fg(theme.text),
+    )];
+
+    // Label line and bucket bounds line around the two strips
+    let strip_rows = (area.height as usize).saturating_sub(2) / 2;
+    let buckets = LATENCY_BUCKET_BOUNDS_MS.len() + 1;
+    let cell = (area.width.saturating_sub(2) as usize / buckets).max(1);
+    let bar_width = if cell > 1 { cell - 1 } else { 1 };
+
+    if strip_rows > 0 {
+        let levels = &glyphs().sparkline;
+        for (name, history, color) in [
+            ("R", read_latency_history, theme.good),
+            ("W", write_latency_history, theme.warn),
+        ] {
+            let counts = latency_histogram(history);
+            let max = counts.iter().copied().max().unwrap_or(0).max(1);
+            // Bar heights in eighths of a row; a bucket with any samples shows
+            let heights: Vec<usize> = counts
+                .iter()
+                .map(|&n| if n == 0 { 0 } else { (n * strip_rows * 8).div_ceil(max) })
+                .collect();
+            for row in 0..strip_rows {
+                let floor = (strip_rows - 1 - row) * 8;
+                let mut text = String::from(if row == strip_rows - 1 { name } else { " " });
+                text.push(' ');
+                for &height in &heights {
+                    let symbol = match height.saturating_sub(floor).min(8) {
+                        0 => levels.empty,
+                        1 => levels.one_eighth,
+                        2 => levels.one_quarter,
+                        3 => levels.three_eighths,
+                        4 => levels.half,
+                        5 => levels.five_eighths,
+                        6 => levels.three_quarters,
+                        7 => levels.seven_eighths,
+                        _ => levels.full,
+                    };
+                    text.push_str(&symbol.repeat(bar_width));
+                    text.push_str(&" ".repeat(cell - bar_width));
+                }
+                lines.push(Line::styled(text, Style::default().fg(color)));
+            }
+        }
+
+        let mut bounds = String::from("  ");
+        for i in 0..buckets {
+            let label = match LATENCY_BUCKET_BOUNDS_MS.get(i) {
+                Some(bound) if *bound < 1.0 => format!("{}", bound).trim_start_matches('0').to_string(),
+                Some(bound) => format!("{}", bound),
+                None => "more".to_string(),
+            };
+            let label: String = label.chars().take(cell).collect();
+            bounds.push_str(&format!("{:<cell$}", label));
+        }
+        lines.push(Line::styled(bounds, Style::default().fg(theme.muted)));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// A row in the per-drive stats panel: either a multipath device or a standalone
+/// disk used directly by a pool (e.g. a SLOG/L2ARC without gmultipath)
+struct DriveRow<'a> {
+    name: &'a str,
+    device: Option<&'a str>,              // daN behind the row (active path for multipath)
+    alias: Option<&'a str>,               // Operator's name from the config, shown instead of `device`
+    enclosure: Option<&'a str>,
+    slot: Option<usize>,
+    zfs_info: Option<&'a ZfsDriveInfo>,
+    statistics: &'a DiskStatistics,
+    lacks_redundancy: bool,               // Multipath device below the expected path count
+    flashing: bool,                       // ZFS role or state changed moments ago
 }
 
 fn render_drive_stats(
     frame: &mut Frame,
     area: Rect,
-    devices: &[MultipathDevice],
-    drive_busy_history: &HashMap<String, VecDeque<f64>>,
+    state: &AppState,
+    flashing: &HashSet<String>,
+    config: &Config,
+    theme: &Theme,
 ) {
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let devices = &state.multipath_devices;
+    let standalone_disks = &state.standalone_disks;
+    let density = state.density;
+    let drive_sort = state.drive_sort;
+    let drive_sparkline = state.drive_sparkline;
+    let synthetic_slots = state.synthetic_slots;
+
+    let rows: Vec<DriveRow> = devices
+        .iter()
+        .map(|d| DriveRow {
+            name: &d.name,
+            device: d.active_path.as_deref().or(d.paths.first().map(String::as_str)),
+            alias: config.alias(d.ident.as_deref(), &d.name),
+            enclosure: d.enclosure.as_deref(),
+            slot: d.slot,
+            zfs_info: d.zfs_info.as_ref(),
+            statistics: &d.statistics,
+            lacks_redundancy: d.lacks_redundancy(config.multipath.expected_paths),
+            flashing: flashing.contains(&d.name),
+        })
+        .chain(standalone_disks.iter().filter(|d| d.zfs_info.is_some()).map(|d| DriveRow {
+            name: &d.device_name,
+            device: Some(&d.device_name),
+            alias: config.alias(d.ident.as_deref(), &d.device_name),
+            enclosure: d.enclosure.as_deref(),
+            slot: d.slot,
+            zfs_info: d.zfs_info.as_ref(),
+            statistics: &d.statistics,
+            lacks_redundancy: false,
+            flashing: flashing.contains(&d.device_name),
+        }))
+        .collect();
+
+    // 'f' leaves idle drives out of the flat list (the pool tree keeps
+    // every member so its vdevs stay whole)
+    let drive_count = rows.len();
+    let hide_idle = state.hide_idle && state.drive_view == DriveView::Flat;
+    let min_activity = config.idle_drives.min_activity;
+    let rows: Vec<DriveRow> = rows
+        .into_iter()
+        .filter(|row| !hide_idle || row.statistics.is_active(min_activity))
+        .collect();
+
     // Just use left border as separator (main panel provides outer border)
-    let block = Block::default()
-        .title(format!(" Drives ({}) ", devices.len()))
+    let mut block = Block::default()
         .borders(Borders::LEFT)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_set(glyphs().border)
+        .border_style(Style::default().fg(theme.muted));
+    if density == Density::Comfortable {
+        let count = if hide_idle {
+            format!("{} of {} active", rows.len(), drive_count)
+        } else {
+            drive_count.to_string()
+        };
+        let mut title = match drive_sort {
+            DriveSort::Slot => format!(" Drives ({}) ", count),
+            sort => format!(" Drives ({}) by {} ", count, sort.label()),
+        };
+
+        // Slot numbers are per enclosure, so name the enclosures when there are several
+        let mut enclosures: Vec<&str> = rows.iter().filter_map(|r| r.enclosure).collect();
+        enclosures.sort();
+        enclosures.dedup();
+        if enclosures.len() > 1 {
+            title.push_str(&format!("[{}] ", enclosures.join(",")));
+        }
+        block = block.title(title);
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    if devices.is_empty() {
-        let placeholder = Paragraph::new("No drives detected")
-            .style(Style::default().fg(Color::DarkGray));
+    if rows.is_empty() {
+        let message = if drive_count > 0 {
+            "All drives idle ([F] shows them)"
+        } else {
+            "No drives detected"
+        };
+        let placeholder = Paragraph::new(message)
+            .style(Style::default().fg(theme.muted));
         frame.render_widget(placeholder, inner);
         return;
     }
 
-    // Sort devices by physical SES slot (if available), otherwise by name
-    let mut sorted_devices: Vec<&MultipathDevice> = devices.iter().collect();
+    if state.drive_view != DriveView::Flat {
+        render_vdev_tree(frame, inner, state, config, theme);
+        return;
+    }
+
+    // Rows stay where they first appeared (slot order at startup, later
+    // arrivals at the end) so the list doesn't reshuffle during failover;
+    // slot then name only orders drives the state hasn't placed yet
+    let position = |row: &DriveRow| state.drive_positions.get(row.name).copied().unwrap_or(usize::MAX);
+    let mut sorted_devices: Vec<&DriveRow> = rows.iter().collect();
     sorted_devices.sort_by(|a, b| {
-        match (a.slot, b.slot) {
+        position(a).cmp(&position(b)).then_with(|| match (a.slot, b.slot) {
             (Some(slot_a), Some(slot_b)) => slot_a.cmp(&slot_b),
             (Some(_), None) => std::cmp::Ordering::Less,
             (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => a.name.cmp(&b.name),
-        }
+            (None, None) => a.name.cmp(b.name),
+        })
     });
 
+    // Activity sorts put the busiest first; the stable sort keeps slot order among ties
+    let activity = |row: &DriveRow| -> f64 {
+        let stats = row.statistics;
+        match drive_sort {
+            DriveSort::Slot => 0.0,
+            DriveSort::Busy => stats.busy_pct,
+            DriveSort::Iops => stats.total_iops(),
+            DriveSort::Bandwidth => stats.total_bw_mbps(),
+            DriveSort::Latency => stats.read_latency_ms.max(stats.write_latency_ms),
+        }
+    };
+    if drive_sort != DriveSort::Slot {
+        sorted_devices.sort_by(|a, b| activity(b).total_cmp(&activity(a)));
+    }
+
     // Create display list with physical slot numbers
-    let slot_devices: Vec<(usize, &MultipathDevice)> = sorted_devices
+    let slot_devices: Vec<(usize, &DriveRow)> = sorted_devices
         .iter()
         .map(|&dev| {
             let display_slot = dev.slot.unwrap_or(0);
@@ -312,8 +850,10 @@ fn render_drive_stats(
         .collect();
 
     // Column widths - expanded layout with more ZFS info
-    // SL POOL ROLE  VDEV S  IOPS MB/s BSY [sparkline]
+    // SL [DEV] POOL ROLE  VDEV S  IOPS MB/s BSY  QD ERR [sparkline]
     const SLOT_W: usize = 2;
+    const DEV_W: usize = 6;
+    const ALIAS_W: usize = 12;
     const POOL_W: usize = 4;
     const ROLE_W: usize = 5;
     const VDEV_W: usize = 4;
@@ -321,8 +861,41 @@ fn render_drive_stats(
     const IOPS_W: usize = 5;
     const BW_W: usize = 5;
     const BUSY_W: usize = 3;
-    // Total: 2+1+4+1+5+1+4+1+1+1+5+1+5+1+3+1 = 37 chars before sparkline
-    const FIXED_PREFIX: u16 = (SLOT_W + 1 + POOL_W + 1 + ROLE_W + 1 + VDEV_W + 1 + STATE_W + 1 + IOPS_W + 1 + BW_W + 1 + BUSY_W + 1) as u16;
+    const QD_W: usize = 3;
+    const ERR_W: usize = 3;
+    // Total: 2+1+4+1+5+1+4+1+1+1+5+1+5+1+3+1+3+1+3+1 = 45 chars before sparkline
+    const FIXED_PREFIX: u16 = (SLOT_W + 1 + POOL_W + 1 + ROLE_W + 1 + VDEV_W + 1 + STATE_W + 1 + IOPS_W + 1 + BW_W + 1 + BUSY_W + 1 + QD_W + 1 + ERR_W + 1) as u16;
+    // Narrowest sparkline worth keeping when dense mode packs columns side by side
+    const DENSE_MIN_SPARKLINE: u16 = 10;
+    // Longest slot annotation tag shown after the sparkline
+    const NOTE_W: usize = 16;
+
+    // Dense mode fills the width with as many drive columns as fit
+    let columns = match density {
+        Density::Comfortable => 1,
+        Density::Dense => (inner.width / (FIXED_PREFIX + DENSE_MIN_SPARKLINE + 1)).max(1),
+    };
+    let column_width = inner.width / columns;
+    let line_width = if columns > 1 { column_width - 1 } else { column_width };
+
+    // Device column (daN, or the drive's alias, widened to fit the longest)
+    // only when it doesn't squeeze the sparkline out; aliases are how the
+    // operator knows the drives, so with any alias it is always shown
+    let dev_width = slot_devices
+        .iter()
+        .filter_map(|(_, dev)| dev.alias.map(|alias| alias.chars().count()))
+        .fold(DEV_W, usize::max)
+        .min(ALIAS_W);
+    let any_alias = slot_devices.iter().any(|(_, dev)| dev.alias.is_some());
+    let show_dev = any_alias || line_width >= FIXED_PREFIX + dev_width as u16 + 1 + DENSE_MIN_SPARKLINE;
+    let prefix_width = if show_dev { FIXED_PREFIX + dev_width as u16 + 1 } else { FIXED_PREFIX };
+
+    // Read/write traces use the same colors as the aggregate charts
+    let sparkline_color = match drive_sparkline {
+        DriveSparkline::Busy => theme.accent,
+        DriveSparkline::ReadIops => theme.chart(ChartSeries::ReadIops),
+        DriveSparkline::WriteIops => theme.chart(ChartSeries::WriteIops),
+    };
 
     // Render header if we have space
     let available_height = inner.height as usize;
@@ -330,44 +903,60 @@ fn render_drive_stats(
     let header_offset: u16 = if show_header { 1 } else { 0 };
 
     if show_header {
-        let header_area = Rect {
-            x: inner.x,
-            y: inner.y,
-            width: inner.width,
-            height: 1,
-        };
-        let header = Line::from(vec![
-            Span::styled(format!("{:<SLOT_W$}", "SL"), Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled(format!("{:<POOL_W$}", "POOL"), Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled(format!("{:<ROLE_W$}", "ROLE"), Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled(format!("{:<VDEV_W$}", "VDEV"), Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled("S", Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled(format!("{:>IOPS_W$}", "IOPS"), Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled(format!("{:>BW_W$}", "MB/s"), Style::default().fg(Color::DarkGray)),
-            Span::raw(" "),
-            Span::styled(format!("{:>BUSY_W$}", "BSY"), Style::default().fg(Color::DarkGray)),
-        ]);
-        frame.render_widget(Paragraph::new(header), header_area);
+        for column in 0..columns {
+            let header_area = Rect {
+                x: inner.x + column * column_width,
+                y: inner.y,
+                width: line_width,
+                height: 1,
+            };
+            let mut header_spans = vec![
+                Span::styled(format!("{:<SLOT_W$}", "SL"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+            ];
+            if show_dev {
+                header_spans.push(Span::styled(format!("{:<dev_width$}", "DEV"), Style::default().fg(theme.muted)));
+                header_spans.push(Span::raw(" "));
+            }
+            header_spans.extend([
+                Span::styled(format!("{:<POOL_W$}", "POOL"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:<ROLE_W$}", "ROLE"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:<VDEV_W$}", "VDEV"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled("S", Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:>IOPS_W$}", "IOPS"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:>BW_W$}", units().mb_label()), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:>BUSY_W$}", "BSY"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:>QD_W$}", "QD"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(format!("{:>ERR_W$}", "ERR"), Style::default().fg(theme.muted)),
+                Span::raw(" "),
+                Span::styled(drive_sparkline.label(), Style::default().fg(sparkline_color)),
+            ]);
+            frame.render_widget(Paragraph::new(Line::from(header_spans)), header_area);
+        }
     }
 
-    let drives_to_show = (available_height - header_offset as usize).min(slot_devices.len());
+    let rows_per_column = available_height - header_offset as usize;
+    let drives_to_show = (rows_per_column * columns as usize).min(slot_devices.len());
+    let flash_on = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .is_ok_and(|now| (now.as_millis() / 250).is_multiple_of(2));
 
     for (idx, (slot, dev)) in slot_devices.iter().take(drives_to_show).enumerate() {
-        let y_pos = inner.y + header_offset + idx as u16;
-        if y_pos >= inner.y + inner.height {
-            break;
-        }
+        let column = (idx / rows_per_column) as u16;
+        let y_pos = inner.y + header_offset + (idx % rows_per_column) as u16;
 
         let line_area = Rect {
-            x: inner.x,
+            x: inner.x + column * column_width,
             y: y_pos,
-            width: inner.width,
+            width: line_width,
             height: 1,
         };
 
@@ -375,25 +964,20 @@ fn render_drive_stats(
         let slot_label = format!("{:02}", slot);
 
         // Pool name (truncated)
-        let pool_name = dev.zfs_info.as_ref()
+        let pool_name = dev.zfs_info
             .map(|z| truncate_str(&z.pool, POOL_W))
             .unwrap_or_else(|| "-".to_string());
 
         // Role name and color
-        let (role_name, role_color) = if let Some(ref zfs_info) = dev.zfs_info {
-            match zfs_info.role {
-                ZfsRole::Data => ("data", Color::Cyan),
-                ZfsRole::Slog => ("log", Color::Yellow),
-                ZfsRole::Cache => ("cache", Color::Magenta),
-                ZfsRole::Spare => ("spare", Color::Blue),
-            }
+        let (role_name, role_color) = if let Some(zfs_info) = dev.zfs_info {
+            role_style(&zfs_info.role, theme)
         } else {
-            ("-", Color::DarkGray)
+            ("-", theme.muted)
         };
 
         // Vdev topology shorthand: raidz1-0 -> r1-0, mirror-5 -> mi-5
         // Shows "-" for devices without a vdev (individual cache/spare)
-        let vdev_short = if let Some(ref zfs_info) = dev.zfs_info {
+        let vdev_short = if let Some(zfs_info) = dev.zfs_info {
             let vdev = &zfs_info.vdev;
             if vdev.starts_with("raidz3") {
                 vdev.replace("raidz3-", "r3-")
@@ -416,85 +1000,127 @@ fn render_drive_stats(
         let vdev_padded = format!("{:<VDEV_W$}", truncate_str(&vdev_short, VDEV_W));
 
         // State indicator (colored dot)
-        let (state_char, state_color) = if let Some(ref zfs_info) = dev.zfs_info {
+        let (state_char, state_color) = if let Some(zfs_info) = dev.zfs_info {
             match zfs_info.state.to_uppercase().as_str() {
-                "ONLINE" => ("●", Color::Green),
-                "DEGRADED" => ("●", Color::Yellow),
-                "FAULTED" | "UNAVAIL" | "OFFLINE" => ("●", Color::Red),
-                "AVAIL" => ("○", Color::Green),  // Spare available
-                _ => ("○", Color::DarkGray),
+                "ONLINE" => (glyphs().led_on, theme.good),
+                "DEGRADED" => (glyphs().led_on, theme.warn),
+                "FAULTED" | "UNAVAIL" | "OFFLINE" => (glyphs().led_on, theme.critical),
+                "AVAIL" => (glyphs().led_off, theme.good),  // Spare available
+                _ => (glyphs().led_off, theme.muted),
             }
         } else {
-            ("○", Color::DarkGray)
+            (glyphs().led_off, theme.muted)
         };
 
         // IOPS (total read + write)
-        let total_iops = dev.statistics.total_iops();
-        let iops_text = if total_iops >= 10000.0 {
-            format!("{:>4.0}k", total_iops / 1000.0)
-        } else {
-            format!("{:>IOPS_W$.0}", total_iops)
-        };
+        let iops_text = format_iops(dev.statistics.total_iops());
 
         // Throughput MB/s (total)
-        let total_bw = dev.statistics.total_bw_mbps();
-        let bw_text = if total_bw >= 1000.0 {
-            format!("{:>4.1}G", total_bw / 1000.0)
-        } else {
-            format!("{:>BW_W$.1}", total_bw)
-        };
+        let bw_text = format_mbps(dev.statistics.total_bw_mbps());
 
         // Busy %
         let busy_pct = dev.statistics.busy_pct;
-        let busy_text = format!("{:>2.0}%", busy_pct.min(99.0));
-        let busy_color = if busy_pct > 80.0 {
-            Color::Red
-        } else if busy_pct > 50.0 {
-            Color::Yellow
-        } else if busy_pct > 0.1 {
-            Color::Green
+        let busy_text = format_busy_cell(dev.statistics);
+        let busy_color = busy_color(busy_pct, theme);
+
+        // Outstanding I/Os
+        let queue_depth = dev.statistics.queue_depth;
+        let qd_text = format!("{:>QD_W$.0}", queue_depth.min(999.0));
+        let qd_color = queue_depth_color(queue_depth, &config.queue_depth, theme);
+
+        // CAM errors since boot; any at all is worth a look
+        let errors = dev.statistics.total_errors();
+        let (err_text, err_color) = if errors > 0 {
+            (format!("{:>ERR_W$}", errors.min(999)), theme.critical)
         } else {
-            Color::DarkGray
+            (format!("{:>ERR_W$}", "-"), theme.muted)
         };
 
         // Calculate sparkline width (remaining space)
-        let sparkline_width = if inner.width > FIXED_PREFIX {
-            (inner.width - FIXED_PREFIX) as usize
+        let mut sparkline_width = if line_width > prefix_width {
+            (line_width - prefix_width) as usize
         } else {
             0
         };
 
+        // Operator's note for this bay, as a short tag after the sparkline
+        // when it still leaves a usable sparkline (notes name physical bays,
+        // so none for logical slot numbers)
+        let note = dev
+            .slot
+            .filter(|_| !synthetic_slots)
+            .and_then(|slot| config.slot_note(dev.enclosure, slot))
+            .map(|note| truncate_str(note, NOTE_W))
+            .filter(|note| sparkline_width >= note.chars().count() + 1 + DENSE_MIN_SPARKLINE as usize);
+        if let Some(ref note) = note {
+            let note_width = note.chars().count() + 1;
+            sparkline_width -= note_width;
+            let note_area = Rect {
+                x: line_area.x + prefix_width + sparkline_width as u16 + 1,
+                y: line_area.y,
+                width: (note_width - 1) as u16,
+                height: 1,
+            };
+            frame.render_widget(
+                Paragraph::new(note.as_str()).style(Style::default().fg(theme.highlight)),
+                note_area,
+            );
+        }
+
         // Build spans
+        // A drive down to one path gets its slot number in reverse video,
+        // and one whose ZFS role or state just changed flashes it
+        let slot_style = if dev.flashing && flash_on {
+            Style::default().fg(theme.critical).add_modifier(Modifier::REVERSED)
+        } else if dev.lacks_redundancy {
+            Style::default().fg(theme.warn).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(if synthetic_slots { theme.muted } else { theme.text })
+        };
         let mut spans = vec![
-            Span::styled(&slot_label, Style::default().fg(Color::White)),
+            Span::styled(&slot_label, slot_style),
             Span::raw(" "),
-            Span::styled(format!("{:<POOL_W$}", pool_name), Style::default().fg(Color::DarkGray)),
+        ];
+        if show_dev {
+            let (dev_name, dev_color) = match dev.alias {
+                Some(alias) => (truncate_str(alias, dev_width), theme.text),
+                None => (truncate_str(dev.device.unwrap_or("-"), dev_width), theme.muted),
+            };
+            spans.push(Span::styled(format!("{:<dev_width$}", dev_name), Style::default().fg(dev_color)));
+            spans.push(Span::raw(" "));
+        }
+        spans.extend([
+            Span::styled(format!("{:<POOL_W$}", pool_name), Style::default().fg(theme.muted)),
             Span::raw(" "),
             Span::styled(format!("{:<ROLE_W$}", role_name), Style::default().fg(role_color)),
             Span::raw(" "),
-            Span::styled(&vdev_padded, Style::default().fg(Color::DarkGray)),
+            Span::styled(&vdev_padded, Style::default().fg(theme.muted)),
             Span::raw(" "),
             Span::styled(state_char, Style::default().fg(state_color)),
             Span::raw(" "),
-            Span::styled(&iops_text, Style::default().fg(Color::White)),
+            Span::styled(&iops_text, Style::default().fg(theme.text)),
             Span::raw(" "),
-            Span::styled(&bw_text, Style::default().fg(Color::White)),
+            Span::styled(&bw_text, Style::default().fg(theme.text)),
             Span::raw(" "),
             Span::styled(&busy_text, Style::default().fg(busy_color)),
             Span::raw(" "),
-        ];
+            Span::styled(&qd_text, Style::default().fg(qd_color)),
+            Span::raw(" "),
+            Span::styled(&err_text, Style::default().fg(err_color)),
+            Span::raw(" "),
+        ]);
 
         if sparkline_width > 0 {
             // Split area: text on left, sparkline on right
             let text_area = Rect {
                 x: line_area.x,
                 y: line_area.y,
-                width: FIXED_PREFIX,
+                width: prefix_width,
                 height: 1,
             };
 
             let sparkline_area = Rect {
-                x: line_area.x + FIXED_PREFIX,
+                x: line_area.x + prefix_width,
                 y: line_area.y,
                 width: sparkline_width as u16,
                 height: 1,
@@ -504,18 +1130,19 @@ fn render_drive_stats(
             frame.render_widget(Paragraph::new(text), text_area);
 
             // Render sparkline if we have history for this device
-            if let Some(history) = drive_busy_history.get(&dev.name) {
+            if let Some(history) = state.drive_sparkline_history().get(dev.name) {
                 if !history.is_empty() {
                     let start = if history.len() > sparkline_width {
                         history.len() - sparkline_width
                     } else {
                         0
                     };
+                    // Pre-fill gaps (NaN) cast to 0, an empty bar
                     let data: Vec<u64> = history.iter().skip(start).map(|&v| v as u64).collect();
                     let sparkline = Sparkline::default()
                         .data(&data)
-                        .style(Style::default().fg(Color::Cyan))
-                        .bar_set(ratatui::symbols::bar::NINE_LEVELS);
+                        .style(Style::default().fg(sparkline_color))
+                        .bar_set(glyphs().sparkline.clone());
                     frame.render_widget(sparkline, sparkline_area);
                 }
             }
@@ -528,31 +1155,226 @@ fn render_drive_stats(
     }
 }
 
-/// Truncate a string to max_len characters
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
+/// Render the pool -> vdev -> member drive tree for grouped mode, members
+/// collapsed in `DriveView::GroupedCollapsed`
+fn render_vdev_tree(frame: &mut Frame, area: Rect, state: &AppState, config: &Config, theme: &Theme) {
+    let groups = group_by_vdev(&state.multipath_devices, &state.standalone_disks);
+    let pool_trends = state.pool_trends();
+    let collapsed = state.drive_view == DriveView::GroupedCollapsed;
+    if groups.is_empty() {
+        let placeholder = Paragraph::new("No ZFS pools detected")
+            .style(Style::default().fg(theme.muted));
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{:<TREE_NAME_W$} {:>5} {:>5} {:>3}", "POOL/VDEV", "IOPS", units().mb_label(), "BSY"),
+        Style::default().fg(theme.muted),
+    ))];
+
+    for (idx, group) in groups.iter().enumerate() {
+        // Pool header with totals across all its vdevs
+        let first_in_pool = idx == 0 || groups[idx - 1].pool != group.pool;
+        if first_in_pool {
+            let pool_stats = aggregate(
+                groups.iter()
+                    .filter(|g| g.pool == group.pool)
+                    .flat_map(|g| g.members.iter().map(|m| &m.statistics)),
+            );
+            let mut line = tree_stats_line(&group.pool, &pool_stats, theme.text, theme);
+            if let Some(trend) = pool_trends.get(&group.pool) {
+                line.spans.extend(pool_trend_spans(trend, theme));
+            }
+            lines.push(line);
+        }
+
+        let last_in_pool = groups.get(idx + 1).is_none_or(|g| g.pool != group.pool);
+        let branch = if last_in_pool { glyphs().tree_last } else { glyphs().tree_branch };
+        let (_, role_color) = role_style(&group.role, theme);
+        let label = format!("{} {} ({})", branch, group.vdev, group.members.len());
+        let mut line = tree_stats_line(&label, &group.statistics, role_color, theme);
+        // Many small reads alongside a write-heavy raidz load: likely read-modify-write
+        if let Some(ratio) = group.rmw_ratio() {
+            line.spans.push(Span::styled(
+                format!(" RMW {:.1}r/w", ratio),
+                Style::default().fg(theme.warn),
+            ));
+        }
+        lines.push(line);
+
+        if !collapsed {
+            let stem = if last_in_pool { " " } else { glyphs().separator };
+            for member in &group.members {
+                let slot = member.slot
+                    .map(|s| format!("{:02}", s))
+                    .unwrap_or_else(|| "--".to_string());
+                let name = config
+                    .alias(member.ident.as_deref(), &member.name)
+                    .unwrap_or_else(|| member.name.strip_prefix("multipath/").unwrap_or(&member.name));
+                let label = format!("{}  {} {}", stem, slot, name);
+                lines.push(tree_stats_line(&label, &member.statistics, theme.muted, theme));
+            }
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Capacity at which a pool's fill level turns amber and then red
+const POOL_CAPACITY_WARN: f64 = 80.0;
+const POOL_CAPACITY_CRITICAL: f64 = 90.0;
+
+/// Time to full below which the estimate is shown in red
+const POOL_FULL_SOON: Duration = Duration::from_secs(7 * 86400);
+
+/// " 63% +1.2G/h full ~35d frag 12% (+2)" after a pool's totals
+fn pool_trend_spans(trend: &PoolTrend, theme: &Theme) -> Vec<Span<'static>> {
+    let capacity = trend.usage.capacity_pct();
+    let capacity_color = if capacity >= POOL_CAPACITY_CRITICAL {
+        theme.critical
+    } else if capacity >= POOL_CAPACITY_WARN {
+        theme.warn
+    } else {
+        theme.text
+    };
+    let mut spans = vec![Span::styled(format!(" {:>3.0}%", capacity), Style::default().fg(capacity_color))];
+
+    match trend.fill_bytes_per_hour {
+        Some(rate) => {
+            let sign = if rate < 0.0 { "-" } else { "+" };
+            spans.push(Span::styled(
+                format!(" {}{}/h", sign, format_bytes(rate.abs())),
+                Style::default().fg(theme.muted),
+            ));
+            if let Some(eta) = trend.time_to_full() {
+                let color = if eta < POOL_FULL_SOON { theme.critical } else { theme.warn };
+                spans.push(Span::styled(
+                    format!(" full ~{}", format_span(eta.as_secs_f64())),
+                    Style::default().fg(color),
+                ));
+            }
+        }
+        None => spans.push(Span::styled(" rate pending", Style::default().fg(theme.muted))),
+    }
+
+    if let Some(frag) = trend.usage.fragmentation_pct {
+        let change = trend
+            .fragmentation_change
+            .filter(|c| c.abs() >= 1.0)
+            .map(|c| format!(" ({:+.0})", c))
+            .unwrap_or_default();
+        spans.push(Span::styled(
+            format!(" frag {:.0}%{}", frag, change),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    spans
+}
+
+/// Width of the name column in the vdev tree
+const TREE_NAME_W: usize = 20;
+
+fn tree_stats_line(label: &str, stats: &DiskStatistics, color: Color, theme: &Theme) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{:<TREE_NAME_W$}", truncate_str(label, TREE_NAME_W)), Style::default().fg(color)),
+        Span::raw(" "),
+        Span::styled(format_iops(stats.total_iops()), Style::default().fg(theme.text)),
+        Span::raw(" "),
+        Span::styled(format_mbps(stats.total_bw_mbps()), Style::default().fg(theme.text)),
+        Span::raw(" "),
+        Span::styled(
+            format_busy_cell(stats),
+            Style::default().fg(busy_color(stats.busy_pct, theme)),
+        ),
+    ])
+}
+
+/// Short role name and color for a ZFS role
+fn role_style(role: &ZfsRole, theme: &Theme) -> (&'static str, Color) {
+    match role {
+        ZfsRole::Data => ("data", theme.accent),
+        ZfsRole::Slog => ("log", theme.warn),
+        ZfsRole::Cache => ("cache", theme.highlight),
+        ZfsRole::Spare => ("spare", theme.info),
+    }
+}
+
+/// Busy% threshold coloring
+fn busy_color(busy_pct: f64, theme: &Theme) -> Color {
+    if busy_pct >= BUSY_ALERT_PCT {
+        theme.critical
+    } else if busy_pct >= BUSY_WARN_PCT {
+        theme.warn
+    } else if busy_pct > 0.1 {
+        theme.good
+    } else {
+        theme.muted
+    }
+}
+
+/// Queue depth threshold coloring (levels from the config file)
+fn queue_depth_color(queue_depth: f64, thresholds: &QueueDepthThresholds, theme: &Theme) -> Color {
+    if queue_depth > thresholds.critical {
+        theme.critical
+    } else if queue_depth > thresholds.warn {
+        theme.warn
+    } else if queue_depth >= 0.5 {
+        theme.text
     } else {
-        s[..max_len].to_string()
+        theme.muted
     }
 }
 
-fn render_vertical_drive(frame: &mut Frame, area: Rect, slot: usize, devices: &[MultipathDevice]) {
+/// 3-char busy cell: "NN%" below saturation, "100" once the drive is pegged
+fn format_busy_cell(stats: &DiskStatistics) -> String {
+    if stats.is_saturated() {
+        "100".to_string()
+    } else {
+        format!("{:>2.0}%", stats.busy_pct)
+    }
+}
+
+/// Truncate a string to max_len characters
+fn truncate_str(s: &str, max_len: usize) -> String {
+    s.chars().take(max_len).collect()
+}
+
+fn render_vertical_drive(
+    frame: &mut Frame,
+    area: Rect,
+    slot: usize,
+    state: &AppState,
+    flashing: &HashSet<String>,
+    theme: &Theme,
+) {
+    if area.width < 3 || area.height < 3 {
+        return;
+    }
+
     // Find device for this slot
-    let device = find_device_for_slot(slot, devices);
+    let device = find_device_for_slot(slot, &state.multipath_devices);
 
     // Slot number as vertical digits (1-based)
     let slot_num = slot + 1;
     let digit1 = format!("{}", slot_num / 10); // tens digit (0 for slots 1-9)
     let digit2 = format!("{}", slot_num % 10); // ones digit
 
+    // Pegged drives get a heavy border so saturation stands out from merely busy
+    let border_set = if device.is_some_and(|d| d.statistics.is_saturated()) {
+        glyphs().border_thick
+    } else {
+        glyphs().border
+    };
+
     let (drive_visual, border_color) = match device {
         Some(dev) => {
-            // Determine blink state based on current time and activity
+            // The locate and transition flashes are a steady blink on the
+            // wall clock; the activity LEDs step with each path's own I/O (AppState)
             let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap();
-            let blink = (now.as_millis() / 250) % 2 == 0; // Toggle every 250ms
+            let blink = (now.as_millis() / 250).is_multiple_of(2); // Toggle every 250ms
 
             // Get per-controller activity from path_stats
             // Controller A (0) LED at top, Controller B (1) LED at bottom
@@ -566,20 +1388,25 @@ fn render_vertical_drive(frame: &mut Frame, area: Rect, slot: usize, devices: &[
                     Some(ps) => {
                         if !ps.is_active {
                             // Passive/standby path - show crossed circle in dark gray
-                            (Color::DarkGray, "⊘")
+                            (theme.muted, glyphs().led_passive)
                         } else {
                             // Active path - show activity-based LED
                             let has_read = ps.statistics.read_iops > 0.1;
                             let has_write = ps.statistics.write_iops > 0.1;
+                            let lit = if state.activity_leds.get(&ps.device_name).copied().unwrap_or(false) {
+                                glyphs().led_on
+                            } else {
+                                glyphs().led_off
+                            };
                             match (has_read, has_write) {
-                                (true, true) => (Color::Magenta, if blink { "●" } else { "○" }),
-                                (true, false) => (Color::Green, if blink { "●" } else { "○" }),
-                                (false, true) => (Color::Yellow, if blink { "●" } else { "○" }),
-                                (false, false) => (Color::DarkGray, "○"),
+                                (true, true) => (theme.highlight, lit),
+                                (true, false) => (theme.good, lit),
+                                (false, true) => (theme.warn, lit),
+                                (false, false) => (theme.muted, glyphs().led_off),
                             }
                         }
                     }
-                    None => (Color::DarkGray, "○"),
+                    None => (theme.muted, glyphs().led_off),
                 }
             };
 
@@ -588,23 +1415,40 @@ fn render_vertical_drive(frame: &mut Frame, area: Rect, slot: usize, devices: &[
 
             // Build vertical drive visualization:
             // Top LED (Controller A), slot digits, Bottom LED (Controller B)
+            // Slot digits flash while the bay's locate LED is on, and are
+            // dimmed when they're logical numbers rather than SES slots
+            let digit_style = if state.locating.contains(&dev.name) && blink {
+                Style::default().fg(theme.warn).add_modifier(Modifier::REVERSED)
+            } else if state.synthetic_slots {
+                Style::default().fg(theme.muted).add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(theme.text)
+            };
             let visual = vec![
                 Line::from(Span::styled(led_a_char, Style::default().fg(led_a_color))),
-                Line::from(Span::styled(&digit1, Style::default().fg(Color::White))),
-                Line::from(Span::styled(&digit2, Style::default().fg(Color::White))),
+                Line::from(Span::styled(&digit1, digit_style)),
+                Line::from(Span::styled(&digit2, digit_style)),
                 Line::from(Span::styled(led_b_char, Style::default().fg(led_b_color))),
             ];
 
-            // Color code border by busy percentage (from multipath device stats)
+            // Color code border by pool, or by busy percentage (from multipath
+            // device stats); a drive whose ZFS role or state just changed flashes
             let stats = &dev.statistics;
-            let color = if stats.busy_pct > 80.0 {
-                Color::Red
-            } else if stats.busy_pct > 50.0 {
-                Color::Yellow
+            let color = if flashing.contains(&dev.name) && blink {
+                theme.critical
+            } else if state.bay_coloring == BayColoring::Pool {
+                match dev.zfs_info {
+                    Some(ref zfs) => theme.pool_color(&zfs.pool),
+                    None => theme.muted,
+                }
+            } else if stats.busy_pct >= BUSY_ALERT_PCT {
+                theme.critical
+            } else if stats.busy_pct >= BUSY_WARN_PCT {
+                theme.warn
             } else if stats.total_iops() > 0.1 {
-                Color::Green
+                theme.good
             } else {
-                Color::DarkGray
+                theme.muted
             };
 
             (visual, color)
@@ -612,19 +1456,26 @@ fn render_vertical_drive(frame: &mut Frame, area: Rect, slot: usize, devices: &[
         None => {
             // Empty slot - show slot number vertically with empty LED positions
             let visual = vec![
-                Line::from(Span::styled(" ", Style::default().fg(Color::DarkGray))),
-                Line::from(Span::styled(&digit1, Style::default().fg(Color::DarkGray))),
-                Line::from(Span::styled(&digit2, Style::default().fg(Color::DarkGray))),
-                Line::from(Span::styled(" ", Style::default().fg(Color::DarkGray))),
+                Line::from(Span::styled(" ", Style::default().fg(theme.muted))),
+                Line::from(Span::styled(&digit1, Style::default().fg(theme.muted))),
+                Line::from(Span::styled(&digit2, Style::default().fg(theme.muted))),
+                Line::from(Span::styled(" ", Style::default().fg(theme.muted))),
             ];
-            (visual, Color::DarkGray)
+            (visual, theme.muted)
         }
     };
 
+    // The bay cursor overrides the activity color
+    let border_style = if state.selected_slot == Some(slot) {
+        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(border_color)
+    };
     let paragraph = Paragraph::new(drive_visual).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(border_color)),
+            .border_set(border_set)
+            .border_style(border_style),
     );
 
     frame.render_widget(paragraph, area);
@@ -1,7 +1,69 @@
+pub mod alerts;
+pub mod datasets;
 pub mod front_panel;
-pub mod stats_table;
 pub mod system_overview;
 
+pub use alerts::{find_alerts, render_alerts, Alert};
+pub use datasets::render_datasets;
 pub use front_panel::render_front_panel;
-pub use stats_table::render_stats_table;
 pub use system_overview::render_system_overview;
+
+use crate::ui::glyphs::glyphs;
+use crate::ui::state::Density;
+use crate::ui::theme::Theme;
+use ratatui::style::Style;
+use ratatui::widgets::{block::Title, Block, Borders};
+
+/// Frame for a top-level panel at the given density
+///
+/// Comfortable panels get a full accent border with `title`; dense panels
+/// collapse to a single separator line above the content and drop the title.
+pub(crate) fn panel_block<'a, T: Into<Title<'a>>>(title: T, density: Density, theme: &Theme) -> Block<'a> {
+    match density {
+        Density::Comfortable => Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(theme.accent)),
+        Density::Dense => Block::default()
+            .borders(Borders::TOP)
+            .border_set(glyphs().border)
+            .border_style(Style::default().fg(theme.muted)),
+    }
+}
+
+/// Room left above the highest point so a peak isn't drawn on the border
+const CHART_HEADROOM: f64 = 1.1;
+
+/// Chart points for `values` on a fixed `window`-point x axis, newest at
+/// the right edge
+///
+/// A history shorter than the window (just started, or zoomed out past
+/// what has been retained) fills in from the right instead of being
+/// stretched across the chart; use with `chart_x_bounds(window)`. Gaps
+/// (the pre-fill before the first sample) are left out, so that stretch
+/// stays blank.
+pub(crate) fn chart_points(values: Vec<f64>, window: usize) -> Vec<(f64, f64)> {
+    let offset = window.saturating_sub(values.len());
+    values
+        .into_iter()
+        .enumerate()
+        .filter(|(_, v)| v.is_finite())
+        .map(|(i, v)| ((offset + i) as f64, v))
+        .collect()
+}
+
+/// X bounds for a `window`-point chart, never a zero-width span
+pub(crate) fn chart_x_bounds(window: usize) -> [f64; 2] {
+    [0.0, window.saturating_sub(1).max(1) as f64]
+}
+
+/// Top of a zero-based y axis for `values`, with headroom
+///
+/// Never below `min_span`, so an empty or all-idle chart draws a flat line
+/// along the bottom against a sensible scale instead of a degenerate axis;
+/// non-finite samples are skipped.
+pub(crate) fn chart_y_max(values: impl IntoIterator<Item = f64>, min_span: f64) -> f64 {
+    let max = values.into_iter().filter(|v| v.is_finite()).fold(0.0, f64::max);
+    (max * CHART_HEADROOM).max(min_span)
+}
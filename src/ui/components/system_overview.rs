@@ -1,36 +1,68 @@
-use crate::collectors::{CpuStats, JailInfo, MemoryStats, NetworkStats, VmInfo};
+use crate::collectors::{CoreStats, CpuStats, JailInfo, MemoryStats, VmInfo};
+use crate::domain::statistics::HISTORY_GAP;
+use crate::ui::components::{chart_points, chart_x_bounds, chart_y_max, panel_block};
+use crate::ui::glyphs::glyphs;
+use crate::ui::options::UiOptions;
+use crate::ui::state::{AppState, Density};
+use crate::ui::theme::{ChartSeries, Theme};
+use crate::ui::zoom::Zoom;
+use crate::util::format::{format_bytes, format_count, format_pps, format_rate};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    symbols::Marker,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, List, ListItem, Paragraph},
+    widgets::{block::Title, Axis, Chart, Dataset, List, ListItem, Paragraph},
     Frame,
 };
 use std::collections::VecDeque;
 
+/// Smallest top of the network chart scale, in bytes per second
+const MIN_NET_CHART_SPAN: f64 = 1000.0;
+
 pub fn render_system_overview(
     frame: &mut Frame,
     area: Rect,
-    cpu_stats: &CpuStats,
-    memory_stats: &MemoryStats,
-    network_stats: &[NetworkStats],
-    vms: &[VmInfo],
-    jails: &[JailInfo],
-    _cpu_history: &[VecDeque<f64>],
-    cpu_aggregate_history: &VecDeque<f64>,
-    memory_history: &VecDeque<f64>,
-    _arc_size_history: &VecDeque<f64>,
-    _arc_ratio_history: &VecDeque<f64>,
-    network_history: &std::collections::HashMap<String, VecDeque<f64>>,
+    state: &AppState,
+    zoom: Zoom,
+    options: &UiOptions,
+    theme: &Theme,
 ) {
-    // Split into left and right sections
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let empty_cpu = CpuStats { cores: Vec::new(), interrupts: None };
+    let empty_mem = MemoryStats::default();
+    let cpu_stats = state.cpu_stats.as_ref().unwrap_or(&empty_cpu);
+    let memory_stats = state.memory_stats.as_ref().unwrap_or(&empty_mem);
+    let memory_pressure = state
+        .memory_stats
+        .as_ref()
+        .map(|m| m.pressure_pct(&options.config.memory_pressure));
+    let network_stats = &state.network_stats;
+    let vms = &state.vms;
+    let jails = &state.jails;
+    let panels = options.panels;
+    let density = state.density;
+
+    // Panels are drawn only when enabled and there is something to list, so
+    // a host without VMs or jails doesn't spend a column on placeholders
+    let show_vms = panels.vms && !vms.is_empty();
+    let show_jails = panels.jails && !jails.is_empty();
+    let show_network = panels.network && !network_stats.is_empty();
+
+    // Split into left and right sections; without VMs or jails the left
+    // section takes the full width
+    let right_visible = show_vms || show_jails;
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(60),  // Left: CPU + Memory + Network
-            Constraint::Percentage(40),  // Right: VMs + Jails
-        ])
+        .constraints(if right_visible {
+            [
+                Constraint::Percentage(60),  // Left: CPU + Memory + Network
+                Constraint::Percentage(40),  // Right: VMs + Jails
+            ]
+        } else {
+            [Constraint::Percentage(100), Constraint::Length(0)]
+        })
         .split(area);
 
     // Calculate CPU rows needed (each row is 1 line)
@@ -38,16 +70,18 @@ pub fn render_system_overview(
     let cpu_rows = if cpu_stats.cores.is_empty() {
         1
     } else {
-        (cpu_stats.cores.len() + cores_per_row - 1) / cores_per_row
+        cpu_stats.cores.len().div_ceil(cores_per_row)
     };
-    let cpu_height = (cpu_rows as u16) + 2; // +2 for border
+    let border_rows = density.border_rows();
+    let cpu_height = (cpu_rows as u16) + 1 + border_rows; // +1 aggregate row
 
-    // Memory needs ~4 lines (gauge + sparkline + swap + border)
-    let memory_height = 5u16;
+    // Memory needs 3 lines (bar + legend + swap) plus border, and a cache line with ZFS
+    let cache_rows = if memory_stats.arc_total_bytes > 0 { 1 } else { 0 };
+    let memory_height = 3 + cache_rows + border_rows;
 
     // Network: 1 line per interface + 2 for border, max ~6 interfaces shown
     let net_count = network_stats.len().min(6);
-    let network_height = (net_count as u16).max(1) + 2;
+    let network_height = if show_network { net_count as u16 + border_rows } else { 0 };
 
     // Left section: CPU, Memory, Network (sized to content)
     let left_chunks = Layout::default()
@@ -60,39 +94,72 @@ pub fn render_system_overview(
         ])
         .split(main_chunks[0]);
 
-    render_cpu_stats(frame, left_chunks[0], cpu_stats, cpu_aggregate_history);
-    render_memory_stats(frame, left_chunks[1], memory_stats, memory_history);
-    render_network_stats(frame, left_chunks[2], network_stats, network_history);
-
-    // Right section: VMs and Jails
-    let right_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(50),  // VMs
-            Constraint::Percentage(50),  // Jails
-        ])
-        .split(main_chunks[1]);
+    render_cpu_stats(frame, left_chunks[0], cpu_stats, state, zoom, theme);
+    render_memory_stats(frame, left_chunks[1], memory_stats, &state.memory_history, memory_pressure, density, theme);
+    if show_network {
+        render_network_stats(frame, left_chunks[2], state, zoom, theme);
+    }
 
-    render_vm_list(frame, right_chunks[0], vms);
-    render_jail_list(frame, right_chunks[1], jails);
+    // Right section: VMs and Jails, one pane gets the whole column if the other is hidden
+    match (show_vms, show_jails) {
+        (true, true) => {
+            let right_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(50),  // VMs
+                    Constraint::Percentage(50),  // Jails
+                ])
+                .split(main_chunks[1]);
+
+            render_vm_list(frame, right_chunks[0], vms, density, theme);
+            render_jail_list(frame, right_chunks[1], jails, density, theme);
+        }
+        (true, false) => render_vm_list(frame, main_chunks[1], vms, density, theme),
+        (false, true) => render_jail_list(frame, main_chunks[1], jails, density, theme),
+        (false, false) => {}
+    }
 }
 
-fn render_cpu_stats(frame: &mut Frame, area: Rect, cpu_stats: &CpuStats, cpu_aggregate_history: &VecDeque<f64>) {
-    let block = Block::default()
-        .title(format!(" CPU ({} cores) ", cpu_stats.cores.len()))
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+fn render_cpu_stats(
+    frame: &mut Frame,
+    area: Rect,
+    cpu_stats: &CpuStats,
+    state: &AppState,
+    zoom: Zoom,
+    theme: &Theme,
+) {
+    let cpu_aggregate_history = &state.cpu_aggregate_history;
+    let breakdown = state.cpu_breakdown;
+    let density = state.density;
+    let mut title = vec![Span::raw(format!(" CPU ({} cores) ", cpu_stats.cores.len()))];
+    if breakdown {
+        title.extend([
+            Span::styled("usr", Style::default().fg(theme.good)),
+            Span::raw(" "),
+            Span::styled("sys", Style::default().fg(theme.warn)),
+            Span::raw(" "),
+            Span::styled("intr", Style::default().fg(theme.critical)),
+            Span::raw(" "),
+        ]);
+    }
+    // Interrupt rate tells IRQ storms apart from syscall-heavy system time;
+    // the breakdown also names the busiest source
+    if let Some(ref interrupts) = cpu_stats.interrupts {
+        title.push(Span::styled(
+            format!("{} irq/s ", format_count(interrupts.total_per_sec)),
+            Style::default().fg(theme.muted),
+        ));
+        if let Some(top) = interrupts.top_sources.first().filter(|_| breakdown) {
+            title.push(Span::styled(
+                format!("({} {}/s) ", top.name, format_count(top.per_sec)),
+                Style::default().fg(theme.critical),
+            ));
+        }
+    }
+
+    let mut block = panel_block(Line::from(title), density, theme);
 
     let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    // Handle empty cores case
-    if cpu_stats.cores.is_empty() {
-        let placeholder = Paragraph::new("Collecting CPU stats...")
-            .style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(placeholder, inner);
-        return;
-    }
 
     // Layout: compact core list on left, aggregate chart on right
     // Each core needs ~10 chars: "● C15 100%" - we show 4 columns
@@ -106,12 +173,38 @@ fn render_cpu_stats(frame: &mut Frame, area: Rect, cpu_stats: &CpuStats, cpu_agg
         0
     };
 
+    // Time window covered by the aggregate chart
+    if chart_width > 3 && density == Density::Comfortable {
+        block = block.title(
+            Title::from(format!(" {} ", zoom.window_label(chart_width as usize * 2)))
+                .alignment(Alignment::Right),
+        );
+    }
+    frame.render_widget(block, area);
+
+    // Handle empty cores case
+    if cpu_stats.cores.is_empty() {
+        let placeholder = Paragraph::new("Collecting CPU stats...")
+            .style(Style::default().fg(theme.muted));
+        frame.render_widget(placeholder, inner);
+        return;
+    }
+
+    // Aggregate (all-core) row above the per-core grid
+    let summary_area = Rect {
+        x: inner.x,
+        y: inner.y,
+        width: core_list_width.min(inner.width),
+        height: 1,
+    };
+    render_cpu_summary(frame, summary_area, cpu_stats, cpu_aggregate_history, breakdown, theme);
+
     // Left side: compact core list
     let list_area = Rect {
         x: inner.x,
-        y: inner.y,
+        y: inner.y + 1,
         width: core_list_width.min(inner.width),
-        height: inner.height,
+        height: inner.height.saturating_sub(1),
     };
 
     // Right side: aggregate CPU chart
@@ -123,15 +216,15 @@ fn render_cpu_stats(frame: &mut Frame, area: Rect, cpu_stats: &CpuStats, cpu_agg
     };
 
     // Render compact core list in column-major order
-    let rows_needed = (cpu_stats.cores.len() + CORES_PER_ROW - 1) / CORES_PER_ROW;
+    let rows_needed = cpu_stats.cores.len().div_ceil(CORES_PER_ROW);
 
     // Blink state for activity indicators
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap();
-    let blink = (now.as_millis() / 200) % 2 == 0;
+    let blink = (now.as_millis() / 200).is_multiple_of(2);
 
-    for row_idx in 0..rows_needed.min(inner.height as usize) {
+    for row_idx in 0..rows_needed.min(list_area.height as usize) {
         let y_pos = list_area.y + row_idx as u16;
 
         for col_idx in 0..CORES_PER_ROW {
@@ -153,28 +246,29 @@ fn render_cpu_stats(frame: &mut Frame, area: Rect, cpu_stats: &CpuStats, cpu_agg
 
             // Determine indicator and color
             let indicator = if core.total_pct > 5.0 {
-                if blink { "●" } else { "○" }
+                if blink { glyphs().led_on } else { glyphs().led_off }
             } else {
-                "○"
+                glyphs().led_off
             };
 
-            let color = if core.total_pct > 80.0 {
-                Color::Red
-            } else if core.total_pct > 50.0 {
-                Color::Yellow
-            } else if core.total_pct > 5.0 {
-                Color::Green
+            let color = cpu_color(core.total_pct, theme);
+
+            let label = if breakdown {
+                let mut spans = vec![Span::styled(
+                    format!("C{:<2} ", core.core_id),
+                    Style::default().fg(theme.text),
+                )];
+                spans.extend(breakdown_bar(core, CORE_WIDTH as usize - 4, theme));
+                Line::from(spans)
             } else {
-                Color::DarkGray
+                Line::from(vec![
+                    Span::styled(format!("{} ", indicator), Style::default().fg(color)),
+                    Span::styled(
+                        format!("C{:<2}{:>3.0}%", core.core_id, core.total_pct),
+                        Style::default().fg(theme.text),
+                    ),
+                ])
             };
-
-            let label = Line::from(vec![
-                Span::styled(format!("{} ", indicator), Style::default().fg(color)),
-                Span::styled(
-                    format!("C{:<2}{:>3.0}%", core.core_id, core.total_pct),
-                    Style::default().fg(Color::White),
-                ),
-            ]);
             frame.render_widget(Paragraph::new(label), core_area);
         }
     }
@@ -184,58 +278,160 @@ fn render_cpu_stats(frame: &mut Frame, area: Rect, cpu_stats: &CpuStats, cpu_agg
         // Fixed window size based on chart width (2 data points per character with Braille)
         let window_size = (chart_width as usize) * 2;
 
-        // Take only the most recent window_size points
-        let start = if cpu_aggregate_history.len() > window_size {
-            cpu_aggregate_history.len() - window_size
-        } else {
-            0
-        };
-
-        // Convert to (x, y) points - always use 0..window_size for X to keep fixed scale
-        let data_points: Vec<(f64, f64)> = cpu_aggregate_history.iter()
-            .skip(start)
-            .enumerate()
-            .map(|(i, &v)| (i as f64, v))
-            .collect();
-
-        // Fixed X bounds - always use window_size so chart doesn't rescale
-        let x_max = window_size as f64;
+        // Take only the most recent window_size points (averaged when zoomed out),
+        // on a fixed X scale with the newest at the right
+        let data_points = chart_points(zoom.tail(cpu_aggregate_history, window_size), window_size);
 
         // CPU is always 0-100%
         let max_val = 100.0;
 
         let datasets = vec![
             Dataset::default()
-                .marker(Marker::Braille)
-                .style(Style::default().fg(Color::Cyan))
+                .marker(glyphs().chart_marker)
+                .style(Style::default().fg(theme.chart(ChartSeries::Cpu)))
                 .data(&data_points),
         ];
 
         let chart = Chart::new(datasets)
             .x_axis(
                 Axis::default()
-                    .bounds([0.0, x_max])
-                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds(chart_x_bounds(window_size))
+                    .style(Style::default().fg(theme.muted))
             )
             .y_axis(
                 Axis::default()
                     .bounds([0.0, max_val])
                     .labels(vec![
-                        Span::styled("0", Style::default().fg(Color::DarkGray)),
-                        Span::styled("100%", Style::default().fg(Color::DarkGray)),
+                        Span::styled("0", Style::default().fg(theme.muted)),
+                        Span::styled("100%", Style::default().fg(theme.muted)),
                     ])
-                    .style(Style::default().fg(Color::DarkGray))
+                    .style(Style::default().fg(theme.muted))
             );
 
         frame.render_widget(chart, chart_area);
     }
 }
 
-fn render_memory_stats(frame: &mut Frame, area: Rect, mem_stats: &MemoryStats, _memory_history: &VecDeque<f64>) {
-    let block = Block::default()
-        .title(" Memory ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+/// CPU% threshold coloring shared by the per-core grid and the aggregate row
+fn cpu_color(pct: f64, theme: &Theme) -> Color {
+    if pct > 80.0 {
+        theme.critical
+    } else if pct > 50.0 {
+        theme.warn
+    } else if pct > 5.0 {
+        theme.good
+    } else {
+        theme.muted
+    }
+}
+
+/// One-line all-core summary: "ALL" gauge bar and percentage, or the
+/// averaged user/system/interrupt split in breakdown mode
+fn render_cpu_summary(
+    frame: &mut Frame,
+    area: Rect,
+    cpu_stats: &CpuStats,
+    cpu_aggregate_history: &VecDeque<f64>,
+    breakdown: bool,
+    theme: &Theme,
+) {
+    let n = cpu_stats.cores.len().max(1) as f64;
+    let avg = |f: fn(&CoreStats) -> f64| cpu_stats.cores.iter().map(f).sum::<f64>() / n;
+    let total = cpu_aggregate_history
+        .back()
+        .copied()
+        .filter(|v| v.is_finite())
+        .unwrap_or_else(|| avg(|c| c.total_pct));
+
+    // "ALL " + bar + " 100%"
+    let bar_width = (area.width as usize).saturating_sub(9);
+    let mut spans = vec![Span::styled("ALL ", Style::default().fg(theme.text))];
+
+    if breakdown {
+        let combined = CoreStats {
+            core_id: 0,
+            user_pct: avg(|c| c.user_pct),
+            system_pct: avg(|c| c.system_pct),
+            interrupt_pct: avg(|c| c.interrupt_pct),
+            idle_pct: avg(|c| c.idle_pct),
+            total_pct: total,
+        };
+        spans.extend(breakdown_bar(&combined, bar_width, theme));
+    } else {
+        let filled = (((total / 100.0) * bar_width as f64).round() as usize).min(bar_width);
+        spans.push(Span::styled(glyphs().bar_full.repeat(filled), Style::default().fg(cpu_color(total, theme))));
+        spans.push(Span::styled(glyphs().bar_idle.repeat(bar_width - filled), Style::default().fg(theme.muted)));
+    }
+
+    spans.push(Span::styled(format!(" {:>3.0}%", total), Style::default().fg(cpu_color(total, theme))));
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Stacked user/system/interrupt bar for one core, `width` cells wide
+fn breakdown_bar(core: &CoreStats, width: usize, theme: &Theme) -> Vec<Span<'static>> {
+    let cells = |pct: f64| ((pct / 100.0) * width as f64).round() as usize;
+    let user = cells(core.user_pct).min(width);
+    let system = cells(core.system_pct).min(width - user);
+    let interrupt = cells(core.interrupt_pct).min(width - user - system);
+    let idle = width - user - system - interrupt;
+
+    vec![
+        Span::styled(glyphs().bar_full.repeat(user), Style::default().fg(theme.good)),
+        Span::styled(glyphs().bar_full.repeat(system), Style::default().fg(theme.warn)),
+        Span::styled(glyphs().bar_full.repeat(interrupt), Style::default().fg(theme.critical)),
+        Span::styled(glyphs().bar_idle.repeat(idle), Style::default().fg(theme.muted)),
+    ]
+}
+
+/// Pressure score at which the memory gauge turns amber and then red
+const PRESSURE_WARN: f64 = 40.0;
+const PRESSURE_CRITICAL: f64 = 70.0;
+
+/// Cells in the header's pressure gauge
+const PRESSURE_GAUGE_WIDTH: usize = 5;
+
+/// "pressure ▮▮▯▯▯ 38 " for the memory panel title
+fn pressure_gauge(pressure: f64, theme: &Theme) -> Vec<Span<'static>> {
+    let color = if pressure >= PRESSURE_CRITICAL {
+        theme.critical
+    } else if pressure >= PRESSURE_WARN {
+        theme.warn
+    } else {
+        theme.good
+    };
+    let filled = ((pressure / 100.0 * PRESSURE_GAUGE_WIDTH as f64).round() as usize).min(PRESSURE_GAUGE_WIDTH);
+    vec![
+        Span::styled("pressure ", Style::default().fg(theme.muted)),
+        Span::styled(glyphs().bar_full.repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            glyphs().bar_free.repeat(PRESSURE_GAUGE_WIDTH - filled),
+            Style::default().fg(theme.muted),
+        ),
+        Span::styled(format!(" {:.0} ", pressure), Style::default().fg(color)),
+    ]
+}
+
+fn render_memory_stats(
+    frame: &mut Frame,
+    area: Rect,
+    mem_stats: &MemoryStats,
+    _memory_history: &VecDeque<f64>,
+    pressure: Option<f64>,
+    density: Density,
+    theme: &Theme,
+) {
+    // Headline figure is committed memory; reclaimable cache is not "used"
+    let mut title = vec![Span::raw(" Memory ")];
+    if let Some(pressure) = pressure {
+        title.extend(pressure_gauge(pressure, theme));
+    }
+    let mut block = panel_block(Line::from(title), density, theme);
+    if density == Density::Comfortable {
+        block = block.title(
+            Title::from(format!(" used {:.0}% ", mem_stats.used_pct))
+                .alignment(Alignment::Right),
+        );
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -261,15 +457,7 @@ fn render_memory_stats(frame: &mut Frame, area: Rect, mem_stats: &MemoryStats, _
     let _laundry_pct = (laundry as f64 / total * 100.0) as u16;
     let _free_pct = (free as f64 / total * 100.0) as u16;
 
-    // Format helper
-    fn fmt_gb(bytes: u64) -> String {
-        let gb = bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-        if gb >= 10.0 {
-            format!("{:.0}G", gb)
-        } else {
-            format!("{:.1}G", gb)
-        }
-    }
+    let fmt_gb = |bytes: u64| format_bytes(bytes as f64);
 
     // Row 1: Stacked bar visualization
     let bar_area = Rect {
@@ -289,25 +477,26 @@ fn render_memory_stats(frame: &mut Frame, area: Rect, mem_stats: &MemoryStats, _
     let active_chars = (active_pct as usize * bar_width / 100).max(if active > 0 { 1 } else { 0 });
     let inactive_chars = (inactive_pct as usize * bar_width / 100).max(if inactive > 0 { 1 } else { 0 });
 
-    // Fill remaining with free
+    // Fill remaining with free; the bar is scaled to installed RAM, so this
+    // also covers what the kernel reserved at boot (total - managed bytes)
     let used_chars = wired_chars + arc_chars + active_chars + inactive_chars;
     let free_chars = bar_width.saturating_sub(used_chars);
 
     // Add segments with block characters
     if wired_chars > 0 {
-        bar_spans.push(Span::styled("█".repeat(wired_chars), Style::default().fg(Color::Red)));
+        bar_spans.push(Span::styled(glyphs().bar_full.repeat(wired_chars), Style::default().fg(theme.critical)));
     }
     if arc_chars > 0 {
-        bar_spans.push(Span::styled("█".repeat(arc_chars), Style::default().fg(Color::Blue)));
+        bar_spans.push(Span::styled(glyphs().bar_full.repeat(arc_chars), Style::default().fg(theme.info)));
     }
     if active_chars > 0 {
-        bar_spans.push(Span::styled("█".repeat(active_chars), Style::default().fg(Color::Green)));
+        bar_spans.push(Span::styled(glyphs().bar_full.repeat(active_chars), Style::default().fg(theme.good)));
     }
     if inactive_chars > 0 {
-        bar_spans.push(Span::styled("█".repeat(inactive_chars), Style::default().fg(Color::Yellow)));
+        bar_spans.push(Span::styled(glyphs().bar_full.repeat(inactive_chars), Style::default().fg(theme.warn)));
     }
     if free_chars > 0 {
-        bar_spans.push(Span::styled("░".repeat(free_chars), Style::default().fg(Color::DarkGray)));
+        bar_spans.push(Span::styled(glyphs().bar_free.repeat(free_chars), Style::default().fg(theme.muted)));
     }
 
     frame.render_widget(Paragraph::new(Line::from(bar_spans)), bar_area);
@@ -321,19 +510,18 @@ fn render_memory_stats(frame: &mut Frame, area: Rect, mem_stats: &MemoryStats, _
             height: 1,
         };
 
-        let total_gb = mem_stats.total_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
         let legend = Line::from(vec![
-            Span::styled("█", Style::default().fg(Color::Red)),
-            Span::styled(format!("Wired:{} ", fmt_gb(wired_non_arc)), Style::default().fg(Color::DarkGray)),
-            Span::styled("█", Style::default().fg(Color::Blue)),
-            Span::styled(format!("ARC:{} ", fmt_gb(arc)), Style::default().fg(Color::DarkGray)),
-            Span::styled("█", Style::default().fg(Color::Green)),
-            Span::styled(format!("Active:{} ", fmt_gb(active)), Style::default().fg(Color::DarkGray)),
-            Span::styled("█", Style::default().fg(Color::Yellow)),
-            Span::styled(format!("Inactive:{} ", fmt_gb(inactive)), Style::default().fg(Color::DarkGray)),
-            Span::styled("░", Style::default().fg(Color::DarkGray)),
-            Span::styled(format!("Free:{} ", fmt_gb(free)), Style::default().fg(Color::DarkGray)),
-            Span::styled(format!("/{:.0}G", total_gb), Style::default().fg(Color::White)),
+            Span::styled(glyphs().bar_full, Style::default().fg(theme.critical)),
+            Span::styled(format!("Wired:{} ", fmt_gb(wired_non_arc)), Style::default().fg(theme.muted)),
+            Span::styled(glyphs().bar_full, Style::default().fg(theme.info)),
+            Span::styled(format!("ARC:{} ", fmt_gb(arc)), Style::default().fg(theme.muted)),
+            Span::styled(glyphs().bar_full, Style::default().fg(theme.good)),
+            Span::styled(format!("Active:{} ", fmt_gb(active)), Style::default().fg(theme.muted)),
+            Span::styled(glyphs().bar_full, Style::default().fg(theme.warn)),
+            Span::styled(format!("Inactive:{} ", fmt_gb(inactive)), Style::default().fg(theme.muted)),
+            Span::styled(glyphs().bar_free, Style::default().fg(theme.muted)),
+            Span::styled(format!("Free:{} ", fmt_gb(free)), Style::default().fg(theme.muted)),
+            Span::styled(format!("/{}", fmt_gb(mem_stats.total_bytes)), Style::default().fg(theme.text)),
         ]);
 
         frame.render_widget(Paragraph::new(legend), legend_area);
@@ -348,54 +536,86 @@ fn render_memory_stats(frame: &mut Frame, area: Rect, mem_stats: &MemoryStats, _
             height: 1,
         };
 
-        let swap_gb = mem_stats.swap_total_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-        let swap_used_gb = mem_stats.swap_used_bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-
         let swap_color = if mem_stats.swap_used_pct > 50.0 {
-            Color::Yellow
+            theme.warn
         } else {
-            Color::DarkGray
+            theme.muted
         };
 
-        let swap_text = format!("Swap: {:.1}/{:.1}G ({:.0}%)", swap_used_gb, swap_gb, mem_stats.swap_used_pct);
-        frame.render_widget(Paragraph::new(swap_text).style(Style::default().fg(swap_color)), swap_area);
+        let swap_text = format!(
+            "Swap: {}/{} ({:.0}%)",
+            format_bytes(mem_stats.swap_used_bytes as f64),
+            format_bytes(mem_stats.swap_total_bytes as f64),
+            mem_stats.swap_used_pct
+        );
+
+        // Paging activity in pages/sec - sustained swapping usually means ARC starvation
+        let swin_color = if mem_stats.swap_in_pages_per_sec > 0.0 { theme.warn } else { theme.muted };
+        let swout_color = if mem_stats.swap_out_pages_per_sec > 0.0 { theme.critical } else { theme.muted };
+
+        let swap_line = Line::from(vec![
+            Span::styled(swap_text, Style::default().fg(swap_color)),
+            Span::styled(format!("  swin:{:.0}/s", mem_stats.swap_in_pages_per_sec), Style::default().fg(swin_color)),
+            Span::styled(format!(" swout:{:.0}/s", mem_stats.swap_out_pages_per_sec), Style::default().fg(swout_color)),
+        ]);
+        frame.render_widget(Paragraph::new(swap_line), swap_area);
     }
-}
 
-fn render_network_stats(
-    frame: &mut Frame,
-    area: Rect,
-    network_stats: &[NetworkStats],
-    network_history: &std::collections::HashMap<String, VecDeque<f64>>,
-) {
-    let title = format!(" Network ({}) ", network_stats.len());
-    let block = Block::default()
-        .title(title)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
+    // Row 4: ARC/L2ARC hit rates over the last interval
+    if mem_stats.arc_total_bytes > 0 && inner.height > 3 {
+        let cache_area = Rect {
+            x: inner.x,
+            y: inner.y + 3,
+            width: inner.width,
+            height: 1,
+        };
 
-    if network_stats.is_empty() {
-        let placeholder = Paragraph::new("No network interfaces")
-            .style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(placeholder, inner);
-        return;
-    }
+        fn hit_span(label: &str, pct: Option<f64>, theme: &Theme) -> Span<'static> {
+            match pct {
+                Some(pct) => {
+                    let color = if pct < 50.0 {
+                        theme.critical
+                    } else if pct < 80.0 {
+                        theme.warn
+                    } else {
+                        theme.good
+                    };
+                    Span::styled(format!("{}:{:.1}% ", label, pct), Style::default().fg(color))
+                }
+                None => Span::styled(format!("{}:-- ", label), Style::default().fg(theme.muted)),
+            }
+        }
 
-    // Format helper for bandwidth
-    fn format_bw(bytes_per_sec: f64) -> String {
-        if bytes_per_sec >= 1_000_000_000.0 {
-            format!("{:>5.1}G", bytes_per_sec / 1_000_000_000.0)
-        } else if bytes_per_sec >= 1_000_000.0 {
-            format!("{:>5.1}M", bytes_per_sec / 1_000_000.0)
-        } else if bytes_per_sec >= 1_000.0 {
-            format!("{:>5.1}K", bytes_per_sec / 1_000.0)
-        } else {
-            format!("{:>5.0}B", bytes_per_sec)
+        let mut spans = vec![
+            Span::styled("Hit ", Style::default().fg(theme.muted)),
+            hit_span("ARC", mem_stats.arc_hit_pct, theme),
+            hit_span("data", mem_stats.arc_demand_data_hit_pct, theme),
+        ];
+        if mem_stats.l2arc_size_bytes > 0 {
+            spans.push(hit_span("L2ARC", mem_stats.l2arc_hit_pct, theme));
+            spans.push(Span::styled(
+                format!("({})", fmt_gb(mem_stats.l2arc_size_bytes)),
+                Style::default().fg(theme.muted),
+            ));
         }
+        frame.render_widget(Paragraph::new(Line::from(spans)), cache_area);
     }
+}
+
+fn render_network_stats(frame: &mut Frame, area: Rect, state: &AppState, zoom: Zoom, theme: &Theme) {
+    let network_stats = &state.network_stats;
+    let network_history = &state.network_history;
+    let net_rate = state.net_rate;
+    let pps = state.net_pps;
+    let density = state.density;
+
+    // Name the rate shown in the list so it isn't mistaken for the chart's raw peaks
+    // (packet rates are always the smoothed ones; the chart stays in bytes)
+    let unit = if pps { "pps" } else { net_rate.label() };
+    let title = format!(" Network ({}, {}) ", network_stats.len(), unit);
+    let mut block = panel_block(title, density, theme);
+
+    let inner = block.inner(area);
 
     // Layout: interface list on left, combined chart on right
     // Text width: name(10) + rx_ind(1) + rx_bw(6) + space(1) + tx_ind(1) + tx_bw(6) = 25
@@ -407,6 +627,27 @@ fn render_network_stats(
         0
     };
 
+    // Time window covered by the combined chart
+    if chart_width > 3 && density == Density::Comfortable {
+        block = block.title(
+            Title::from(format!(" {} ", zoom.window_label(chart_width as usize * 2)))
+                .alignment(Alignment::Right),
+        );
+    }
+    frame.render_widget(block, area);
+
+    if network_stats.is_empty() {
+        let placeholder = Paragraph::new("No network interfaces")
+            .style(Style::default().fg(theme.muted));
+        frame.render_widget(placeholder, inner);
+        return;
+    }
+
+    // Right-aligned to 6 columns; IEC prefixes and packet rates take the
+    // list's spare columns
+    let format_bw = |bytes_per_sec: f64| format!("{:>6}", format_bytes(bytes_per_sec));
+    let format_packets = |pps: f64| format!("{:>7}", format_pps(pps));
+
     // Left side: interface list
     let list_area = Rect {
         x: inner.x,
@@ -435,43 +676,47 @@ fn render_network_stats(
         };
 
         // Indent members of aggregates
-        let name_prefix = if iface.is_member { " └" } else { "" };
+        let name_prefix = if iface.is_member { format!(" {}", glyphs().tree_last) } else { String::new() };
         let name_display = format!("{}{}", name_prefix, iface.name);
 
         // Determine if interface has traffic
-        let has_rx = iface.rx_bytes_per_sec > 100.0;
-        let has_tx = iface.tx_bytes_per_sec > 100.0;
+        let (rx_rate, tx_rate) = net_rate.rates(iface);
+        let has_rx = rx_rate > 100.0;
+        let has_tx = tx_rate > 100.0;
 
         // Activity indicators with triangles
         let (rx_indicator, rx_color) = if has_rx {
-            ("▼", Color::Green)
+            (glyphs().rx_active, theme.good)
         } else {
-            ("▽", Color::DarkGray)
+            (glyphs().rx_idle, theme.muted)
         };
 
         let (tx_indicator, tx_color) = if has_tx {
-            ("▲", Color::Yellow)
+            (glyphs().tx_active, theme.warn)
         } else {
-            ("△", Color::DarkGray)
+            (glyphs().tx_idle, theme.muted)
         };
 
-        let rx_bw = format_bw(iface.rx_bytes_per_sec);
-        let tx_bw = format_bw(iface.tx_bytes_per_sec);
+        let (rx_bw, tx_bw) = if pps {
+            (format_packets(iface.rx_packets_per_sec), format_packets(iface.tx_packets_per_sec))
+        } else {
+            (format_bw(rx_rate), format_bw(tx_rate))
+        };
 
         let name_color = if iface.is_aggregate {
-            Color::White
+            theme.text
         } else if iface.is_member {
-            Color::Cyan
+            theme.accent
         } else {
-            Color::White
+            theme.text
         };
 
         let spans = vec![
             Span::styled(format!("{:<8}", name_display), Style::default().fg(name_color)),
             Span::styled(rx_indicator, Style::default().fg(rx_color)),
-            Span::styled(format!("{}", rx_bw), Style::default().fg(if has_rx { Color::Green } else { Color::DarkGray })),
+            Span::styled(rx_bw, Style::default().fg(if has_rx { theme.good } else { theme.muted })),
             Span::styled(tx_indicator, Style::default().fg(tx_color)),
-            Span::styled(format!("{}", tx_bw), Style::default().fg(if has_tx { Color::Yellow } else { Color::DarkGray })),
+            Span::styled(tx_bw, Style::default().fg(if has_tx { theme.warn } else { theme.muted })),
         ];
         let text = Line::from(spans);
         frame.render_widget(Paragraph::new(text), line_area);
@@ -495,14 +740,20 @@ fn render_network_stats(
                     .map(|s| s.name.as_str())
                     .collect();
 
+                // Align on the newest sample; shorter (newer) histories and
+                // pre-fill gaps count as 0, and a slot where every interface
+                // is a gap stays one
                 (0..max_len).map(|i| {
-                    non_member_ifaces.iter()
+                    let samples: Vec<f64> = non_member_ifaces.iter()
                         .filter_map(|name| {
                             network_history.get(*name).and_then(|h| {
-                                if i < h.len() { Some(h[i]) } else { h.back().copied() }
+                                let offset = max_len - h.len();
+                                if i >= offset { Some(h[i - offset]) } else { None }
                             })
                         })
-                        .sum()
+                        .filter(|v| v.is_finite())
+                        .collect();
+                    if samples.is_empty() { HISTORY_GAP } else { samples.iter().sum() }
                 }).collect()
             }
         };
@@ -511,56 +762,38 @@ fn render_network_stats(
             // Fixed window size based on chart width (2 data points per character with Braille)
             let window_size = (chart_width as usize) * 2;
 
-            // Take only the most recent window_size points
-            let start = if total_history.len() > window_size {
-                total_history.len() - window_size
-            } else {
-                0
-            };
-
-            // Convert to (x, y) points - always use 0..window_size for X to keep fixed scale
-            let data_points: Vec<(f64, f64)> = total_history.iter()
-                .skip(start)
-                .enumerate()
-                .map(|(i, &v)| (i as f64, v))
-                .collect();
+            // Take only the most recent window_size points (averaged when zoomed out),
+            // on a fixed X scale with the newest at the right
+            let total_history = VecDeque::from(total_history);
+            let data_points = chart_points(zoom.tail(&total_history, window_size), window_size);
 
-            let max_val = data_points.iter().map(|(_, y)| *y).fold(1.0f64, f64::max);
-            // Fixed X bounds - always use window_size so chart doesn't rescale
-            let x_max = window_size as f64;
+            // An idle link still gets a readable scale rather than "1B/s"
+            let max_val = chart_y_max(data_points.iter().map(|&(_, y)| y), MIN_NET_CHART_SPAN);
 
             // Format max value for Y axis label
-            let max_label = if max_val >= 1_000_000_000.0 {
-                format!("{:.1}G", max_val / 1_000_000_000.0)
-            } else if max_val >= 1_000_000.0 {
-                format!("{:.1}M", max_val / 1_000_000.0)
-            } else if max_val >= 1_000.0 {
-                format!("{:.1}K", max_val / 1_000.0)
-            } else {
-                format!("{:.0}B", max_val)
-            };
+            let max_label = format_rate(max_val);
 
             let datasets = vec![
                 Dataset::default()
-                    .marker(Marker::Braille)
-                    .style(Style::default().fg(Color::Cyan))
+                    .marker(glyphs().chart_marker)
+                    .style(Style::default().fg(theme.chart(ChartSeries::Network)))
                     .data(&data_points),
             ];
 
             let chart = Chart::new(datasets)
                 .x_axis(
                     Axis::default()
-                        .bounds([0.0, x_max])
-                        .style(Style::default().fg(Color::DarkGray))
+                        .bounds(chart_x_bounds(window_size))
+                        .style(Style::default().fg(theme.muted))
                 )
                 .y_axis(
                     Axis::default()
                         .bounds([0.0, max_val])
                         .labels(vec![
-                            Span::styled("0", Style::default().fg(Color::DarkGray)),
-                            Span::styled(max_label, Style::default().fg(Color::DarkGray)),
+                            Span::styled("0", Style::default().fg(theme.muted)),
+                            Span::styled(max_label, Style::default().fg(theme.muted)),
                         ])
-                        .style(Style::default().fg(Color::DarkGray))
+                        .style(Style::default().fg(theme.muted))
                 );
 
             frame.render_widget(chart, chart_area);
@@ -568,34 +801,20 @@ fn render_network_stats(
     }
 }
 
-fn render_vm_list(frame: &mut Frame, area: Rect, vms: &[VmInfo]) {
+fn render_vm_list(frame: &mut Frame, area: Rect, vms: &[VmInfo], density: Density, theme: &Theme) {
     let title = format!(" bhyve VMs ({}) ", vms.len());
-    let block = Block::default()
-        .title(title)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+    let block = panel_block(title, density, theme);
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
     if vms.is_empty() {
         let paragraph = Paragraph::new("No VMs running")
-            .style(Style::default().fg(Color::DarkGray));
+            .style(Style::default().fg(theme.muted));
         frame.render_widget(paragraph, inner);
         return;
     }
 
-    // Format helper for memory
-    fn format_mem(bytes: u64) -> String {
-        let gb = bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-        if gb >= 1.0 {
-            format!("{:.1}G", gb)
-        } else {
-            let mb = bytes as f64 / 1024.0 / 1024.0;
-            format!("{:.0}M", mb)
-        }
-    }
-
     let available_height = inner.height as usize;
 
     for (idx, vm) in vms.iter().take(available_height).enumerate() {
@@ -609,22 +828,22 @@ fn render_vm_list(frame: &mut Frame, area: Rect, vms: &[VmInfo]) {
 
         // Color based on CPU usage
         let cpu_color = if vm.cpu_pct > 80.0 {
-            Color::Red
+            theme.critical
         } else if vm.cpu_pct > 50.0 {
-            Color::Yellow
+            theme.warn
         } else if vm.cpu_pct > 5.0 {
-            Color::Green
+            theme.good
         } else {
-            Color::DarkGray
+            theme.muted
         };
 
         // Format: ● name CPU% MEM
-        let mem_str = format_mem(vm.memory_bytes);
+        let mem_str = format_bytes(vm.memory_bytes as f64);
         let spans = vec![
-            Span::styled("● ", Style::default().fg(Color::Green)),
-            Span::styled(format!("{:<12}", vm.name), Style::default().fg(Color::White)),
+            Span::styled(format!("{} ", glyphs().led_on), Style::default().fg(theme.good)),
+            Span::styled(format!("{:<12}", vm.name), Style::default().fg(theme.text)),
             Span::styled(format!("{:>5.1}%", vm.cpu_pct), Style::default().fg(cpu_color)),
-            Span::styled(format!(" {:>6}", mem_str), Style::default().fg(Color::Cyan)),
+            Span::styled(format!(" {:>6}", mem_str), Style::default().fg(theme.accent)),
         ];
 
         let line = Line::from(spans);
@@ -632,24 +851,39 @@ fn render_vm_list(frame: &mut Frame, area: Rect, vms: &[VmInfo]) {
     }
 }
 
-fn render_jail_list(frame: &mut Frame, area: Rect, jails: &[JailInfo]) {
+fn render_jail_list(frame: &mut Frame, area: Rect, jails: &[JailInfo], density: Density, theme: &Theme) {
     let title = format!(" Jails ({}) ", jails.len());
-    let block = Block::default()
-        .title(title)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+    let block = panel_block(title, density, theme);
 
     if jails.is_empty() {
         let paragraph = Paragraph::new("No jails running")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .block(block);
         frame.render_widget(paragraph, area);
     } else {
+        let width = block.inner(area).width as usize;
         let items: Vec<ListItem> = jails
             .iter()
             .map(|jail| {
-                let content = format!("● {} (JID: {})", jail.name, jail.jid);
-                ListItem::new(content).style(Style::default().fg(Color::Green))
+                let content = format!("{} {} (JID: {})", glyphs().led_on, jail.name, jail.jid);
+
+                // First address plus a count of the rest, cut to the remaining width
+                let room = width.saturating_sub(content.chars().count() + 2);
+                let mut addresses = jail.ip_addresses.first().cloned().unwrap_or_default();
+                if jail.ip_addresses.len() > 1 {
+                    addresses.push_str(&format!(" +{}", jail.ip_addresses.len() - 1));
+                }
+                if addresses.chars().count() > room {
+                    let ellipsis = glyphs().ellipsis;
+                    let keep = room.saturating_sub(ellipsis.chars().count());
+                    addresses = addresses.chars().take(keep).collect::<String>() + ellipsis;
+                }
+
+                let mut spans = vec![Span::styled(content, Style::default().fg(theme.good))];
+                if room > 0 && !jail.ip_addresses.is_empty() {
+                    spans.push(Span::styled(format!("  {}", addresses), Style::default().fg(theme.muted)));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
@@ -0,0 +1,121 @@
+//! Glyph set used by every component
+//!
+//! Selected once at startup: the default uses Unicode LEDs, block bars and
+//! braille charts; `--ascii` swaps them for plain ASCII so the UI stays
+//! readable on consoles and terminal emulators without those glyphs.
+
+use ratatui::symbols::{bar, border, Marker};
+use std::sync::OnceLock;
+
+static GLYPHS: OnceLock<Glyphs> = OnceLock::new();
+
+#[derive(Clone, Debug)]
+pub struct Glyphs {
+    pub led_on: &'static str,       // Activity LED lit
+    pub led_off: &'static str,      // Activity LED dark / idle
+    pub led_passive: &'static str,  // Standby multipath path
+    pub bar_full: &'static str,     // Filled cell of a usage bar
+    pub bar_free: &'static str,     // Free memory cell
+    pub bar_idle: &'static str,     // Idle CPU cell
+    pub rx_active: &'static str,
+    pub rx_idle: &'static str,
+    pub tx_active: &'static str,
+    pub tx_idle: &'static str,
+    pub separator: &'static str,    // Vertical rule between footer/legend fields
+    pub tree_branch: &'static str,
+    pub tree_last: &'static str,
+    pub ellipsis: &'static str,
+    pub arrow: &'static str,        // Disk to the partitions on it
+    pub chart_marker: Marker,
+    pub sparkline: bar::Set,
+    pub border: border::Set,
+    pub border_thick: border::Set,  // Saturated drive outline
+}
+
+impl Glyphs {
+    pub fn unicode() -> Self {
+        Self {
+            led_on: "●",
+            led_off: "○",
+            led_passive: "⊘",
+            bar_full: "█",
+            bar_free: "░",
+            bar_idle: "·",
+            rx_active: "▼",
+            rx_idle: "▽",
+            tx_active: "▲",
+            tx_idle: "△",
+            separator: "│",
+            tree_branch: "├",
+            tree_last: "└",
+            ellipsis: "…",
+            arrow: "→",
+            chart_marker: Marker::Braille,
+            sparkline: bar::NINE_LEVELS,
+            border: border::PLAIN,
+            border_thick: border::THICK,
+        }
+    }
+
+    pub fn ascii() -> Self {
+        Self {
+            led_on: "*",
+            led_off: "o",
+            led_passive: "x",
+            bar_full: "#",
+            bar_free: "-",
+            bar_idle: ".",
+            rx_active: "v",
+            rx_idle: ".",
+            tx_active: "^",
+            tx_idle: ".",
+            separator: "|",
+            tree_branch: "|",
+            tree_last: "`",
+            ellipsis: "...",
+            arrow: "->",
+            chart_marker: Marker::Dot,
+            sparkline: bar::Set {
+                full: "#",
+                seven_eighths: "#",
+                three_quarters: "=",
+                five_eighths: "=",
+                half: "-",
+                three_eighths: "-",
+                one_quarter: "_",
+                one_eighth: "_",
+                empty: " ",
+            },
+            border: border::Set {
+                top_left: "+",
+                top_right: "+",
+                bottom_left: "+",
+                bottom_right: "+",
+                vertical_left: "|",
+                vertical_right: "|",
+                horizontal_top: "-",
+                horizontal_bottom: "-",
+            },
+            border_thick: border::Set {
+                top_left: "#",
+                top_right: "#",
+                bottom_left: "#",
+                bottom_right: "#",
+                vertical_left: "#",
+                vertical_right: "#",
+                horizontal_top: "=",
+                horizontal_bottom: "=",
+            },
+        }
+    }
+}
+
+/// Choose the glyph set; only the first call has an effect
+pub fn select(ascii: bool) {
+    let _ = GLYPHS.set(if ascii { Glyphs::ascii() } else { Glyphs::unicode() });
+}
+
+/// The selected glyph set (Unicode if none was selected)
+pub fn glyphs() -> &'static Glyphs {
+    GLYPHS.get_or_init(Glyphs::unicode)
+}
@@ -1,6 +1,14 @@
 pub mod app;
+pub mod options;
 pub mod state;
+pub mod theme;
+pub mod zoom;
 pub mod components;
+pub mod glyphs;
 
 pub use app::run_tui;
-pub use state::AppState;
+pub use glyphs::Glyphs;
+pub use options::{ClockStyle, OverviewPanels, UiOptions};
+pub use state::{AppState, BayColoring, Density, DriveSort, DriveSparkline, DriveTotals, DriveView, HealthScore, LocateRequest, LogEvent, Maximized, NetRate};
+pub use theme::{ChartColors, ChartSeries, Theme};
+pub use zoom::Zoom;
@@ -0,0 +1,88 @@
+use crate::config::Config;
+use crate::ui::theme::Theme;
+use crate::util::format::Units;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Display options fixed at startup (from the command line)
+#[derive(Clone, Debug)]
+pub struct UiOptions {
+    /// Show y-axis scale labels on the storage charts
+    pub chart_axis_labels: bool,
+    /// Color palette for all components
+    pub theme: Theme,
+    /// Storage collection interval in milliseconds (one history sample per interval)
+    pub refresh_ms: u64,
+    /// Per-drive numbers show the mean over this many milliseconds of samples
+    pub average_ms: u64,
+    /// CPU/memory/network interval, for the system overview's chart windows
+    pub system_refresh_ms: u64,
+    /// Which optional system overview panels have a collector behind them
+    pub panels: OverviewPanels,
+    /// Front panel title naming the array or enclosure
+    pub title: String,
+    /// Draw with ASCII glyphs only (no LEDs, block bars or braille)
+    pub ascii: bool,
+    /// SI (MB = 10^6) or IEC (MiB = 2^20) sizes and rates
+    pub units: Units,
+    /// How the header shows the time of the last update
+    pub clock: ClockStyle,
+    /// Fade the UI to gray after this long with every drive idle
+    pub idle_dim: Option<Duration>,
+    /// Allow capturing mouse events (off with --no-mouse)
+    pub mouse: bool,
+    /// Settings from the configuration file (slot annotations)
+    pub config: Config,
+}
+
+/// Header time of the last update
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClockStyle {
+    /// "Updated: 1.2s ago"
+    #[default]
+    Relative,
+    /// "Updated: 14:03:27" (local wall clock, for recordings and log correlation)
+    Absolute,
+    /// "Updated: 14:03:27 (1.2s ago)"
+    Both,
+}
+
+/// Optional panels in the system overview; a disabled panel is not drawn
+/// and its space goes to the remaining panels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OverviewPanels {
+    pub network: bool,
+    pub vms: bool,
+    pub jails: bool,
+}
+
+impl Default for OverviewPanels {
+    fn default() -> Self {
+        Self {
+            network: true,
+            vms: true,
+            jails: true,
+        }
+    }
+}
+
+impl Default for UiOptions {
+    fn default() -> Self {
+        Self {
+            chart_axis_labels: true,
+            theme: Theme::default(),
+            refresh_ms: 250,
+            average_ms: 1000,
+            system_refresh_ms: 250,
+            panels: OverviewPanels::default(),
+            title: "Storage Array".to_string(),
+            ascii: false,
+            units: Units::default(),
+            clock: ClockStyle::default(),
+            idle_dim: None,
+            mouse: true,
+            config: Config::default(),
+        }
+    }
+}
@@ -1,11 +1,315 @@
-use crate::collectors::{CpuStats, JailInfo, MemoryStats, NetworkStats, VmInfo};
-use crate::domain::device::{MultipathDevice, PhysicalDisk};
-use std::collections::{HashMap, VecDeque};
-use std::time::Instant;
+use crate::collectors::{
+    CpuStats, DatasetUsage, EnclosureHealth, JailInfo, MemoryStats, NetworkStats, PoolUsage, VmInfo, ZfsDriveInfo,
+    ZfsRole, ZilStats,
+};
+use crate::collectors::ElementStatus;
+use crate::domain::device::{DiskStatistics, MultipathDevice, MultipathState, PhysicalDisk};
+use crate::domain::health::{zfs_state_severity, Severity, BUSY_ALERT_PCT, LINK_STATE_DOWN};
+use crate::domain::statistics::{mean, HISTORY_GAP};
+use crate::ui::zoom::MAX_ZOOM_FACTOR;
+use crate::util::format::{units, DEVSTAT_MB};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Pool allocation is sampled at most this often, so the fill-rate window
+/// spans hours rather than the last few refreshes
+const POOL_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Allocation samples kept per pool (6 hours at one a minute)
+const POOL_SAMPLES: usize = 360;
+
+/// Span of samples needed before a pool's fill rate is shown
+const POOL_TREND_MIN: Duration = Duration::from_secs(600);
 
 /// Minimum history size to ensure some data is always available
 const MIN_HISTORY_SIZE: usize = 60;
 
+/// Default number of samples retained per series, independent of terminal width
+pub const DEFAULT_MAX_HISTORY: usize = 3600;
+
+/// Number of events kept in the event log
+const MAX_EVENTS: usize = 100;
+
+/// Topology updates to wait before reporting insertions; GEOM needs two
+/// snapshots before disks show up, so early arrivals are just startup
+const INSERT_EVENT_WARMUP: u64 = 4;
+
+/// How long a drive whose ZFS role or state changed flashes in the UI
+const TRANSITION_FLASH: Duration = Duration::from_secs(10);
+
+/// Bays drawn in the front panel (and reachable with the bay cursor)
+const BAY_SLOTS: usize = 25;
+
+/// Busy% above which a drive counts as active for --idle-dim
+const IDLE_BUSY_PCT: f64 = 1.0;
+
+/// IOPS above which a path's bay LED counts the sample as activity
+const ACTIVITY_LED_MIN_IOPS: f64 = 0.1;
+
+/// Busy% at which a path's bay LED stops blinking and stays lit
+const SOLID_LED_BUSY_PCT: f64 = 90.0;
+
+/// A notable change (disk inserted, removed, replaced) for the event log
+#[derive(Clone, Debug)]
+pub struct LogEvent {
+    pub at: Instant,
+    pub message: String,
+}
+
+/// Array health at a glance, for the header badge
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthScore {
+    #[default]
+    Healthy,
+    /// Redundancy reduced: single path, degraded vdev, link down, warning sensor
+    Degraded,
+    /// Something is out of service: failed multipath, faulted member, failed PSU
+    Failed,
+}
+
+impl HealthScore {
+    pub fn label(self) -> &'static str {
+        match self {
+            HealthScore::Healthy => "HEALTHY",
+            HealthScore::Degraded => "DEGRADED",
+            HealthScore::Failed => "FAILED",
+        }
+    }
+}
+
+/// A locate LED change asked for from the UI, carried out by the collection loop
+#[derive(Clone, Debug)]
+pub struct LocateRequest {
+    pub device: String,      // Multipath device in the selected bay
+    pub paths: Vec<String>,  // Its da* paths, as SES reports them
+    pub on: bool,
+}
+
+/// Layout of the per-drive stats panel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DriveView {
+    /// One row per drive
+    #[default]
+    Flat,
+    /// pool -> vdev -> member drives
+    Grouped,
+    /// pool -> vdev rollups only
+    GroupedCollapsed,
+}
+
+impl DriveView {
+    pub fn next(self) -> Self {
+        match self {
+            DriveView::Flat => DriveView::Grouped,
+            DriveView::Grouped => DriveView::GroupedCollapsed,
+            DriveView::GroupedCollapsed => DriveView::Flat,
+        }
+    }
+}
+
+/// Metric traced by the per-drive sparklines
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DriveSparkline {
+    #[default]
+    Busy,
+    ReadIops,
+    WriteIops,
+}
+
+impl DriveSparkline {
+    pub fn next(self) -> Self {
+        match self {
+            DriveSparkline::Busy => DriveSparkline::ReadIops,
+            DriveSparkline::ReadIops => DriveSparkline::WriteIops,
+            DriveSparkline::WriteIops => DriveSparkline::Busy,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DriveSparkline::Busy => "busy%",
+            DriveSparkline::ReadIops => "read IOPS",
+            DriveSparkline::WriteIops => "write IOPS",
+        }
+    }
+}
+
+/// What the drive bay borders are colored by
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BayColoring {
+    /// Busy% and I/O activity
+    #[default]
+    Activity,
+    /// A stable color per ZFS pool
+    Pool,
+}
+
+impl BayColoring {
+    pub fn toggle(self) -> Self {
+        match self {
+            BayColoring::Activity => BayColoring::Pool,
+            BayColoring::Pool => BayColoring::Activity,
+        }
+    }
+}
+
+/// Ordering of the flat per-drive list
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DriveSort {
+    /// Physical SES slot, matching the bay strip
+    #[default]
+    Slot,
+    /// Busy% descending
+    Busy,
+    /// Total IOPS descending
+    Iops,
+    /// Total MB/s descending
+    Bandwidth,
+    /// Worse of read/write latency descending
+    Latency,
+}
+
+impl DriveSort {
+    pub fn next(self) -> Self {
+        match self {
+            DriveSort::Slot => DriveSort::Busy,
+            DriveSort::Busy => DriveSort::Iops,
+            DriveSort::Iops => DriveSort::Bandwidth,
+            DriveSort::Bandwidth => DriveSort::Latency,
+            DriveSort::Latency => DriveSort::Slot,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DriveSort::Slot => "slot",
+            DriveSort::Busy => "busy",
+            DriveSort::Iops => "IOPS",
+            DriveSort::Bandwidth => units().mb_label(),
+            DriveSort::Latency => "latency",
+        }
+    }
+}
+
+/// How tightly panels are packed on screen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Density {
+    /// Full borders and panel titles
+    #[default]
+    Comfortable,
+    /// Single-line separators, no titles, multi-column drive list
+    Dense,
+}
+
+impl Density {
+    pub fn toggle(self) -> Self {
+        match self {
+            Density::Comfortable => Density::Dense,
+            Density::Dense => Density::Comfortable,
+        }
+    }
+
+    /// Rows a panel spends on its border
+    pub fn border_rows(self) -> u16 {
+        match self {
+            Density::Comfortable => 2,
+            Density::Dense => 1,
+        }
+    }
+}
+
+/// Which of the two panel sections get the screen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Maximized {
+    /// System overview above the drive array
+    #[default]
+    Neither,
+    /// Drive array and storage charts only ('o')
+    Storage,
+    /// System overview only ('O')
+    System,
+}
+
+impl Maximized {
+    /// Switch to `section` alone, or back to both if it already is
+    pub fn toggle(self, section: Maximized) -> Self {
+        if self == section {
+            Maximized::Neither
+        } else {
+            section
+        }
+    }
+}
+
+/// One allocation sample of a pool
+#[derive(Clone, Debug)]
+struct PoolSample {
+    at: Instant,
+    alloc_bytes: u64,
+    fragmentation_pct: Option<f64>,
+}
+
+/// How a pool's usage moved over the retained samples
+#[derive(Clone, Debug)]
+pub struct PoolTrend {
+    pub usage: PoolUsage,
+    /// Net growth (negative when shrinking); None until enough time has passed
+    pub fill_bytes_per_hour: Option<f64>,
+    /// Fragmentation change in percentage points over the same window
+    pub fragmentation_change: Option<f64>,
+}
+
+impl PoolTrend {
+    /// Naive time until full at the current fill rate (None unless growing)
+    pub fn time_to_full(&self) -> Option<Duration> {
+        let rate = self.fill_bytes_per_hour.filter(|&r| r > 0.0)?;
+        Duration::try_from_secs_f64(self.usage.free_bytes as f64 / rate * 3600.0).ok()
+    }
+}
+
+/// Bytes moved by one drive since sanview started
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DriveTotals {
+    pub name: String,      // Device name the drive was last seen under
+    pub read_bytes: f64,
+    pub write_bytes: f64,
+}
+
+/// Which network rate the interface list shows; the chart always plots raw samples
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NetRate {
+    /// Exponentially smoothed, steadier to read
+    #[default]
+    Smoothed,
+    /// Per-interval rate, matches the chart peaks
+    Raw,
+}
+
+impl NetRate {
+    pub fn toggle(self) -> Self {
+        match self {
+            NetRate::Smoothed => NetRate::Raw,
+            NetRate::Raw => NetRate::Smoothed,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NetRate::Smoothed => "smoothed",
+            NetRate::Raw => "raw",
+        }
+    }
+
+    /// (rx, tx) bytes/sec for an interface in this mode
+    pub fn rates(self, iface: &NetworkStats) -> (f64, f64) {
+        match self {
+            NetRate::Smoothed => (iface.rx_bytes_per_sec, iface.tx_bytes_per_sec),
+            NetRate::Raw => (iface.rx_bytes_per_sec_raw, iface.tx_bytes_per_sec_raw),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AppState {
     pub multipath_devices: Vec<MultipathDevice>,
@@ -15,11 +319,47 @@ pub struct AppState {
     pub network_stats: Vec<NetworkStats>,
     pub vms: Vec<VmInfo>,
     pub jails: Vec<JailInfo>,
+    pub zil_stats: Option<ZilStats>,       // None without ZFS
+    pub enclosure_health: Vec<EnclosureHealth>, // SES PSUs, fans, sensors (slow cadence)
+    pub pool_usage: Vec<PoolUsage>,        // zpool list (slow cadence)
+    pub datasets: Vec<DatasetUsage>,       // zfs list (slow cadence)
+    pub hostname: Option<String>,          // Monitored host (the server's under --connect)
+    pub notice: Option<String>,            // Collection problem shown in the header
     pub last_update: Instant,
+    pub last_update_wall: SystemTime,      // Same moment on the wall clock, for --clock absolute
+    pub last_active: Instant,              // Last update with a drive above IDLE_BUSY_PCT
     pub should_quit: bool,
+    pub drive_view: DriveView,
+    pub drive_sparkline: DriveSparkline,
+    pub drive_sort: DriveSort,
+    pub bay_coloring: BayColoring,
+    pub cpu_breakdown: bool,               // Per-core user/system/interrupt bars
+    pub density: Density,
+    pub maximized: Maximized,
+    pub latency_histogram: bool,           // Latency chart shows the distribution instead
+    pub net_rate: NetRate,
+    pub net_pps: bool,                     // Network list shows packets/sec instead of bytes/sec
+    pub synthetic_slots: bool,             // No SES slots: bays numbered by device name instead
+    pub expected_paths: usize,             // Working paths a multipath device should have
+    redundancy_lost: HashSet<String>,      // Multipath devices below expected_paths
+    pub alerts_only: bool,                 // Problems-only view in place of the panels
+    pub hide_idle: bool,                   // Drive list leaves out drives below the activity cutoff
+    pub datasets_view: bool,               // Dataset space list in place of the panels
+    pub help_view: bool,                   // Key list in place of the panels
+    pub remote_source: bool,               // Snapshots from --connect/--simulate, no collection loop
+    pub refreshing_topology: bool,         // Forced topology refresh in progress
+    topology_refresh_requested: bool,      // Set by the UI, taken by the collection loop
+    pub selected_slot: Option<usize>,      // Bay cursor (0-based, as drawn), moved with Left/Right
+    pub locating: HashSet<String>,         // Drives whose locate LED sanview turned on
+    pub activity_leds: HashMap<String, bool>, // Lit state of each active path's bay LED
+    locate_requested: Option<LocateRequest>, // Set by 'l', taken by the collection loop
 
-    // Dynamic history size based on terminal width
-    history_size: usize,
+    // Points visible in a full-width chart (terminal width * 2 for braille)
+    display_points: usize,
+    // Samples retained per series; charts slice the tail they can show
+    max_history: usize,
+    // Samples averaged into each chart point (1 = live view)
+    pub zoom_factor: usize,
 
     // Historical data for sparklines
     pub cpu_history: Vec<VecDeque<f64>>,  // Per-core history
@@ -37,12 +377,62 @@ pub struct AppState {
     pub storage_write_latency_history: VecDeque<f64>, // Write latency ms
     pub storage_queue_depth_history: VecDeque<f64>,   // Queue depth
     pub storage_busy_history: VecDeque<f64>,        // Avg busy %
+    pub storage_max_busy_history: VecDeque<f64>,    // Busiest device's busy %
+    pub storage_hot_drives_history: VecDeque<f64>,  // Devices at or above BUSY_ALERT_PCT
 
-    // Per-drive busy % history for individual sparklines
+    // Per-drive history for individual sparklines
     pub drive_busy_history: HashMap<String, VecDeque<f64>>,
+    pub drive_read_iops_history: HashMap<String, VecDeque<f64>>,
+    pub drive_write_iops_history: HashMap<String, VecDeque<f64>>,
+    pub drive_read_bw_history: HashMap<String, VecDeque<f64>>,
+    pub drive_write_bw_history: HashMap<String, VecDeque<f64>>,
+    pub drive_read_latency_history: HashMap<String, VecDeque<f64>>,   // For the selected drive's percentiles
+    pub drive_write_latency_history: HashMap<String, VecDeque<f64>>,
+
+    // Last known disk identity per drive name, kept after a drive disappears
+    // so a different disk reusing the name is recognized as a replacement
+    drive_idents: HashMap<String, String>,
+    topology_updates: u64,
+
+    // Last ZFS membership per drive name, and when drives whose role or
+    // state changed stop flashing
+    drive_zfs: HashMap<String, ZfsDriveInfo>,
+    transitions: HashMap<String, Instant>,
+
+    // Drive identities in display order; drives keep their row across
+    // refreshes, new ones are appended and vanished ones dropped
+    drive_order: Vec<String>,
+    pub drive_positions: HashMap<String, usize>,  // Drive name -> display row
+
+    // Cumulative bytes per drive, keyed by disk ident (name if none) so a
+    // renamed device keeps its count; integrated from the per-interval rates
+    pub drive_totals: HashMap<String, DriveTotals>,
+    pub session_read_bytes: f64,
+    pub session_write_bytes: f64,
+    totals_sampled: Option<Instant>,
 
     // Network interface history (combined RX+TX bytes/sec)
     pub network_history: HashMap<String, VecDeque<f64>>,
+
+    // Per-pool allocation, one sample per POOL_SAMPLE_INTERVAL
+    pool_history: HashMap<String, VecDeque<PoolSample>>,
+
+    // Most recent last
+    pub events: VecDeque<LogEvent>,
+}
+
+/// Without any SES slot (no enclosure, or the scan failed) number the
+/// multipath devices 1, 2, ... by name so the bays still fill; returns
+/// whether it did, so the UI can mark the numbers as logical
+fn assign_synthetic_slots(multipath_devices: &mut [MultipathDevice]) -> bool {
+    if multipath_devices.is_empty() || multipath_devices.iter().any(|d| d.slot.is_some()) {
+        return false;
+    }
+    multipath_devices.sort_by(|a, b| a.name.cmp(&b.name));
+    for (index, device) in multipath_devices.iter_mut().enumerate() {
+        device.slot = Some(index + 1);
+    }
+    true
 }
 
 impl Default for AppState {
@@ -55,9 +445,43 @@ impl Default for AppState {
             network_stats: Vec::new(),
             vms: Vec::new(),
             jails: Vec::new(),
+            zil_stats: None,
+            enclosure_health: Vec::new(),
+            pool_usage: Vec::new(),
+            datasets: Vec::new(),
+            hostname: None,
+            notice: None,
             last_update: Instant::now(),
+            last_update_wall: SystemTime::now(),
+            last_active: Instant::now(),
             should_quit: false,
-            history_size: MIN_HISTORY_SIZE,
+            drive_view: DriveView::default(),
+            drive_sparkline: DriveSparkline::default(),
+            drive_sort: DriveSort::default(),
+            bay_coloring: BayColoring::default(),
+            cpu_breakdown: false,
+            density: Density::default(),
+            maximized: Maximized::default(),
+            latency_histogram: false,
+            net_rate: NetRate::default(),
+            net_pps: false,
+            synthetic_slots: false,
+            expected_paths: 2,
+            redundancy_lost: HashSet::new(),
+            alerts_only: false,
+            hide_idle: false,
+            datasets_view: false,
+            help_view: false,
+            remote_source: false,
+            refreshing_topology: false,
+            topology_refresh_requested: false,
+            selected_slot: None,
+            locating: HashSet::new(),
+            activity_leds: HashMap::new(),
+            locate_requested: None,
+            display_points: MIN_HISTORY_SIZE,
+            max_history: DEFAULT_MAX_HISTORY,
+            zoom_factor: 1,
             cpu_history: Vec::new(),
             cpu_aggregate_history: VecDeque::new(),
             memory_history: VecDeque::new(),
@@ -71,8 +495,28 @@ impl Default for AppState {
             storage_write_latency_history: VecDeque::new(),
             storage_queue_depth_history: VecDeque::new(),
             storage_busy_history: VecDeque::new(),
+            storage_max_busy_history: VecDeque::new(),
+            storage_hot_drives_history: VecDeque::new(),
             drive_busy_history: HashMap::new(),
+            drive_read_iops_history: HashMap::new(),
+            drive_write_iops_history: HashMap::new(),
+            drive_read_bw_history: HashMap::new(),
+            drive_write_bw_history: HashMap::new(),
+            drive_read_latency_history: HashMap::new(),
+            drive_write_latency_history: HashMap::new(),
+            drive_idents: HashMap::new(),
+            drive_zfs: HashMap::new(),
+            transitions: HashMap::new(),
+            topology_updates: 0,
+            drive_order: Vec::new(),
+            drive_positions: HashMap::new(),
+            drive_totals: HashMap::new(),
+            session_read_bytes: 0.0,
+            session_write_bytes: 0.0,
+            totals_sampled: None,
             network_history: HashMap::new(),
+            pool_history: HashMap::new(),
+            events: VecDeque::new(),
         }
     }
 }
@@ -82,29 +526,72 @@ impl AppState {
         Self::default()
     }
 
-    /// Update history size based on terminal width
-    /// Pre-fills storage history buffers with zeros on first call so charts scroll from start
+    /// State retaining up to `max_history` samples per series
+    pub fn with_max_history(max_history: usize) -> Self {
+        Self {
+            max_history: max_history.max(MIN_HISTORY_SIZE),
+            ..Self::default()
+        }
+    }
+
+    pub fn max_history(&self) -> usize {
+        self.max_history
+    }
+
+    /// Samples to keep: the configured retention, or a full screen if wider
+    fn retention(&self) -> usize {
+        self.max_history.max(self.display_points)
+    }
+
+    /// Update the chart display window based on terminal width
+    ///
+    /// Every existing history, per-drive, per-core and per-interface ones
+    /// included, is padded with leading gaps (`HISTORY_GAP`) up to the new
+    /// window so charts scroll from the start, and trimmed to the new retention when the
+    /// terminal shrinks below the configured history.
     pub fn set_terminal_width(&mut self, width: u16) {
         let new_size = (width as usize * 2).max(MIN_HISTORY_SIZE); // *2 for braille resolution
-
-        // Pre-fill histories if they're empty (first call) so charts scroll from start
-        if self.storage_read_iops_history.is_empty() {
-            self.storage_read_iops_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_write_iops_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_read_bw_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_write_bw_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_read_latency_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_write_latency_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_queue_depth_history = VecDeque::from(vec![0.0; new_size]);
-            self.storage_busy_history = VecDeque::from(vec![0.0; new_size]);
+        if new_size == self.display_points && !self.storage_busy_history.is_empty() {
+            return;
         }
+        self.display_points = new_size;
+        let retention = self.retention();
 
-        // Pre-fill CPU aggregate history
-        if self.cpu_aggregate_history.is_empty() {
-            self.cpu_aggregate_history = VecDeque::from(vec![0.0; new_size]);
-        }
+        let fit = |history: &mut VecDeque<f64>| {
+            while history.len() < new_size {
+                history.push_front(HISTORY_GAP);
+            }
+            Self::trim_history(history, retention);
+        };
 
-        self.history_size = new_size;
+        for history in [
+            &mut self.storage_read_iops_history,
+            &mut self.storage_write_iops_history,
+            &mut self.storage_read_bw_history,
+            &mut self.storage_write_bw_history,
+            &mut self.storage_read_latency_history,
+            &mut self.storage_write_latency_history,
+            &mut self.storage_queue_depth_history,
+            &mut self.storage_busy_history,
+            &mut self.storage_max_busy_history,
+            &mut self.storage_hot_drives_history,
+            &mut self.cpu_aggregate_history,
+        ] {
+            fit(history);
+        }
+        self.cpu_history.iter_mut().for_each(fit);
+        for histories in [
+            &mut self.drive_busy_history,
+            &mut self.drive_read_iops_history,
+            &mut self.drive_write_iops_history,
+            &mut self.drive_read_bw_history,
+            &mut self.drive_write_bw_history,
+            &mut self.drive_read_latency_history,
+            &mut self.drive_write_latency_history,
+            &mut self.network_history,
+        ] {
+            histories.values_mut().for_each(fit);
+        }
     }
 
     fn trim_history<T>(history: &mut VecDeque<T>, max_size: usize) {
@@ -113,12 +600,121 @@ impl AppState {
         }
     }
 
+    /// Append a sample to a per-drive history, pre-filling new entries with
+    /// gaps so the sparkline scrolls from the start
+    fn push_drive_sample(
+        histories: &mut HashMap<String, VecDeque<f64>>,
+        name: &str,
+        value: f64,
+        prefill: usize,
+        max_size: usize,
+    ) {
+        let history = histories
+            .entry(name.to_string())
+            .or_insert_with(|| VecDeque::from(vec![HISTORY_GAP; prefill]));
+        history.push_back(value);
+        Self::trim_history(history, max_size);
+    }
+
+    /// Step every active path's bay LED for this sample
+    ///
+    /// Like a real drive LED, it changes only when the path does I/O: each
+    /// sample with I/O flips it, so occasional I/O flickers irregularly and
+    /// steady I/O blinks at the refresh rate, while a pegged drive stays lit
+    /// and an idle one stays dark.
+    fn update_activity_leds(&mut self, devices: &[MultipathDevice]) {
+        let mut leds = HashMap::new();
+        for path in devices.iter().flat_map(|d| &d.path_stats).filter(|p| p.is_active) {
+            let stats = &path.statistics;
+            let lit = if stats.busy_pct >= SOLID_LED_BUSY_PCT {
+                true
+            } else if stats.total_iops() > ACTIVITY_LED_MIN_IOPS {
+                !self.activity_leds.get(&path.device_name).copied().unwrap_or(false)
+            } else {
+                false
+            };
+            leds.insert(path.device_name.clone(), lit);
+        }
+        self.activity_leds = leds;
+    }
+
+    /// Drives to flash for a recent ZFS role or state change
+    pub fn flashing_drives(&self) -> HashSet<String> {
+        let now = Instant::now();
+        self.transitions
+            .iter()
+            .filter(|(_, until)| **until > now)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    fn push_event(&mut self, message: String) {
+        log::info!("{}", message);
+        self.events.push_back(LogEvent {
+            at: Instant::now(),
+            message,
+        });
+        Self::trim_history(&mut self.events, MAX_EVENTS);
+    }
+
+    fn clear_drive_history(&mut self, name: &str) {
+        self.drive_busy_history.remove(name);
+        self.drive_read_iops_history.remove(name);
+        self.drive_write_iops_history.remove(name);
+        self.drive_read_bw_history.remove(name);
+        self.drive_write_bw_history.remove(name);
+        self.drive_read_latency_history.remove(name);
+        self.drive_write_latency_history.remove(name);
+    }
+
+    /// Rebuild `drive_positions`, keeping known drives where they were
+    ///
+    /// Multipath labels are the disk serial, so the name is the identity;
+    /// standalone daN numbers can move between boots or rescans, so those
+    /// are tracked by GEOM ident. A batch of new drives is appended in slot
+    /// then name order.
+    fn update_drive_order(&mut self, multipath_devices: &[MultipathDevice], standalone_disks: &[PhysicalDisk]) {
+        let mut current: Vec<(&String, &String, Option<usize>)> = multipath_devices
+            .iter()
+            .map(|d| (&d.name, &d.name, d.slot))
+            .chain(
+                standalone_disks
+                    .iter()
+                    .filter(|d| d.zfs_info.is_some())
+                    .map(|d| (d.ident.as_ref().unwrap_or(&d.device_name), &d.device_name, d.slot)),
+            )
+            .collect();
+
+        self.drive_order.retain(|key| current.iter().any(|(k, _, _)| *k == key));
+        current.sort_by(|a, b| (a.2.is_none(), a.2, a.1).cmp(&(b.2.is_none(), b.2, b.1)));
+        for (key, _, _) in &current {
+            if !self.drive_order.contains(key) {
+                self.drive_order.push((*key).clone());
+            }
+        }
+
+        self.drive_positions = current
+            .iter()
+            .filter_map(|(key, name, _)| {
+                let position = self.drive_order.iter().position(|k| k == *key)?;
+                Some(((*name).clone(), position))
+            })
+            .collect();
+    }
+
     pub fn update_topology(
         &mut self,
-        multipath_devices: Vec<MultipathDevice>,
+        mut multipath_devices: Vec<MultipathDevice>,
         standalone_disks: Vec<PhysicalDisk>,
     ) {
-        let history_size = self.history_size;
+        self.synthetic_slots = assign_synthetic_slots(&mut multipath_devices);
+        let history_size = self.retention();
+        let prefill = self.display_points;
+
+        // Interval the current rates cover, for the cumulative byte counters
+        let now = Instant::now();
+        let interval_secs = self.totals_sampled.map_or(0.0, |t| now.duration_since(t).as_secs_f64());
+        self.totals_sampled = Some(now);
 
         // Calculate aggregate stats from multipath devices only (no double counting)
         let total_read_iops: f64 = multipath_devices.iter().map(|d| d.statistics.read_iops).sum();
@@ -150,6 +746,15 @@ impl AppState {
             (0.0, 0.0)
         };
 
+        let active = multipath_devices
+            .iter()
+            .map(|d| &d.statistics)
+            .chain(standalone_disks.iter().map(|d| &d.statistics))
+            .any(|s| s.busy_pct > IDLE_BUSY_PCT);
+        if active {
+            self.last_active = now;
+        }
+
         // Sum queue depths
         let total_queue_depth: f64 = multipath_devices.iter().map(|d| d.statistics.queue_depth).sum();
 
@@ -158,6 +763,13 @@ impl AppState {
         } else {
             0.0
         };
+        // The mean hides a single saturated drive on a wide array; the
+        // busiest device and the count of hot ones show the bottleneck
+        let max_busy = multipath_devices.iter().map(|d| d.statistics.busy_pct).fold(0.0, f64::max);
+        let hot_drives = multipath_devices
+            .iter()
+            .filter(|d| d.statistics.busy_pct >= BUSY_ALERT_PCT)
+            .count();
 
         // Update storage history
         self.storage_read_iops_history.push_back(total_read_iops);
@@ -184,38 +796,160 @@ impl AppState {
         self.storage_busy_history.push_back(avg_busy);
         Self::trim_history(&mut self.storage_busy_history, history_size);
 
-        // Update per-drive busy % history
-        for device in &multipath_devices {
-            let history = self.drive_busy_history
-                .entry(device.name.clone())
-                .or_insert_with(|| {
-                    // Pre-fill with zeros so sparkline scrolls from start
-                    VecDeque::from(vec![0.0; history_size])
-                });
+        self.storage_max_busy_history.push_back(max_busy);
+        Self::trim_history(&mut self.storage_max_busy_history, history_size);
 
-            history.push_back(device.statistics.busy_pct);
-            Self::trim_history(history, history_size);
+        self.storage_hot_drives_history.push_back(hot_drives as f64);
+        Self::trim_history(&mut self.storage_hot_drives_history, history_size);
+
+        // Update per-drive history (multipath devices plus standalone pool members)
+        self.topology_updates += 1;
+        let drives = multipath_devices.iter()
+            .map(|d| (&d.name, tracked_ident(d), d.slot, &d.statistics))
+            .chain(standalone_disks.iter()
+                .filter(|d| d.zfs_info.is_some())
+                .map(|d| (&d.device_name, d.ident.as_ref(), d.slot, &d.statistics)));
+        for (name, ident, slot, stats) in drives {
+            let location = slot.map_or_else(|| name.clone(), |s| format!("slot {}", s));
+
+            if let Some(ident) = ident {
+                match self.drive_idents.insert(name.clone(), ident.clone()) {
+                    Some(previous) if previous != *ident => {
+                        self.clear_drive_history(name);
+                        self.push_event(format!(
+                            "Disk replaced in {}: {} ({} -> {})",
+                            location, name, previous, ident
+                        ));
+                    }
+                    None if self.topology_updates > INSERT_EVENT_WARMUP => {
+                        self.push_event(format!("Disk inserted in {}: {} ({})", location, name, ident));
+                    }
+                    _ => {}
+                }
+            }
+
+            let read_bytes = stats.read_bw_mbps * DEVSTAT_MB * interval_secs;
+            let write_bytes = stats.write_bw_mbps * DEVSTAT_MB * interval_secs;
+            let totals = self.drive_totals.entry(totals_key(ident, name).clone()).or_default();
+            totals.name.clone_from(name);
+            totals.read_bytes += read_bytes;
+            totals.write_bytes += write_bytes;
+            self.session_read_bytes += read_bytes;
+            self.session_write_bytes += write_bytes;
+
+            Self::push_drive_sample(&mut self.drive_busy_history, name, stats.busy_pct, prefill, history_size);
+            Self::push_drive_sample(&mut self.drive_read_iops_history, name, stats.read_iops, prefill, history_size);
+            Self::push_drive_sample(&mut self.drive_write_iops_history, name, stats.write_iops, prefill, history_size);
+            Self::push_drive_sample(&mut self.drive_read_bw_history, name, stats.read_bw_mbps, prefill, history_size);
+            Self::push_drive_sample(&mut self.drive_write_bw_history, name, stats.write_bw_mbps, prefill, history_size);
+            Self::push_drive_sample(&mut self.drive_read_latency_history, name, stats.read_latency_ms, prefill, history_size);
+            Self::push_drive_sample(&mut self.drive_write_latency_history, name, stats.write_latency_ms, prefill, history_size);
+        }
+
+        // Losing one of two paths leaves gmultipath OPTIMAL, so count them
+        for mp in &multipath_devices {
+            let location = mp.slot.map_or_else(|| mp.name.clone(), |s| format!("slot {}", s));
+            if mp.lacks_redundancy(self.expected_paths) {
+                if self.redundancy_lost.insert(mp.name.clone()) {
+                    self.push_event(format!(
+                        "Path redundancy lost in {}: {} ({} of {} paths)",
+                        location,
+                        mp.name,
+                        mp.working_paths(),
+                        self.expected_paths
+                    ));
+                }
+            } else if self.redundancy_lost.remove(&mp.name) {
+                self.push_event(format!("Path redundancy restored in {}: {}", location, mp.name));
+            }
         }
+        self.redundancy_lost.retain(|name| multipath_devices.iter().any(|d| &d.name == name));
+
+        // ZFS role and state changes; a spare taking over from a failed
+        // drive recovers the pool silently, so it gets its own event
+        let members = multipath_devices
+            .iter()
+            .map(|d| (&d.name, d.slot, d.zfs_info.as_ref()))
+            .chain(standalone_disks.iter().map(|d| (&d.device_name, d.slot, d.zfs_info.as_ref())));
+        let mut members_seen = HashSet::new();
+        for (name, slot, zfs) in members {
+            let Some(zfs) = zfs else {
+                continue;
+            };
+            members_seen.insert(name.clone());
+            let Some(previous) = self.drive_zfs.insert(name.clone(), zfs.clone()) else {
+                continue;
+            };
+            if previous.role == zfs.role && previous.state == zfs.state {
+                continue;
+            }
+            let location = slot.map_or_else(|| name.clone(), |s| format!("slot {}", s));
+            let message = if spare_activated(&previous, zfs) {
+                format!("Spare activated in {}: {} ({} {})", location, name, zfs.pool, zfs.state)
+            } else if previous.role != zfs.role {
+                format!(
+                    "ZFS role changed in {}: {} ({:?} -> {:?})",
+                    location, name, previous.role, zfs.role
+                )
+            } else {
+                format!(
+                    "ZFS state changed in {}: {} ({} -> {})",
+                    location, name, previous.state, zfs.state
+                )
+            };
+            self.push_event(message);
+            self.transitions.insert(name.clone(), now + TRANSITION_FLASH);
+        }
+        self.drive_zfs.retain(|name, _| members_seen.contains(name));
+        self.transitions.retain(|_, until| *until > now);
+
+        self.update_drive_order(&multipath_devices, &standalone_disks);
 
         // Clean up history for devices that no longer exist
-        self.drive_busy_history.retain(|name, _| {
+        let still_present = |name: &String| {
             multipath_devices.iter().any(|d| &d.name == name)
-        });
+                || standalone_disks.iter().any(|d| d.zfs_info.is_some() && &d.device_name == name)
+        };
+        let removed: Vec<String> = self.drive_busy_history
+            .keys()
+            .filter(|name| !still_present(name))
+            .cloned()
+            .collect();
+        for name in removed {
+            let slot = self.multipath_devices.iter()
+                .find(|d| d.name == name)
+                .and_then(|d| d.slot)
+                .or_else(|| self.standalone_disks.iter().find(|d| d.device_name == name).and_then(|d| d.slot));
+            let location = slot.map_or_else(|| name.clone(), |s| format!("slot {}", s));
+            self.push_event(format!("Disk removed from {}: {}", location, name));
+        }
+        self.drive_busy_history.retain(|name, _| still_present(name));
+        self.drive_read_iops_history.retain(|name, _| still_present(name));
+        self.drive_write_iops_history.retain(|name, _| still_present(name));
+        self.drive_read_bw_history.retain(|name, _| still_present(name));
+        self.drive_write_bw_history.retain(|name, _| still_present(name));
+        self.drive_read_latency_history.retain(|name, _| still_present(name));
+        self.drive_write_latency_history.retain(|name, _| still_present(name));
 
+        self.update_activity_leds(&multipath_devices);
         self.multipath_devices = multipath_devices;
         self.standalone_disks = standalone_disks;
         self.last_update = Instant::now();
+        self.last_update_wall = SystemTime::now();
     }
 
+    /// Store this cycle's system stats; VMs and jails are only replaced
+    /// when `Some` (they are re-collected on the slow cadence)
     pub fn update_system_stats(
         &mut self,
         cpu_stats: CpuStats,
         memory_stats: MemoryStats,
         network_stats: Vec<NetworkStats>,
-        vms: Vec<VmInfo>,
-        jails: Vec<JailInfo>,
+        vms: Option<Vec<VmInfo>>,
+        jails: Option<Vec<JailInfo>>,
     ) {
-        let history_size = self.history_size;
+        let history_size = self.retention();
+        let prefill = self.display_points;
 
         // Initialize CPU history if needed
         if self.cpu_history.len() != cpu_stats.cores.len() {
@@ -258,8 +992,8 @@ impl AppState {
             let history = self.network_history
                 .entry(iface.name.clone())
                 .or_insert_with(|| {
-                    // Pre-fill with zeros so chart scrolls from start
-                    VecDeque::from(vec![0.0; history_size])
+                    // Pre-fill with gaps so chart scrolls from start
+                    VecDeque::from(vec![HISTORY_GAP; prefill])
                 });
             history.push_back(total_bw_raw);
             Self::trim_history(history, history_size);
@@ -274,11 +1008,372 @@ impl AppState {
         self.cpu_stats = Some(cpu_stats);
         self.memory_stats = Some(memory_stats);
         self.network_stats = network_stats;
-        self.vms = vms;
-        self.jails = jails;
+        if let Some(vms) = vms {
+            self.vms = vms;
+        }
+        if let Some(jails) = jails {
+            self.jails = jails;
+        }
+    }
+
+    /// Store the latest pool usage and add it to the fill-rate history
+    pub fn update_pool_usage(&mut self, usage: Vec<PoolUsage>) {
+        let now = Instant::now();
+        for pool in &usage {
+            let history = self.pool_history.entry(pool.pool.clone()).or_default();
+            if history.back().is_some_and(|s| now.duration_since(s.at) < POOL_SAMPLE_INTERVAL) {
+                continue;
+            }
+            history.push_back(PoolSample {
+                at: now,
+                alloc_bytes: pool.alloc_bytes,
+                fragmentation_pct: pool.fragmentation_pct,
+            });
+            if history.len() > POOL_SAMPLES {
+                history.pop_front();
+            }
+        }
+        self.pool_history.retain(|name, _| usage.iter().any(|p| &p.pool == name));
+        self.pool_usage = usage;
+    }
+
+    /// Usage and fill rate per pool, from the oldest retained sample to now
+    pub fn pool_trends(&self) -> HashMap<String, PoolTrend> {
+        self.pool_usage
+            .iter()
+            .map(|usage| {
+                let first = self.pool_history.get(&usage.pool).and_then(|h| h.front());
+                let span = first.map(|s| s.at.elapsed()).unwrap_or_default();
+                let (fill_bytes_per_hour, fragmentation_change) = match first {
+                    Some(first) if span >= POOL_TREND_MIN => (
+                        Some(
+                            (usage.alloc_bytes as f64 - first.alloc_bytes as f64)
+                                / (span.as_secs_f64() / 3600.0),
+                        ),
+                        usage.fragmentation_pct.zip(first.fragmentation_pct).map(|(now, then)| now - then),
+                    ),
+                    _ => (None, None),
+                };
+                let trend = PoolTrend {
+                    usage: usage.clone(),
+                    fill_bytes_per_hour,
+                    fragmentation_change,
+                };
+                (usage.pool.clone(), trend)
+            })
+            .collect()
+    }
+
+    /// Show a shorter time window (down to one sample per point)
+    pub fn zoom_in(&mut self) {
+        self.zoom_factor = (self.zoom_factor / 2).max(1);
+    }
+
+    /// Show a longer time window, until the window covers all retained history
+    pub fn zoom_out(&mut self) {
+        if self.zoom_factor < MAX_ZOOM_FACTOR && self.display_points * self.zoom_factor < self.max_history {
+            self.zoom_factor *= 2;
+        }
+    }
+
+    pub fn cycle_drive_view(&mut self) {
+        self.drive_view = self.drive_view.next();
+    }
+
+    pub fn cycle_drive_sort(&mut self) {
+        self.drive_sort = self.drive_sort.next();
+    }
+
+    pub fn toggle_bay_coloring(&mut self) {
+        self.bay_coloring = self.bay_coloring.toggle();
+    }
+
+    pub fn toggle_cpu_breakdown(&mut self) {
+        self.cpu_breakdown = !self.cpu_breakdown;
+    }
+
+    pub fn toggle_density(&mut self) {
+        self.density = self.density.toggle();
+    }
+
+    pub fn toggle_maximized(&mut self, section: Maximized) {
+        self.maximized = self.maximized.toggle(section);
+    }
+
+    pub fn toggle_latency_histogram(&mut self) {
+        self.latency_histogram = !self.latency_histogram;
+    }
+
+    /// Bytes a multipath device has moved since startup, under the same key
+    /// they are accumulated by
+    pub fn drive_totals_for(&self, drive: &MultipathDevice) -> Option<&DriveTotals> {
+        self.drive_totals.get(totals_key(tracked_ident(drive), &drive.name))
+    }
+
+    /// Drive that has written the most since startup
+    pub fn top_writer(&self) -> Option<&DriveTotals> {
+        self.drive_totals
+            .values()
+            .filter(|t| t.write_bytes > 0.0)
+            .max_by(|a, b| a.write_bytes.total_cmp(&b.write_bytes))
+    }
+
+    pub fn toggle_net_rate(&mut self) {
+        self.net_rate = self.net_rate.toggle();
+    }
+
+    pub fn toggle_net_pps(&mut self) {
+        self.net_pps = !self.net_pps;
+    }
+
+    pub fn toggle_alerts_only(&mut self) {
+        self.alerts_only = !self.alerts_only;
+        self.datasets_view = false;
+    }
+
+    pub fn toggle_hide_idle(&mut self) {
+        self.hide_idle = !self.hide_idle;
+    }
+
+    pub fn toggle_datasets_view(&mut self) {
+        self.datasets_view = !self.datasets_view;
+        self.alerts_only = false;
+    }
+
+    pub fn toggle_help_view(&mut self) {
+        self.help_view = !self.help_view;
+    }
+
+    pub fn cycle_drive_sparkline(&mut self) {
+        self.drive_sparkline = self.drive_sparkline.next();
+    }
+
+    /// Per-drive history backing the currently selected sparkline metric
+    pub fn drive_sparkline_history(&self) -> &HashMap<String, VecDeque<f64>> {
+        match self.drive_sparkline {
+            DriveSparkline::Busy => &self.drive_busy_history,
+            DriveSparkline::ReadIops => &self.drive_read_iops_history,
+            DriveSparkline::WriteIops => &self.drive_write_iops_history,
+        }
+    }
+
+    /// Replace each drive's IOPS, bandwidth and busy% with the mean of its
+    /// last `samples` history entries, so the numbers hold still at fast
+    /// refresh rates; the histories (and so the sparklines) keep every sample
+    ///
+    /// Returns the live statistics, in drive order, for `restore_drive_stats`
+    /// to put back once the frame is drawn.
+    pub fn average_drive_stats(&mut self, samples: usize) -> Vec<DiskStatistics> {
+        if samples <= 1 {
+            return Vec::new();
+        }
+        let Self {
+            multipath_devices,
+            standalone_disks,
+            drive_busy_history,
+            drive_read_iops_history,
+            drive_write_iops_history,
+            drive_read_bw_history,
+            drive_write_bw_history,
+            ..
+        } = self;
+
+        let drives = multipath_devices
+            .iter_mut()
+            .map(|d| (&d.name, &mut d.statistics))
+            .chain(standalone_disks.iter_mut().map(|d| (&d.device_name, &mut d.statistics)));
+        let mut live = Vec::new();
+        for (name, stats) in drives {
+            live.push(stats.clone());
+            let tail_mean = |histories: &HashMap<String, VecDeque<f64>>| {
+                mean(histories.get(name)?.iter().rev().take(samples).copied())
+            };
+            for (histories, value) in [
+                (&*drive_read_iops_history, &mut stats.read_iops),
+                (&*drive_write_iops_history, &mut stats.write_iops),
+                (&*drive_read_bw_history, &mut stats.read_bw_mbps),
+                (&*drive_write_bw_history, &mut stats.write_bw_mbps),
+                (&*drive_busy_history, &mut stats.busy_pct),
+            ] {
+                if let Some(average) = tail_mean(histories) {
+                    *value = average;
+                }
+            }
+        }
+        live
+    }
+
+    /// Undo `average_drive_stats` with the statistics it returned
+    pub fn restore_drive_stats(&mut self, live: Vec<DiskStatistics>) {
+        let drives = self
+            .multipath_devices
+            .iter_mut()
+            .map(|d| &mut d.statistics)
+            .chain(self.standalone_disks.iter_mut().map(|d| &mut d.statistics));
+        for (stats, live) in drives.zip(live) {
+            *stats = live;
+        }
+    }
+
+    /// Ask the collection loop to bypass the topology caches on its next cycle
+    pub fn request_topology_refresh(&mut self) {
+        if self.remote_source {
+            self.push_event("Topology refresh is not available with --connect or --simulate".to_string());
+            return;
+        }
+        self.topology_refresh_requested = true;
+    }
+
+    /// Collection-loop side: returns true once per request and marks the refresh in progress
+    pub fn take_topology_refresh(&mut self) -> bool {
+        if !self.topology_refresh_requested {
+            return false;
+        }
+        self.topology_refresh_requested = false;
+        self.refreshing_topology = true;
+        true
+    }
+
+    /// Called after a topology update; ends a forced refresh if one was running
+    pub fn finish_topology_refresh(&mut self) {
+        if self.refreshing_topology {
+            self.refreshing_topology = false;
+            self.push_event("Topology refreshed".to_string());
+        }
+    }
+
+    /// Whether a forced refresh is pending or running (for the header)
+    pub fn topology_refresh_pending(&self) -> bool {
+        self.topology_refresh_requested || self.refreshing_topology
+    }
+
+    /// Whether no drive has been above the activity threshold for `after`
+    pub fn is_idle(&self, after: Duration) -> bool {
+        self.last_active.elapsed() >= after
+    }
+
+    /// Worst condition across multipaths, pool members, links and enclosures
+    ///
+    /// Uses the same thresholds as the alerts view, except that busy drives
+    /// don't count: a hot array isn't an unhealthy one.
+    pub fn health_score(&self) -> HealthScore {
+        let multipaths = self.multipath_devices.iter().map(|mp| match mp.state {
+            MultipathState::Failed => HealthScore::Failed,
+            MultipathState::Degraded => HealthScore::Degraded,
+            _ if mp.lacks_redundancy(self.expected_paths) => HealthScore::Degraded,
+            MultipathState::Optimal | MultipathState::Unknown => HealthScore::Healthy,
+        });
+        let members = self
+            .multipath_devices
+            .iter()
+            .filter_map(|mp| mp.zfs_info.as_ref())
+            .chain(self.standalone_disks.iter().filter_map(|d| d.zfs_info.as_ref()))
+            .map(|zfs| match zfs_state_severity(&zfs.state) {
+                Some(Severity::Critical) => HealthScore::Failed,
+                Some(Severity::Warning) => HealthScore::Degraded,
+                None => HealthScore::Healthy,
+            });
+        let links = self.network_stats.iter().map(|iface| {
+            if iface.link_state == LINK_STATE_DOWN {
+                HealthScore::Degraded
+            } else {
+                HealthScore::Healthy
+            }
+        });
+        let enclosures = self.enclosure_health.iter().map(|health| match health.worst_status() {
+            ElementStatus::NonCritical => HealthScore::Degraded,
+            status if status.is_fault() => HealthScore::Failed,
+            _ => HealthScore::Healthy,
+        });
+
+        multipaths
+            .chain(members)
+            .chain(links)
+            .chain(enclosures)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Multipath device in the bay under the cursor
+    pub fn selected_drive(&self) -> Option<&MultipathDevice> {
+        // Bays are drawn from multipath devices; slots are 1-based
+        let slot = self.selected_slot?;
+        self.multipath_devices.iter().find(|d| d.slot == Some(slot + 1))
+    }
+
+    /// Move the bay cursor; the first press selects the leftmost bay
+    pub fn move_bay_cursor(&mut self, delta: isize) {
+        self.selected_slot = Some(match self.selected_slot {
+            Some(slot) => slot.saturating_add_signed(delta).min(BAY_SLOTS - 1),
+            None => 0,
+        });
+    }
+
+    /// Ask the collection loop to switch the locate LED of the selected bay
+    pub fn request_locate_toggle(&mut self) {
+        if self.remote_source {
+            self.push_event("Locate is not available with --connect or --simulate".to_string());
+            return;
+        }
+        let Some(slot) = self.selected_slot else {
+            self.push_event("Select a bay with Left/Right before locating".to_string());
+            return;
+        };
+        let Some(device) = self.selected_drive() else {
+            self.push_event(format!("No drive in bay {}", slot + 1));
+            return;
+        };
+        self.locate_requested = Some(LocateRequest {
+            device: device.name.clone(),
+            paths: device.paths.clone(),
+            on: !self.locating.contains(&device.name),
+        });
+    }
+
+    /// Collection-loop side: the pending locate request, once
+    pub fn take_locate_request(&mut self) -> Option<LocateRequest> {
+        self.locate_requested.take()
+    }
+
+    /// Record the outcome of a locate request in the event log
+    pub fn finish_locate(&mut self, request: LocateRequest, result: Result<String>) {
+        let action = if request.on { "on" } else { "off" };
+        match result {
+            Ok(element) => {
+                if request.on {
+                    self.locating.insert(request.device.clone());
+                } else {
+                    self.locating.remove(&request.device);
+                }
+                self.push_event(format!("Locate {} for {} ({})", action, request.device, element));
+            }
+            Err(e) => {
+                self.push_event(format!("Locate {} failed for {}: {:#}", action, request.device, e));
+            }
+        }
     }
 
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
 }
+
+/// A hot spare that went from standing by to covering for a failed drive:
+/// it leaves the spares section for a data vdev, or zpool marks it INUSE
+fn spare_activated(previous: &ZfsDriveInfo, current: &ZfsDriveInfo) -> bool {
+    previous.role == ZfsRole::Spare
+        && previous.state != "INUSE"
+        && (current.role != ZfsRole::Spare || current.state == "INUSE")
+}
+
+/// Ident a multipath device's identity and totals are tracked by. Devices
+/// whose paths are all gone report a placeholder ident, so it only counts
+/// while a path is present.
+fn tracked_ident(device: &MultipathDevice) -> Option<&String> {
+    device.ident.as_ref().filter(|_| !device.paths.is_empty())
+}
+
+/// Key of a drive's `drive_totals` entry: its ident, or its name without one
+fn totals_key<'a>(ident: Option<&'a String>, name: &'a String) -> &'a String {
+    ident.unwrap_or(name)
+}
@@ -0,0 +1,216 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer};
+
+/// Semantic color palette used by every UI component
+///
+/// Components never hardcode a `Color`; they pick the role that matches
+/// what they draw, so a different palette (e.g. for light terminals) can be
+/// swapped in without touching call sites.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub accent: Color,    // Panel borders, titles, key hints
+    pub text: Color,      // Primary text and values
+    pub muted: Color,     // Secondary text, idle indicators, separators
+    pub good: Color,      // Healthy / low load / read activity
+    pub warn: Color,      // Elevated load / write activity
+    pub critical: Color,  // Saturated / failed
+    pub info: Color,      // Auxiliary series (ARC, spares)
+    pub highlight: Color, // Mixed activity, cache devices
+    pub pools: [Color; 6], // Bay tints in pool coloring mode ('c')
+    pub charts: ChartColors, // Per-series overrides from the config's [colors]
+}
+
+/// A chart series whose color the config can set
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChartSeries {
+    ReadIops,
+    WriteIops,
+    Throughput,
+    Latency,
+    QueueDepth,
+    BusyMax,
+    BusyMean,
+    Cpu,
+    Network,
+}
+
+/// Chart series colors from the config's `[colors]` section, each a color
+/// name ("lightblue"), a 256-color index ("208") or "#rrggbb"; a series left
+/// out keeps its theme role's color
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChartColors {
+    #[serde(deserialize_with = "color")]
+    pub read_iops: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub write_iops: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub throughput: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub latency: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub queue_depth: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub busy_max: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub busy_mean: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub cpu: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub network: Option<Color>,
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    name.parse()
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("unknown color \"{}\"", name)))
+}
+
+impl Theme {
+    /// Default palette for dark-background terminals
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::Cyan,
+            text: Color::White,
+            muted: Color::DarkGray,
+            good: Color::Green,
+            warn: Color::Yellow,
+            critical: Color::Red,
+            info: Color::Blue,
+            highlight: Color::Magenta,
+            pools: [
+                Color::Cyan,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightBlue,
+                Color::LightMagenta,
+                Color::LightRed,
+            ],
+            charts: ChartColors::default(),
+        }
+    }
+
+    /// Palette for light-background terminals: black text, a mid-gray that
+    /// stays readable on white, and darker variants of cyan/yellow
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            text: Color::Black,
+            muted: Color::Indexed(244),
+            good: Color::Indexed(28),
+            warn: Color::Indexed(130),
+            critical: Color::Red,
+            info: Color::Indexed(25),
+            highlight: Color::Magenta,
+            pools: [
+                Color::Indexed(30),
+                Color::Indexed(28),
+                Color::Indexed(130),
+                Color::Indexed(25),
+                Color::Indexed(90),
+                Color::Indexed(124),
+            ],
+            charts: ChartColors::default(),
+        }
+    }
+
+    /// No colors at all: every role resets to the terminal's default
+    /// foreground, for NO_COLOR, dumb terminals and script(1) captures
+    pub fn monochrome() -> Self {
+        Self {
+            accent: Color::Reset,
+            text: Color::Reset,
+            muted: Color::Reset,
+            good: Color::Reset,
+            warn: Color::Reset,
+            critical: Color::Reset,
+            info: Color::Reset,
+            highlight: Color::Reset,
+            pools: [Color::Reset; 6],
+            charts: ChartColors::default(),
+        }
+    }
+
+    /// The same palette faded to gray for an idle array (--idle-dim), so
+    /// a wall display only draws the eye when something is happening;
+    /// critical keeps its color since a failure matters even when quiet
+    pub fn dimmed(&self) -> Self {
+        Self {
+            accent: self.muted,
+            text: self.muted,
+            muted: self.muted,
+            good: self.muted,
+            warn: self.muted,
+            critical: self.critical,
+            info: self.muted,
+            highlight: self.muted,
+            pools: [self.muted; 6],
+            charts: ChartColors::default(),
+        }
+    }
+
+    /// The same palette with the config's chart colors; monochrome stays
+    /// colorless, since it stands for NO_COLOR or a dumb terminal
+    pub fn with_chart_colors(self, charts: ChartColors) -> Self {
+        if self == Self::monochrome() {
+            return self;
+        }
+        Self { charts, ..self }
+    }
+
+    /// Color of a chart series: the configured one, else its role's
+    pub fn chart(&self, series: ChartSeries) -> Color {
+        let (configured, role) = match series {
+            ChartSeries::ReadIops => (self.charts.read_iops, self.good),
+            ChartSeries::WriteIops => (self.charts.write_iops, self.warn),
+            ChartSeries::Throughput => (self.charts.throughput, self.good),
+            ChartSeries::Latency => (self.charts.latency, self.warn),
+            ChartSeries::QueueDepth => (self.charts.queue_depth, self.highlight),
+            ChartSeries::BusyMax => (self.charts.busy_max, self.critical),
+            ChartSeries::BusyMean => (self.charts.busy_mean, self.good),
+            ChartSeries::Cpu => (self.charts.cpu, self.accent),
+            ChartSeries::Network => (self.charts.network, self.accent),
+        };
+        configured.unwrap_or(role)
+    }
+
+    /// Tint for a pool, stable across runs and hosts: an FNV-1a hash of
+    /// the name picks from the pool palette
+    pub fn pool_color(&self, pool: &str) -> Color {
+        let hash = pool
+            .bytes()
+            .fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+        self.pools[(hash % self.pools.len() as u64) as usize]
+    }
+
+    /// Whether the environment asks for no color: NO_COLOR set to any
+    /// non-empty value (https://no-color.org) or TERM=dumb
+    pub fn color_disabled() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+            || std::env::var("TERM").is_ok_and(|t| t == "dumb")
+    }
+
+    /// Pick a palette from the environment: monochrome if color is
+    /// disabled, otherwise from the terminal's COLORFGBG hint ("fg;bg"),
+    /// which rxvt, Konsole and iTerm set. Background 7 or 15 means a light
+    /// terminal.
+    pub fn detect() -> Self {
+        if Self::color_disabled() {
+            return Self::monochrome();
+        }
+
+        let light = std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|v| v.rsplit(';').next().map(str::to_string))
+            .is_some_and(|bg| bg == "7" || bg == "15");
+
+        if light { Self::light() } else { Self::dark() }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
@@ -0,0 +1,58 @@
+//! Time window for the history charts
+//!
+//! Charts normally plot one sample per braille column. Zooming out folds
+//! `factor` consecutive samples into one plotted point (averaged), so the
+//! same width covers a longer stretch of the retained history.
+
+use crate::domain::statistics::{mean, HISTORY_GAP};
+use std::collections::VecDeque;
+
+/// Largest samples-per-point factor offered by zoom out
+pub const MAX_ZOOM_FACTOR: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Zoom {
+    pub factor: usize,    // Samples averaged into each plotted point
+    pub sample_ms: u64,   // Interval between samples (the refresh rate)
+}
+
+impl Zoom {
+    pub fn new(factor: usize, sample_ms: u64) -> Self {
+        Self {
+            factor: factor.max(1),
+            sample_ms,
+        }
+    }
+
+    /// The newest `points * factor` samples, averaged down to at most `points`
+    ///
+    /// Buckets are aligned to the newest sample so the rightmost point always
+    /// includes the latest value; a partial bucket at the old end is dropped.
+    /// A bucket averages its real samples and is a gap only if it has none.
+    pub fn tail(&self, history: &VecDeque<f64>, points: usize) -> Vec<f64> {
+        if self.factor == 1 {
+            let start = history.len().saturating_sub(points);
+            return history.iter().skip(start).copied().collect();
+        }
+
+        let buckets = (history.len() / self.factor).min(points);
+        let start = history.len() - buckets * self.factor;
+        let samples: Vec<f64> = history.iter().skip(start).copied().collect();
+        samples
+            .chunks(self.factor)
+            .map(|c| mean(c.iter().copied()).unwrap_or(HISTORY_GAP))
+            .collect()
+    }
+
+    /// Human-readable span covered by `points` plotted points, e.g. "80s", "10m"
+    pub fn window_label(&self, points: usize) -> String {
+        let secs = (points * self.factor) as u64 * self.sample_ms / 1000;
+        if secs >= 7200 {
+            format!("{}h", secs / 3600)
+        } else if secs >= 120 {
+            format!("{}m", secs / 60)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Byte, bandwidth and count formatting
+//!
+//! Selected once at startup like the glyph set: SI units (the default) use
+//! powers of 1000 and label bandwidth "MB/s", `--units iec` uses powers of
+//! 1024 and labels it "MiB/s". Every size and rate on screen goes through
+//! here so memory, network and disk figures agree with each other. Wall-clock
+//! timestamps are formatted here too, in the local time zone.
+
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+static UNITS: OnceLock<Units> = OnceLock::new();
+
+/// devstat reports bandwidth in units of 2^20 bytes
+pub const DEVSTAT_MB: f64 = 1_048_576.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    /// Decimal: K = 1000, M = 10^6 (what network tools report)
+    #[default]
+    Si,
+    /// Binary: Ki = 1024, Mi = 2^20 (what top and zfs report)
+    Iec,
+}
+
+impl Units {
+    fn base(self) -> f64 {
+        match self {
+            Units::Si => 1000.0,
+            Units::Iec => 1024.0,
+        }
+    }
+
+    /// Prefixes for successive powers of the base
+    fn prefixes(self) -> [&'static str; 5] {
+        match self {
+            Units::Si => ["", "K", "M", "G", "T"],
+            Units::Iec => ["", "Ki", "Mi", "Gi", "Ti"],
+        }
+    }
+
+    /// Label for disk bandwidth figures
+    pub fn mb_label(self) -> &'static str {
+        match self {
+            Units::Si => "MB/s",
+            Units::Iec => "MiB/s",
+        }
+    }
+}
+
+/// Choose the unit convention; only the first call has an effect
+pub fn select(units: Units) {
+    let _ = UNITS.set(units);
+}
+
+/// The selected unit convention (SI if none was selected)
+pub fn units() -> Units {
+    *UNITS.get_or_init(Units::default)
+}
+
+/// Byte count scaled to a prefix: "512B", "1.5G" (SI) or "1.5Gi" (IEC)
+///
+/// Steps up at 1000 of a unit under either convention so the number keeps
+/// at most three integer digits and fits the same column width.
+pub fn format_bytes(bytes: f64) -> String {
+    format_bytes_with(bytes, units())
+}
+
+fn format_bytes_with(bytes: f64, units: Units) -> String {
+    let prefixes = units.prefixes();
+    let mut value = bytes;
+    let mut power = 0;
+    // Step up before the value would round to 1000 at its precision
+    let rounds_to_1000 = |value: f64, power: usize| value >= if power == 0 { 999.5 } else { 999.95 };
+    while rounds_to_1000(value, power) && power < prefixes.len() - 1 {
+        value /= units.base();
+        power += 1;
+    }
+    if power == 0 {
+        format!("{:.0}B", value)
+    } else {
+        format!("{:.1}{}", value, prefixes[power])
+    }
+}
+
+/// Bytes per second with the same prefixes: "1.5G/s"
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec))
+}
+
+/// Plain count with k/M/G suffixes for large values (always decimal)
+pub fn format_count(v: f64) -> String {
+    // Thresholds sit where the smaller unit would round up to the next
+    if v >= 999_950_000.0 {
+        format!("{:.1}G", v / 1_000_000_000.0)
+    } else if v >= 999_950.0 {
+        format!("{:.1}M", v / 1_000_000.0)
+    } else if v >= 999.5 {
+        format!("{:.1}k", v / 1_000.0)
+    } else if v >= 9.95 {
+        format!("{:.0}", v)
+    } else {
+        format!("{:.1}", v)
+    }
+}
+
+/// Packet rate with pps/Kpps/Mpps suffixes, at most 7 chars (always decimal)
+pub fn format_pps(pps: f64) -> String {
+    if pps >= 10_000_000.0 {
+        format!("{:.0}Mpps", pps / 1_000_000.0)
+    } else if pps >= 1_000_000.0 {
+        format!("{:.1}Mpps", pps / 1_000_000.0)
+    } else if pps >= 10_000.0 {
+        format!("{:.0}Kpps", pps / 1_000.0)
+    } else if pps >= 1_000.0 {
+        format!("{:.1}Kpps", pps / 1_000.0)
+    } else {
+        format!("{:.0}pps", pps)
+    }
+}
+
+/// 5-char IOPS cell, switching to thousands above 10k
+pub fn format_iops(iops: f64) -> String {
+    if iops >= 10000.0 {
+        format!("{:>4.0}k", iops / 1000.0)
+    } else {
+        format!("{:>5.0}", iops)
+    }
+}
+
+/// 5-char disk bandwidth cell in the selected unit, switching to G above 1000
+pub fn format_mbps(devstat_mbps: f64) -> String {
+    format_mbps_with(devstat_mbps, units())
+}
+
+fn format_mbps_with(devstat_mbps: f64, units: Units) -> String {
+    let mbps = disk_mbps_with(devstat_mbps, units);
+    if mbps >= 999.95 {
+        format!("{:>4.1}G", mbps / units.base())
+    } else {
+        format!("{:>5.1}", mbps)
+    }
+}
+
+/// Disk bandwidth from devstat's MB/s in the selected unit (see `mb_label`)
+pub fn disk_mbps(devstat_mbps: f64) -> f64 {
+    disk_mbps_with(devstat_mbps, units())
+}
+
+fn disk_mbps_with(devstat_mbps: f64, units: Units) -> f64 {
+    devstat_mbps * DEVSTAT_MB / units.base().powi(2)
+}
+
+/// Rough length of time in its largest unit: "45m", "12h", "35d", "3y"
+pub fn format_span(secs: f64) -> String {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = 3600.0;
+    const DAY: f64 = 86400.0;
+    const YEAR: f64 = 365.0 * DAY;
+    if secs >= YEAR {
+        format!("{:.0}y", secs / YEAR)
+    } else if secs >= DAY {
+        format!("{:.0}d", secs / DAY)
+    } else if secs >= HOUR {
+        format!("{:.0}h", secs / HOUR)
+    } else {
+        format!("{:.0}m", (secs / MINUTE).max(1.0))
+    }
+}
+
+/// Local "YYYY-MM-DD HH:MM:SS" for a Unix time in milliseconds
+pub fn format_datetime(timestamp_ms: u64) -> String {
+    match local_tm(timestamp_ms) {
+        Some(tm) => format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            tm.tm_year + 1900,
+            tm.tm_mon + 1,
+            tm.tm_mday,
+            tm.tm_hour,
+            tm.tm_min,
+            tm.tm_sec
+        ),
+        None => (timestamp_ms / 1000).to_string(),
+    }
+}
+
+/// Local "HH:MM:SS" for a Unix time in milliseconds
+pub fn format_clock(timestamp_ms: u64) -> String {
+    match local_tm(timestamp_ms) {
+        Some(tm) => format!("{:02}:{:02}:{:02}", tm.tm_hour, tm.tm_min, tm.tm_sec),
+        None => (timestamp_ms / 1000).to_string(),
+    }
+}
+
+fn local_tm(timestamp_ms: u64) -> Option<libc::tm> {
+    let secs = (timestamp_ms / 1000) as libc::time_t;
+    // SAFETY: localtime_r only writes the tm we pass it
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    (!unsafe { libc::localtime_r(&secs, &mut tm) }.is_null()).then_some(tm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_step_up_at_a_thousand_of_either_unit() {
+        assert_eq!(format_bytes_with(999.0, Units::Si), "999B");
+        assert_eq!(format_bytes_with(1000.0, Units::Si), "1.0K");
+        assert_eq!(format_bytes_with(1024.0, Units::Si), "1.0K");
+        assert_eq!(format_bytes_with(999.0, Units::Iec), "999B");
+        assert_eq!(format_bytes_with(1000.0, Units::Iec), "1.0Ki");
+        assert_eq!(format_bytes_with(1024.0, Units::Iec), "1.0Ki");
+        assert_eq!(format_bytes_with(1_500_000_000.0, Units::Si), "1.5G");
+        assert_eq!(format_bytes_with(1024.0 * 1024.0, Units::Iec), "1.0Mi");
+    }
+
+    #[test]
+    fn bytes_never_show_four_integer_digits() {
+        assert_eq!(format_bytes_with(999.7, Units::Si), "1.0K");
+        assert_eq!(format_bytes_with(999_990.0, Units::Si), "1.0M");
+        assert_eq!(format_bytes_with(1023.0 * 1024.0, Units::Iec), "1.0Mi");
+        // Past the largest prefix the number just grows
+        assert_eq!(format_bytes_with(2e15, Units::Si), "2000.0T");
+    }
+
+    #[test]
+    fn count_boundaries() {
+        assert_eq!(format_count(0.0), "0.0");
+        assert_eq!(format_count(9.9), "9.9");
+        assert_eq!(format_count(9.99), "10");
+        assert_eq!(format_count(10.0), "10");
+        assert_eq!(format_count(999.0), "999");
+        assert_eq!(format_count(999.7), "1.0k");
+        assert_eq!(format_count(9999.0), "10.0k");
+        assert_eq!(format_count(10000.0), "10.0k");
+        assert_eq!(format_count(999_990.0), "1.0M");
+        assert_eq!(format_count(2_500_000_000.0), "2.5G");
+    }
+
+    #[test]
+    fn iops_cell_is_five_chars() {
+        assert_eq!(format_iops(9.99), "   10");
+        assert_eq!(format_iops(10.0), "   10");
+        assert_eq!(format_iops(9999.0), " 9999");
+        assert_eq!(format_iops(10000.0), "  10k");
+        assert_eq!(format_iops(123_456.0), " 123k");
+    }
+
+    #[test]
+    fn mbps_cell_follows_the_unit() {
+        // devstat's MB is 2^20 bytes
+        assert_eq!(format_mbps_with(1.0, Units::Iec), "  1.0");
+        assert_eq!(format_mbps_with(1.0, Units::Si), "  1.0");
+        assert_eq!(format_mbps_with(100.0, Units::Si), "104.9");
+        assert_eq!(format_mbps_with(999.0, Units::Iec), "999.0");
+        assert_eq!(format_mbps_with(1000.0, Units::Iec), " 1.0G");
+        assert_eq!(format_mbps_with(1024.0, Units::Iec), " 1.0G");
+        assert_eq!(format_mbps_with(953.0, Units::Si), "999.3");
+        assert_eq!(format_mbps_with(954.0, Units::Si), " 1.0G");
+        assert_eq!(format_mbps_with(9.99, Units::Iec), " 10.0");
+        assert_eq!(format_mbps_with(999.96, Units::Iec), " 1.0G");
+        for units in [Units::Si, Units::Iec] {
+            for mbps in [0.0, 9.99, 10.0, 999.0, 1000.0, 1024.0, 9999.0] {
+                assert_eq!(format_mbps_with(mbps, units).len(), 5, "{} {:?}", mbps, units);
+            }
+        }
+    }
+}
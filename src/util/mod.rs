@@ -0,0 +1,3 @@
+//! Helpers shared across the UI and exporters
+
+pub mod format;